@@ -0,0 +1,139 @@
+use criterion::{
+    criterion_group, criterion_main, measurement::WallTime, BenchmarkGroup, BenchmarkId, Criterion,
+    Throughput,
+};
+use fibonacci::fibonacci::{example1, example2};
+use halo2_proofs::{
+    pasta::{EqAffine, Fp},
+    plonk::*,
+    poly::commitment::Params,
+    transcript::{Blake2bRead, Blake2bWrite, Challenge255},
+};
+use rand_core::OsRng;
+
+// both circuits compute the 10th term starting from (1, 2)
+const K: u32 = 4;
+// the k's verification is measured at, with the same circuits in more rows
+const VERIFY_KS: [u32; 4] = [4, 6, 8, 10];
+const ITER_NUM: usize = 10;
+const OUT: u64 = 89;
+
+fn example1_circuit() -> example1::MyCircuit<Fp, ITER_NUM> {
+    example1::MyCircuit {
+        a: Some(Fp::from(1)),
+        b: Some(Fp::from(2)),
+        expose_all: false,
+    }
+}
+
+fn example2_circuit() -> example2::MyCircuit<Fp> {
+    example2::MyCircuit {
+        a: Some(Fp::from(1)),
+        b: Some(Fp::from(2)),
+        iter_num: ITER_NUM,
+    }
+}
+
+fn bench_circuit<C: Circuit<Fp>>(c: &mut Criterion, name: &str, circuit: C) {
+    let params: Params<EqAffine> = Params::new(K);
+    let public_input = vec![Fp::from(OUT)];
+
+    let mut group = c.benchmark_group(name);
+    // one element per computed Fibonacci term
+    group.throughput(Throughput::Elements(ITER_NUM as u64));
+
+    group.bench_function(BenchmarkId::new("keygen", K), |b| {
+        b.iter(|| {
+            let vk = keygen_vk(&params, &circuit).unwrap();
+            keygen_pk(&params, vk, &circuit).unwrap()
+        })
+    });
+
+    let vk = keygen_vk(&params, &circuit).unwrap();
+    let pk = keygen_pk(&params, vk, &circuit).unwrap();
+    group.bench_function(BenchmarkId::new("prove", K), |b| {
+        b.iter(|| {
+            let mut transcript = Blake2bWrite::<_, _, Challenge255<_>>::init(vec![]);
+            create_proof(
+                &params,
+                &pk,
+                std::slice::from_ref(&circuit),
+                &[&[&public_input]],
+                OsRng,
+                &mut transcript,
+            )
+            .unwrap();
+            transcript.finalize()
+        })
+    });
+
+    group.finish();
+}
+
+fn bench_example1(c: &mut Criterion) {
+    bench_circuit(c, "example1", example1_circuit());
+}
+
+fn bench_example2(c: &mut Criterion) {
+    bench_circuit(c, "example2", example2_circuit());
+}
+
+// Verification alone, proofs made beforehand, to compare with the proving time
+// of the groups above.
+//
+// The circuits stay the same, only k changes: the IPA verifier has to check a
+// commitment over all the 2^k rows, a multi-scalar multiplication of size 2^k,
+// so its time grows with k (linearly, unlike KZG's constant pairing check),
+// while staying far below the prover's, which runs FFTs over the extended
+// domain and commits to every column.
+fn bench_verify(c: &mut Criterion) {
+    let mut group = c.benchmark_group("verify");
+    for k in VERIFY_KS {
+        verify_circuit(&mut group, "example1", k, example1_circuit());
+        verify_circuit(&mut group, "example2", k, example2_circuit());
+    }
+    group.finish();
+}
+
+fn verify_circuit<C: Circuit<Fp>>(
+    group: &mut BenchmarkGroup<WallTime>,
+    name: &str,
+    k: u32,
+    circuit: C,
+) {
+    let params: Params<EqAffine> = Params::new(k);
+    let public_input = vec![Fp::from(OUT)];
+
+    let vk = keygen_vk(&params, &circuit).unwrap();
+    let pk = keygen_pk(&params, vk, &circuit).unwrap();
+    let mut transcript = Blake2bWrite::<_, _, Challenge255<_>>::init(vec![]);
+    create_proof(
+        &params,
+        &pk,
+        &[circuit],
+        &[&[&public_input]],
+        OsRng,
+        &mut transcript,
+    )
+    .unwrap();
+    let proof = transcript.finalize();
+
+    group.bench_function(BenchmarkId::new(name, k), |b| {
+        b.iter(|| {
+            let strategy = SingleVerifier::new(&params);
+            let mut transcript = Blake2bRead::<_, _, Challenge255<_>>::init(&proof[..]);
+            verify_proof(
+                &params,
+                pk.get_vk(),
+                strategy,
+                &[&[&public_input]],
+                &mut transcript,
+            )
+            .unwrap()
+        })
+    });
+}
+
+criterion_group!(benches, bench_example1, bench_example2);
+criterion_group!(verify, bench_verify);
+criterion_main!(benches, verify);
@@ -0,0 +1,26 @@
+#![no_main]
+
+use fibonacci::{fibonacci::example2::MyCircuit, io::verify_only};
+use halo2_proofs::{
+    pasta::{EqAffine, Fp},
+    poly::commitment::Params,
+};
+use libfuzzer_sys::fuzz_target;
+use std::sync::OnceLock;
+
+// the params of example2 at k = 4, serialized once
+fn params_bytes() -> &'static [u8] {
+    static PARAMS: OnceLock<Vec<u8>> = OnceLock::new();
+    PARAMS.get_or_init(|| {
+        let mut bytes = vec![];
+        Params::<EqAffine>::new(4).write(&mut bytes).unwrap();
+        bytes
+    })
+}
+
+// any bytes as the proof: verify_only must reject them without panicking
+fuzz_target!(|proof: &[u8]| {
+    let public_input = [Fp::from(89)];
+    let result = verify_only(params_bytes(), &MyCircuit::default(), proof, &public_input);
+    assert!(!matches!(result, Ok(true)));
+});
@@ -0,0 +1,2 @@
+pub(crate) mod gate;
+mod inverse;
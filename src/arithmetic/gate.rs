@@ -0,0 +1,230 @@
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+use crate::error::CircuitError;
+
+//
+// q_add | q_mul | col_a | col_b | col_c
+// ------+-------+-------+-------+-------
+//   1   |   0   |   a0  |   b0  | a0 + b0
+//   0   |   1   |   a1  |   b1  | a1 * b1
+//
+// One gate handles both operations, each row turns on exactly one of the selectors:
+//   q_add * (a + b - c) + q_mul * (a * b - c) = 0
+// Simple selectors can't be added together, so the two terms are kept as two
+// constraints of the same gate, which is equivalent since only one selector is on.
+// Operands are copied in from previous results, so operations can be chained,
+// e.g. (2 + 3) * 4 => row 0: 2 + 3 = 5, row 1: 5 * 4 = 20 with c0 == a1.
+
+#[derive(Debug, Clone)]
+pub(crate) struct ArithmeticConfig {
+    pub advice: [Column<Advice>; 3],
+    pub instance: Column<Instance>,
+    pub q_add: Selector,
+    pub q_mul: Selector,
+}
+
+pub(crate) struct ArithmeticChip<F: FieldExt> {
+    config: ArithmeticConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> ArithmeticChip<F> {
+    pub fn construct(config: ArithmeticConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        instance: Column<Instance>,
+    ) -> ArithmeticConfig {
+        let [col_a, col_b, col_c] = advice;
+        let q_add = meta.selector();
+        let q_mul = meta.selector();
+
+        // for copy constraints between operations
+        meta.enable_equality(col_a);
+        meta.enable_equality(col_b);
+        meta.enable_equality(col_c);
+        meta.enable_equality(instance);
+
+        meta.create_gate("arithmetic", |meta| {
+            let q_add = meta.query_selector(q_add);
+            let q_mul = meta.query_selector(q_mul);
+            let a = meta.query_advice(col_a, Rotation::cur());
+            let b = meta.query_advice(col_b, Rotation::cur());
+            let c = meta.query_advice(col_c, Rotation::cur());
+            vec![
+                q_add * (a.clone() + b.clone() - c.clone()),
+                q_mul * (a * b - c),
+            ]
+        });
+
+        ArithmeticConfig {
+            advice,
+            instance,
+            q_add,
+            q_mul,
+        }
+    }
+
+    // witnesses a value so that it can be used as an operand
+    pub fn load_private(
+        &self,
+        mut layouter: impl Layouter<F>,
+        value: Option<F>,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        layouter.assign_region(
+            || "load private",
+            |mut region| {
+                region.assign_advice(
+                    || "private input",
+                    self.config.advice[0],
+                    0,
+                    || value.ok_or(CircuitError::MissingWitness.into()),
+                )
+            },
+        )
+    }
+
+    pub fn add(
+        &self,
+        layouter: impl Layouter<F>,
+        a: &AssignedCell<F, F>,
+        b: &AssignedCell<F, F>,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        self.assign_op(layouter, "add", self.config.q_add, a, b, |a, b| a + b)
+    }
+
+    pub fn mul(
+        &self,
+        layouter: impl Layouter<F>,
+        a: &AssignedCell<F, F>,
+        b: &AssignedCell<F, F>,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        self.assign_op(layouter, "mul", self.config.q_mul, a, b, |a, b| a * b)
+    }
+
+    fn assign_op(
+        &self,
+        mut layouter: impl Layouter<F>,
+        name: &str,
+        selector: Selector,
+        a: &AssignedCell<F, F>,
+        b: &AssignedCell<F, F>,
+        op: impl Fn(F, F) -> F,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        layouter.assign_region(
+            || name,
+            |mut region| {
+                selector.enable(&mut region, 0)?;
+
+                let a = a.copy_advice(|| "a", &mut region, self.config.advice[0], 0)?;
+                let b = b.copy_advice(|| "b", &mut region, self.config.advice[1], 0)?;
+
+                let c_val = a.value().zip(b.value()).map(|(a, b)| op(*a, *b));
+                region.assign_advice(
+                    || "c",
+                    self.config.advice[2],
+                    0,
+                    || c_val.ok_or(CircuitError::MissingWitness.into()),
+                )
+            },
+        )
+    }
+
+    pub fn expose_public(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cell: &AssignedCell<F, F>,
+        row: usize,
+    ) -> Result<(), Error> {
+        layouter.constrain_instance(cell.cell(), self.config.instance, row)
+    }
+}
+
+#[derive(Default)]
+struct MyCircuit<F> {
+    pub a: Option<F>,
+    pub b: Option<F>,
+    pub c: Option<F>,
+}
+
+// computes (a + b) * c
+impl<F: FieldExt> Circuit<F> for MyCircuit<F> {
+    type Config = ArithmeticConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let col_a = meta.advice_column();
+        let col_b = meta.advice_column();
+        let col_c = meta.advice_column();
+        let instance = meta.instance_column();
+
+        ArithmeticChip::configure(meta, [col_a, col_b, col_c], instance)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let chip = ArithmeticChip::construct(config);
+
+        let a = chip.load_private(layouter.namespace(|| "load a"), self.a)?;
+        let b = chip.load_private(layouter.namespace(|| "load b"), self.b)?;
+        let c = chip.load_private(layouter.namespace(|| "load c"), self.c)?;
+
+        let sum = chip.add(layouter.namespace(|| "a + b"), &a, &b)?;
+        let out = chip.mul(layouter.namespace(|| "(a + b) * c"), &sum, &c)?;
+        chip.expose_public(layouter.namespace(|| "out"), &out, 0)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MyCircuit;
+    use halo2_proofs::{dev::MockProver, pasta::Fp};
+
+    #[test]
+    fn test_arithmetic() {
+        let k = 4;
+
+        let circuit = MyCircuit {
+            a: Some(Fp::from(2)),
+            b: Some(Fp::from(3)),
+            c: Some(Fp::from(4)),
+        };
+
+        // (2 + 3) * 4
+        let public_input = vec![Fp::from(20)];
+        let prover = MockProver::run(k, &circuit, vec![public_input]).unwrap();
+        prover.assert_satisfied();
+    }
+
+    #[test]
+    fn test_arithmetic_failed() {
+        let k = 4;
+
+        let circuit = MyCircuit {
+            a: Some(Fp::from(2)),
+            b: Some(Fp::from(3)),
+            c: Some(Fp::from(4)),
+        };
+
+        // 2 + 3 * 4
+        let public_input = vec![Fp::from(14)];
+        let prover = MockProver::run(k, &circuit, vec![public_input]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}
@@ -0,0 +1,207 @@
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+use crate::error::CircuitError;
+
+//
+// selector |  x  |  inv
+// ---------+-----+-------
+//    1     |  x  |  1/x
+//
+//   s * (x * inv - 1) = 0
+//
+// The inverse is assigned as an Assigned<F>, the fraction 1/x, instead of an F.
+// Inverting a field element costs hundreds of multiplications (it's an
+// exponentiation), so rather than inverting every such cell on its own, the
+// prover keeps them as numerator/denominator pairs and inverts all the
+// denominators of the circuit at once at the end (batch inversion: one
+// inversion plus three multiplications per element). Circuits with many divisions, e.g. the slope
+// in elliptic curve additions, save most of the inversions that way.
+//
+// x = 0 has no inverse: the gate can't be satisfied, so assign returns an
+// error before laying anything out.
+
+#[derive(Debug, Clone)]
+struct InverseConfig {
+    pub x: Column<Advice>,
+    pub inv: Column<Advice>,
+    pub instance: Column<Instance>,
+    pub selector: Selector,
+}
+
+struct InverseChip<F: FieldExt> {
+    config: InverseConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> InverseChip<F> {
+    pub fn construct(config: InverseConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        x: Column<Advice>,
+        inv: Column<Advice>,
+        instance: Column<Instance>,
+    ) -> InverseConfig {
+        let selector = meta.selector();
+
+        meta.enable_equality(inv);
+        meta.enable_equality(instance);
+
+        meta.create_gate("inverse", |meta| {
+            let s = meta.query_selector(selector);
+            let x = meta.query_advice(x, Rotation::cur());
+            let inv = meta.query_advice(inv, Rotation::cur());
+            let one = Expression::Constant(F::one());
+            vec![s * (x * inv - one)]
+        });
+
+        InverseConfig {
+            x,
+            inv,
+            instance,
+            selector,
+        }
+    }
+
+    // returns the cell holding 1/x, still as a fraction
+    pub fn assign(
+        &self,
+        mut layouter: impl Layouter<F>,
+        x: Option<F>,
+    ) -> Result<AssignedCell<Assigned<F>, F>, Error> {
+        if x == Some(F::zero()) {
+            return Err(CircuitError::ZeroValue.into());
+        }
+
+        layouter.assign_region(
+            || "inverse",
+            |mut region| {
+                self.config.selector.enable(&mut region, 0)?;
+
+                region.assign_advice(
+                    || "x",
+                    self.config.x,
+                    0,
+                    || x.ok_or(CircuitError::MissingWitness.into()),
+                )?;
+                region.assign_advice(
+                    || "inv",
+                    self.config.inv,
+                    0,
+                    || {
+                        x.map(|x| Assigned::Rational(F::one(), x))
+                            .ok_or(CircuitError::MissingWitness.into())
+                    },
+                )
+            },
+        )
+    }
+
+    pub fn expose_public(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cell: &AssignedCell<Assigned<F>, F>,
+        row: usize,
+    ) -> Result<(), Error> {
+        layouter.constrain_instance(cell.cell(), self.config.instance, row)
+    }
+}
+
+#[derive(Default)]
+struct MyCircuit<F> {
+    pub x: Option<F>,
+}
+
+impl<F: FieldExt> Circuit<F> for MyCircuit<F> {
+    type Config = InverseConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let x = meta.advice_column();
+        let inv = meta.advice_column();
+        let instance = meta.instance_column();
+
+        InverseChip::configure(meta, x, inv, instance)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let chip = InverseChip::construct(config);
+
+        let inv = chip.assign(layouter.namespace(|| "inverse"), self.x)?;
+        chip.expose_public(layouter.namespace(|| "inv"), &inv, 0)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MyCircuit;
+    use crate::fibonacci::prove::prove_and_verify;
+    use halo2_proofs::{
+        arithmetic::Field,
+        dev::MockProver,
+        pasta::Fp,
+        plonk::{Assigned, Error},
+    };
+
+    #[test]
+    fn test_inverse() {
+        let k = 4;
+
+        // what the prover evaluates the fraction to
+        let inv = Assigned::Rational(Fp::one(), Fp::from(4)).evaluate();
+        assert_eq!(Fp::from(4) * inv, Fp::one());
+        assert_eq!(Some(inv), Option::from(Fp::from(4).invert()));
+
+        let circuit = MyCircuit {
+            x: Some(Fp::from(4)),
+        };
+        let prover = MockProver::run(k, &circuit, vec![vec![inv]]).unwrap();
+        prover.assert_satisfied();
+
+        // through keygen and create_proof, where the inversions are batched
+        assert!(prove_and_verify(k, circuit, vec![inv]).unwrap());
+    }
+
+    #[test]
+    fn test_inverse_failed() {
+        let k = 4;
+
+        let circuit = MyCircuit {
+            x: Some(Fp::from(4)),
+        };
+
+        // 1/2 instead of 1/4
+        let public_input = vec![Fp::from(2).invert().unwrap()];
+        let prover = MockProver::run(k, &circuit, vec![public_input]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn test_inverse_zero() {
+        let k = 4;
+
+        let circuit = MyCircuit {
+            x: Some(Fp::zero()),
+        };
+        assert!(matches!(
+            MockProver::run(k, &circuit, vec![vec![Fp::zero()]]),
+            Err(Error::Synthesis)
+        ));
+    }
+}
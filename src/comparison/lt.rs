@@ -0,0 +1,242 @@
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+use crate::error::CircuitError;
+use crate::range_check::example::{RangeCheckChip, RangeCheckConfig};
+
+//
+// selector |  a  |  b  | lt | diff
+// ---------+-----+-----+----+------
+//    1     |  a  |  b  | lt | a - b + lt * RANGE
+//
+// Checks a < b for a, b in [0, RANGE), where RANGE is a power of two 2^N.
+// a - b + RANGE lies in [1, 2 * RANGE), so it's an (N + 1)-bit number whose top
+// bit is 0 iff a < b. The top bit is witnessed as 1 - lt and the remaining N
+// bits as diff:
+//   a - b + RANGE = (1 - lt) * RANGE + diff
+//   => s * (a - b + lt * RANGE - diff) = 0
+//      s * lt * (1 - lt) = 0
+// and diff is range checked to [0, RANGE) with the range check chip. With the
+// wrong lt, diff would have to be a - b + RANGE or a - b, which are out of the
+// range when a < b and a >= b respectively.
+//
+// a and b go through the range check chip too, the argument above doesn't hold
+// for larger values.
+
+#[derive(Debug, Clone)]
+struct LtConfig<const RANGE: usize> {
+    pub a: Column<Advice>,
+    pub b: Column<Advice>,
+    pub lt: Column<Advice>,
+    pub diff: Column<Advice>,
+    pub selector: Selector,
+    pub range: RangeCheckConfig<RANGE>,
+}
+
+struct LtChip<F: FieldExt, const RANGE: usize> {
+    config: LtConfig<RANGE>,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt, const RANGE: usize> LtChip<F, RANGE> {
+    pub fn construct(config: LtConfig<RANGE>) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        a: Column<Advice>,
+        b: Column<Advice>,
+        lt: Column<Advice>,
+        diff: Column<Advice>,
+        value: Column<Advice>,
+    ) -> LtConfig<RANGE> {
+        assert!(RANGE.is_power_of_two());
+
+        let selector = meta.selector();
+
+        meta.enable_equality(a);
+        meta.enable_equality(b);
+        meta.enable_equality(lt);
+        meta.enable_equality(diff);
+
+        meta.create_gate("lt", |meta| {
+            let s = meta.query_selector(selector);
+            let a = meta.query_advice(a, Rotation::cur());
+            let b = meta.query_advice(b, Rotation::cur());
+            let lt = meta.query_advice(lt, Rotation::cur());
+            let diff = meta.query_advice(diff, Rotation::cur());
+            let range = Expression::Constant(F::from(RANGE as u64));
+            let one = Expression::Constant(F::one());
+            vec![
+                s.clone() * (a - b + lt.clone() * range - diff),
+                s * lt.clone() * (one - lt),
+            ]
+        });
+
+        LtConfig {
+            a,
+            b,
+            lt,
+            diff,
+            selector,
+            range: RangeCheckChip::configure(meta, value),
+        }
+    }
+
+    // returns the cell holding 1 if a < b, 0 otherwise
+    pub fn assign(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: Option<F>,
+        b: Option<F>,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        let range_chip = RangeCheckChip::<F, RANGE>::construct(self.config.range.clone());
+        let a_cell = range_chip.assign(layouter.namespace(|| "a"), a)?;
+        let b_cell = range_chip.assign(layouter.namespace(|| "b"), b)?;
+
+        let (lt_cell, diff_cell) = layouter.assign_region(
+            || "lt",
+            |mut region| {
+                self.config.selector.enable(&mut region, 0)?;
+
+                a_cell.copy_advice(|| "a", &mut region, self.config.a, 0)?;
+                b_cell.copy_advice(|| "b", &mut region, self.config.b, 0)?;
+
+                // both are below RANGE, so the lower 128 bits are the whole value
+                let lt = a.zip(b).map(|(a, b)| {
+                    if a.get_lower_128() < b.get_lower_128() {
+                        F::one()
+                    } else {
+                        F::zero()
+                    }
+                });
+                let lt_cell = region.assign_advice(
+                    || "lt",
+                    self.config.lt,
+                    0,
+                    || lt.ok_or(CircuitError::MissingWitness.into()),
+                )?;
+
+                let diff = a
+                    .zip(b)
+                    .zip(lt)
+                    .map(|((a, b), lt)| a - b + lt * F::from(RANGE as u64));
+                let diff_cell = region.assign_advice(
+                    || "diff",
+                    self.config.diff,
+                    0,
+                    || diff.ok_or(CircuitError::MissingWitness.into()),
+                )?;
+
+                Ok((lt_cell, diff_cell))
+            },
+        )?;
+
+        range_chip.check(layouter.namespace(|| "diff"), &diff_cell)?;
+
+        Ok(lt_cell)
+    }
+
+    pub fn expose_public(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cell: &AssignedCell<F, F>,
+        instance: Column<Instance>,
+        row: usize,
+    ) -> Result<(), Error> {
+        layouter.constrain_instance(cell.cell(), instance, row)
+    }
+}
+
+#[derive(Default)]
+struct MyCircuit<F, const RANGE: usize> {
+    pub a: Option<F>,
+    pub b: Option<F>,
+}
+
+impl<F: FieldExt, const RANGE: usize> Circuit<F> for MyCircuit<F, RANGE> {
+    type Config = (LtConfig<RANGE>, Column<Instance>);
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let a = meta.advice_column();
+        let b = meta.advice_column();
+        let lt = meta.advice_column();
+        let diff = meta.advice_column();
+        let value = meta.advice_column();
+        let instance = meta.instance_column();
+
+        meta.enable_equality(instance);
+
+        (LtChip::configure(meta, a, b, lt, diff, value), instance)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let (config, instance) = config;
+        let chip = LtChip::<F, RANGE>::construct(config);
+
+        let lt = chip.assign(layouter.namespace(|| "a < b"), self.a, self.b)?;
+        chip.expose_public(layouter.namespace(|| "out"), &lt, instance, 0)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MyCircuit;
+    use halo2_proofs::{dev::MockProver, pasta::Fp};
+
+    // 3-bit values
+    const RANGE: usize = 8;
+
+    fn circuit(a: u64, b: u64) -> MyCircuit<Fp, RANGE> {
+        MyCircuit {
+            a: Some(Fp::from(a)),
+            b: Some(Fp::from(b)),
+        }
+    }
+
+    #[test]
+    fn test_lt() {
+        let k = 4;
+
+        for (a, b, lt) in [(3, 5, 1), (5, 3, 0), (4, 4, 0), (0, 7, 1), (7, 0, 0)] {
+            let public_input = vec![Fp::from(lt)];
+            let prover = MockProver::run(k, &circuit(a, b), vec![public_input]).unwrap();
+            prover.assert_satisfied();
+        }
+    }
+
+    #[test]
+    fn test_lt_failed() {
+        let k = 4;
+
+        // claims 5 < 3
+        let public_input = vec![Fp::one()];
+        let prover = MockProver::run(k, &circuit(5, 3), vec![public_input]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn test_lt_out_of_range() {
+        let k = 4;
+
+        // 9 doesn't fit in 3 bits
+        let public_input = vec![Fp::one()];
+        let prover = MockProver::run(k, &circuit(3, 9), vec![public_input]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}
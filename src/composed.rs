@@ -0,0 +1,3 @@
+mod fib_range;
+mod fib_sum;
+mod mode_switch;
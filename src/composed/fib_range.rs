@@ -0,0 +1,119 @@
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use crate::fibonacci::{
+    example2::{FiboChip, FiboConfig},
+    FiboInstructions,
+};
+use crate::range_check::example::{RangeCheckChip, RangeCheckConfig};
+
+//
+// fibonacci (example2)       range check
+//
+// selector |     col    |    selector | value
+// ---------+------------+    ---------+-------
+//   s0     |     a0     |      s      |  out   <- copied from the last row
+//   ...    |    ...     |
+//          | out = a9   |
+//
+// Two independent chips configured on the same ConstraintSystem: the last term
+// of the Fibonacci table is copied into the range check chip, which asserts
+// it's in [0, RANGE). The output is also exposed as public input.
+//
+// NOTE: the polynomial range check has degree RANGE, which MockProver doesn't
+// mind, but a real proof with RANGE = 256 would be better off with the lookup
+// version in range_check/lookup.rs.
+
+#[derive(Debug, Clone)]
+struct FibRangeConfig<const RANGE: usize> {
+    pub fib: FiboConfig,
+    pub range: RangeCheckConfig<RANGE>,
+}
+
+#[derive(Default)]
+struct MyCircuit<F, const RANGE: usize> {
+    pub a: Option<F>,
+    pub b: Option<F>,
+    pub iter_num: usize,
+}
+
+impl<F: FieldExt, const RANGE: usize> Circuit<F> for MyCircuit<F, RANGE> {
+    type Config = FibRangeConfig<RANGE>;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            iter_num: self.iter_num,
+            ..Self::default()
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let fib_advice = meta.advice_column();
+        let instance = meta.instance_column();
+        let constant = meta.fixed_column();
+        let range_advice = meta.advice_column();
+
+        FibRangeConfig {
+            fib: FiboChip::configure(meta, fib_advice, instance, constant),
+            range: RangeCheckChip::<F, RANGE>::configure(meta, range_advice),
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let fib_chip = FiboChip::construct(config.fib);
+        let range_chip = RangeCheckChip::<F, RANGE>::construct(config.range);
+
+        let out = fib_chip.assign(
+            layouter.namespace(|| "fibonacci table"),
+            self.a,
+            self.b,
+            self.iter_num,
+        )?;
+        range_chip.check(layouter.namespace(|| "range check out"), &out)?;
+        fib_chip.expose_public(layouter.namespace(|| "out"), &out, 0)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MyCircuit;
+    use crate::testing::{assert_fails_with, nth_fibonacci};
+    use halo2_proofs::{dev::MockProver, pasta::Fp};
+
+    #[test]
+    fn test_fib_range() {
+        let k = 4;
+
+        let circuit = MyCircuit::<Fp, 256> {
+            a: Some(Fp::from(1)),
+            b: Some(Fp::from(2)),
+            iter_num: 10,
+        };
+
+        let public_input = vec![Fp::from(nth_fibonacci(1, 2, 10))];
+        let prover = MockProver::run(k, &circuit, vec![public_input]).unwrap();
+        prover.assert_satisfied();
+    }
+
+    #[test]
+    fn test_fib_range_failed() {
+        let k = 4;
+
+        // the Fibonacci part is still right, only 89 >= 64
+        let circuit = MyCircuit::<Fp, 64> {
+            a: Some(Fp::from(1)),
+            b: Some(Fp::from(2)),
+            iter_num: 10,
+        };
+
+        let public_input = vec![Fp::from(nth_fibonacci(1, 2, 10))];
+        let prover = MockProver::run(k, &circuit, vec![public_input]).unwrap();
+        assert_fails_with(&prover, "range check");
+    }
+}
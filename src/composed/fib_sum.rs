@@ -0,0 +1,115 @@
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use crate::fibonacci::example1::{FiboChip, FiboConfig};
+use crate::gadgets::running_sum::{RunningSumChip, RunningSumConfig};
+
+//
+// fibonacci (example1)          running sum
+//
+// s | col_a | col_b | col_c      s |  x  |  acc
+// --+-------+-------+------      --+-----+------
+// 1 |   1   |   1   |   2        0 |     |   0
+// 1 |   1   |   2   |   3        1 |  1  |   1     <- every term copied
+//        ...                     1 |  1  |   2        from the table
+// 1 |  21   |  34   |  55       ...| ... |  ...
+//                                1 | 55  |  143   -> instance
+//
+// Sums the first N Fibonacci numbers: example1's chip lays out the sequence and
+// returns the cell of every term, which are copied into the running sum gadget
+// one by one. Since they're copies, the x's can only be the Fibonacci terms and
+// the exposed total is bound to the sequence starting from a, b.
+
+#[derive(Debug, Clone)]
+struct FibSumConfig {
+    pub fib: FiboConfig,
+    pub sum: RunningSumConfig,
+}
+
+#[derive(Default)]
+struct MyCircuit<F, const N: usize> {
+    pub a: Option<F>,
+    pub b: Option<F>,
+}
+
+impl<F: FieldExt, const N: usize> Circuit<F> for MyCircuit<F, N> {
+    type Config = FibSumConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let fib_advice = [
+            meta.advice_column(),
+            meta.advice_column(),
+            meta.advice_column(),
+        ];
+        let instance = meta.instance_column();
+        let x = meta.advice_column();
+        let acc = meta.advice_column();
+        let constant = meta.fixed_column();
+
+        // the total goes to the instance column of the Fibonacci chip
+        FibSumConfig {
+            fib: FiboChip::<F, N>::configure(meta, fib_advice, instance),
+            sum: RunningSumChip::configure(meta, x, acc, constant),
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let instance = config.fib.instance;
+        let fib_chip = FiboChip::<F, N>::construct(config.fib);
+        let sum_chip = RunningSumChip::construct(config.sum);
+
+        let terms =
+            fib_chip.assign_sequence(layouter.namespace(|| "fibonacci table"), self.a, self.b)?;
+        let sum = sum_chip.accumulate_cells(layouter.namespace(|| "sum"), &terms)?;
+        layouter.constrain_instance(sum.cell(), instance, 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MyCircuit;
+    use crate::testing::nth_fibonacci;
+    use halo2_proofs::{dev::MockProver, pasta::Fp};
+
+    #[test]
+    fn test_fib_sum() {
+        // 10 terms after the initial 0
+        let k = 5;
+
+        // 1 + 1 + 2 + 3 + 5 + 8 + 13 + 21 + 34 + 55
+        let circuit = MyCircuit::<_, 10> {
+            a: Some(Fp::from(1)),
+            b: Some(Fp::from(1)),
+        };
+
+        let sum: u64 = (1..=10).map(|n| nth_fibonacci(1, 1, n)).sum();
+        assert_eq!(sum, 143);
+
+        let public_input = vec![Fp::from(sum)];
+        let prover = MockProver::run(k, &circuit, vec![public_input]).unwrap();
+        prover.assert_satisfied();
+    }
+
+    #[test]
+    fn test_fib_sum_failed() {
+        let k = 5;
+
+        let circuit = MyCircuit::<_, 10> {
+            a: Some(Fp::from(1)),
+            b: Some(Fp::from(1)),
+        };
+
+        // the 10th term alone
+        let public_input = vec![Fp::from(nth_fibonacci(1, 1, 10))];
+        let prover = MockProver::run(k, &circuit, vec![public_input]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}
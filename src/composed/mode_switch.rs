@@ -0,0 +1,161 @@
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use crate::arithmetic::gate::{ArithmeticChip, ArithmeticConfig};
+use crate::conditional_select::example::{CondSelectChip, CondSelectConfig};
+
+//
+// arithmetic                         conditional select
+//
+// q_add | q_mul | a  | b  |   c         s | cond | a  | b  | out
+// ------+-------+----+----+--------    ---+------+----+----+------
+//   1   |   0   | x0 | x1 | x0 + x1     1 | mode | p  | s  |  x2   <- x2 for the next steps
+//   0   |   1   | x0 | x1 | x0 * x1    ...
+//  ...
+//
+// instance: [mode, out]
+//
+// The public mode picks the recurrence: 0 gives the Fibonacci sequence
+//   x(i + 2) = x(i) + x(i + 1)
+// and 1 its multiplicative version
+//   x(i + 2) = x(i) * x(i + 1)
+// Every step computes both the sum and the product with the arithmetic chip,
+// and the conditional select chip keeps one of them, its cond being a copy of
+// the mode read from the instance column.
+//
+// Both branches are always in the circuit: the layout, the gates and so the
+// keys are the same for either mode, and a proof costs the sum and the product
+// whatever the mode is. Only the witnesses (the mode and the selected values)
+// differ, the circuit itself can't skip a branch. The select gate also
+// constrains the mode to be boolean, any other value fails to verify.
+
+#[derive(Debug, Clone)]
+struct ModeSwitchConfig {
+    pub arithmetic: ArithmeticConfig,
+    pub select: CondSelectConfig,
+}
+
+#[derive(Default)]
+struct MyCircuit<F> {
+    pub a: Option<F>,
+    pub b: Option<F>,
+    // the number of terms, a and b included
+    pub iter_num: usize,
+}
+
+impl<F: FieldExt> Circuit<F> for MyCircuit<F> {
+    type Config = ModeSwitchConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            iter_num: self.iter_num,
+            ..Self::default()
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let arithmetic_advice = [
+            meta.advice_column(),
+            meta.advice_column(),
+            meta.advice_column(),
+        ];
+        let select_advice = [
+            meta.advice_column(),
+            meta.advice_column(),
+            meta.advice_column(),
+            meta.advice_column(),
+        ];
+        // the mode and the output, shared by both chips
+        let instance = meta.instance_column();
+
+        ModeSwitchConfig {
+            arithmetic: ArithmeticChip::configure(meta, arithmetic_advice, instance),
+            select: CondSelectChip::configure(meta, select_advice, instance),
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let arithmetic = ArithmeticChip::construct(config.arithmetic);
+        let select = CondSelectChip::construct(config.select.clone());
+
+        let mode = layouter.assign_region(
+            || "load mode",
+            |mut region| {
+                region.assign_advice_from_instance(
+                    || "mode",
+                    config.select.instance,
+                    0,
+                    config.select.cond,
+                    0,
+                )
+            },
+        )?;
+
+        let mut x0 = arithmetic.load_private(layouter.namespace(|| "load a"), self.a)?;
+        let mut x1 = arithmetic.load_private(layouter.namespace(|| "load b"), self.b)?;
+        for _ in 2..self.iter_num {
+            let sum = arithmetic.add(layouter.namespace(|| "sum"), &x0, &x1)?;
+            let product = arithmetic.mul(layouter.namespace(|| "product"), &x0, &x1)?;
+            // mode = 1 picks the product
+            let x2 = select.select(layouter.namespace(|| "select"), &mode, &product, &sum)?;
+
+            x0 = x1;
+            x1 = x2;
+        }
+        select.expose_public(layouter.namespace(|| "out"), &x1, 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MyCircuit;
+    use crate::testing::nth_fibonacci;
+    use halo2_proofs::{dev::MockProver, pasta::Fp};
+
+    fn circuit() -> MyCircuit<Fp> {
+        MyCircuit {
+            a: Some(Fp::from(1)),
+            b: Some(Fp::from(2)),
+            iter_num: 10,
+        }
+    }
+
+    #[test]
+    fn test_mode_switch_sum() {
+        let k = 5;
+
+        let public_input = vec![Fp::zero(), Fp::from(nth_fibonacci(1, 2, 10))];
+        let prover = MockProver::run(k, &circuit(), vec![public_input]).unwrap();
+        prover.assert_satisfied();
+    }
+
+    #[test]
+    fn test_mode_switch_product() {
+        let k = 5;
+
+        // 1, 2, 2, 4, 8, 32, ..., the exponents of 2 are the Fibonacci numbers
+        let out = Fp::from(1 << nth_fibonacci(0, 1, 10));
+        let public_input = vec![Fp::one(), out];
+        let prover = MockProver::run(k, &circuit(), vec![public_input]).unwrap();
+        prover.assert_satisfied();
+    }
+
+    #[test]
+    fn test_mode_switch_failed() {
+        let k = 5;
+
+        // the sum's output in product mode
+        let public_input = vec![Fp::one(), Fp::from(nth_fibonacci(1, 2, 10))];
+        let prover = MockProver::run(k, &circuit(), vec![public_input]).unwrap();
+        assert!(prover.verify().is_err());
+
+        // neither mode
+        let public_input = vec![Fp::from(2), Fp::from(nth_fibonacci(1, 2, 10))];
+        let prover = MockProver::run(k, &circuit(), vec![public_input]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}
@@ -0,0 +1,383 @@
+use crate::error::CircuitError;
+use crate::is_zero::example::{IsZeroChip, IsZeroConfig};
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+//
+// selector | cond |  a  |  b  |  out
+// ---------+------+-----+-----+-------
+//   s0     |   c  |  a  |  b  | c * a + (1 - c) * b
+//
+// cond must be boolean, otherwise the output would be some mix of a and b:
+//   cond * (1 - cond) = 0
+//   out = cond * a + (1 - cond) * b
+// The cond is usually the output of another gadget, e.g. the is_zero gadget
+// in IsZeroSelectCircuit, and `assign_with_cond` or `select` copies it in.
+
+#[derive(Debug, Clone)]
+pub(crate) struct CondSelectConfig {
+    pub cond: Column<Advice>,
+    pub a: Column<Advice>,
+    pub b: Column<Advice>,
+    pub out: Column<Advice>,
+    pub instance: Column<Instance>,
+    pub selector: Selector,
+}
+
+pub(crate) struct CondSelectChip<F: FieldExt> {
+    config: CondSelectConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> CondSelectChip<F> {
+    pub fn construct(config: CondSelectConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 4],
+        instance: Column<Instance>,
+    ) -> CondSelectConfig {
+        let [cond, a, b, out] = advice;
+        let selector = meta.selector();
+
+        // the inputs can be copied in from other chips, see select
+        for column in advice {
+            meta.enable_equality(column);
+        }
+        meta.enable_equality(instance);
+
+        meta.create_gate("conditional select", |meta| {
+            let s = meta.query_selector(selector);
+            let cond = meta.query_advice(cond, Rotation::cur());
+            let a = meta.query_advice(a, Rotation::cur());
+            let b = meta.query_advice(b, Rotation::cur());
+            let out = meta.query_advice(out, Rotation::cur());
+            let one = Expression::Constant(F::one());
+
+            vec![
+                // cond is boolean
+                s.clone() * cond.clone() * (one.clone() - cond.clone()),
+                s * (cond.clone() * a + (one - cond) * b - out),
+            ]
+        });
+
+        CondSelectConfig {
+            cond,
+            a,
+            b,
+            out,
+            instance,
+            selector,
+        }
+    }
+
+    pub fn assign(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cond: Option<F>,
+        a: Option<F>,
+        b: Option<F>,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        layouter.assign_region(
+            || "conditional select",
+            |mut region| {
+                self.config.selector.enable(&mut region, 0)?;
+
+                region.assign_advice(
+                    || "cond",
+                    self.config.cond,
+                    0,
+                    || cond.ok_or(CircuitError::MissingWitness.into()),
+                )?;
+                region.assign_advice(
+                    || "a",
+                    self.config.a,
+                    0,
+                    || a.ok_or(CircuitError::MissingWitness.into()),
+                )?;
+                region.assign_advice(
+                    || "b",
+                    self.config.b,
+                    0,
+                    || b.ok_or(CircuitError::MissingWitness.into()),
+                )?;
+
+                let out = cond.and_then(|c| a.zip(b).map(|(a, b)| c * a + (F::one() - c) * b));
+                region.assign_advice(
+                    || "out",
+                    self.config.out,
+                    0,
+                    || out.ok_or(CircuitError::MissingWitness.into()),
+                )
+            },
+        )
+    }
+
+    // same as `assign`, with cond copied from a cell assigned elsewhere, e.g. the
+    // output of the is_zero chip
+    pub fn assign_with_cond(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cond: &AssignedCell<F, F>,
+        a: Option<F>,
+        b: Option<F>,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        layouter.assign_region(
+            || "conditional select",
+            |mut region| {
+                self.config.selector.enable(&mut region, 0)?;
+
+                let cond = cond.copy_advice(|| "cond", &mut region, self.config.cond, 0)?;
+                region.assign_advice(
+                    || "a",
+                    self.config.a,
+                    0,
+                    || a.ok_or(CircuitError::MissingWitness.into()),
+                )?;
+                region.assign_advice(
+                    || "b",
+                    self.config.b,
+                    0,
+                    || b.ok_or(CircuitError::MissingWitness.into()),
+                )?;
+
+                let out = cond
+                    .value()
+                    .and_then(|c| a.zip(b).map(|(a, b)| *c * a + (F::one() - c) * b));
+                region.assign_advice(
+                    || "out",
+                    self.config.out,
+                    0,
+                    || out.ok_or(CircuitError::MissingWitness.into()),
+                )
+            },
+        )
+    }
+
+    // same as `assign`, with all three inputs copied from cells assigned elsewhere
+    pub fn select(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cond: &AssignedCell<F, F>,
+        a: &AssignedCell<F, F>,
+        b: &AssignedCell<F, F>,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        layouter.assign_region(
+            || "conditional select",
+            |mut region| {
+                self.config.selector.enable(&mut region, 0)?;
+
+                let cond = cond.copy_advice(|| "cond", &mut region, self.config.cond, 0)?;
+                let a = a.copy_advice(|| "a", &mut region, self.config.a, 0)?;
+                let b = b.copy_advice(|| "b", &mut region, self.config.b, 0)?;
+
+                let out = cond.value().and_then(|c| {
+                    a.value()
+                        .zip(b.value())
+                        .map(|(a, b)| *c * a + (F::one() - c) * b)
+                });
+                region.assign_advice(
+                    || "out",
+                    self.config.out,
+                    0,
+                    || out.ok_or(CircuitError::MissingWitness.into()),
+                )
+            },
+        )
+    }
+
+    pub fn expose_public(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cell: &AssignedCell<F, F>,
+        row: usize,
+    ) -> Result<(), Error> {
+        layouter.constrain_instance(cell.cell(), self.config.instance, row)
+    }
+}
+
+#[derive(Default)]
+struct MyCircuit<F> {
+    pub cond: Option<F>,
+    pub a: Option<F>,
+    pub b: Option<F>,
+}
+
+impl<F: FieldExt> Circuit<F> for MyCircuit<F> {
+    type Config = CondSelectConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let advice = [
+            meta.advice_column(),
+            meta.advice_column(),
+            meta.advice_column(),
+            meta.advice_column(),
+        ];
+        let instance = meta.instance_column();
+
+        CondSelectChip::configure(meta, advice, instance)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let chip = CondSelectChip::construct(config);
+
+        let out = chip.assign(layouter.namespace(|| "select"), self.cond, self.a, self.b)?;
+        chip.expose_public(layouter.namespace(|| "out"), &out, 0)?;
+
+        Ok(())
+    }
+}
+
+// picks a if x is zero and b otherwise, the cond is the is_zero chip's output
+//
+// selector | value | value_inv | is_zero |
+// ---------+-------+-----------+---------+
+//   s0     |   x   |   x^-1    |    c    |
+//
+// selector | cond |  a  |  b  |  out
+// ---------+------+-----+-----+-------
+//   s1     |   c  |  a  |  b  | c * a + (1 - c) * b
+#[derive(Default)]
+struct IsZeroSelectCircuit<F> {
+    pub x: Option<F>,
+    pub a: Option<F>,
+    pub b: Option<F>,
+}
+
+impl<F: FieldExt> Circuit<F> for IsZeroSelectCircuit<F> {
+    type Config = (IsZeroConfig, CondSelectConfig);
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let advice = [
+            meta.advice_column(),
+            meta.advice_column(),
+            meta.advice_column(),
+            meta.advice_column(),
+        ];
+        let instance = meta.instance_column();
+
+        // the select chip enables equality on every advice column, so the
+        // is_zero output can be copied from the column it's assigned in
+        (
+            IsZeroChip::configure(meta, advice[0], advice[1], advice[2]),
+            CondSelectChip::configure(meta, advice, instance),
+        )
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let (is_zero_config, select_config) = config;
+        let is_zero = IsZeroChip::construct(is_zero_config);
+        let select = CondSelectChip::construct(select_config);
+
+        let cond = is_zero.assign(layouter.namespace(|| "is_zero"), self.x)?;
+        let out =
+            select.assign_with_cond(layouter.namespace(|| "select"), &cond, self.a, self.b)?;
+        select.expose_public(layouter.namespace(|| "out"), &out, 0)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{IsZeroSelectCircuit, MyCircuit};
+    use halo2_proofs::{dev::MockProver, pasta::Fp};
+
+    #[test]
+    fn test_conditional_select() {
+        let k = 4;
+
+        let a = Fp::from(3);
+        let b = Fp::from(5);
+
+        // cond = 1 picks a, cond = 0 picks b
+        for (cond, out) in [(1, a), (0, b)] {
+            let circuit = MyCircuit {
+                cond: Some(Fp::from(cond)),
+                a: Some(a),
+                b: Some(b),
+            };
+
+            let public_input = vec![out];
+            let prover = MockProver::run(k, &circuit, vec![public_input]).unwrap();
+            prover.assert_satisfied();
+        }
+    }
+
+    #[test]
+    fn test_conditional_select_failed() {
+        let k = 4;
+
+        // out = 2 * 3 + (1 - 2) * 5 = 1 satisfies the select constraint,
+        // but the boolean constraint rejects cond = 2
+        let circuit = MyCircuit {
+            cond: Some(Fp::from(2)),
+            a: Some(Fp::from(3)),
+            b: Some(Fp::from(5)),
+        };
+
+        let public_input = vec![Fp::from(1)];
+        let prover = MockProver::run(k, &circuit, vec![public_input]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn test_conditional_select_is_zero() {
+        let k = 4;
+
+        let a = Fp::from(3);
+        let b = Fp::from(5);
+
+        // x = 0 picks a, anything else picks b
+        for (x, out) in [(0, a), (7, b)] {
+            let circuit = IsZeroSelectCircuit {
+                x: Some(Fp::from(x)),
+                a: Some(a),
+                b: Some(b),
+            };
+
+            let public_input = vec![out];
+            let prover = MockProver::run(k, &circuit, vec![public_input]).unwrap();
+            prover.assert_satisfied();
+        }
+    }
+
+    #[test]
+    fn test_conditional_select_is_zero_failed() {
+        let k = 4;
+
+        // 7 isn't zero, so the output must be b
+        let circuit = IsZeroSelectCircuit {
+            x: Some(Fp::from(7)),
+            a: Some(Fp::from(3)),
+            b: Some(Fp::from(5)),
+        };
+
+        let public_input = vec![Fp::from(3)];
+        let prover = MockProver::run(k, &circuit, vec![public_input]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}
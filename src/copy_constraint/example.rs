@@ -0,0 +1,298 @@
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+use crate::error::CircuitError;
+
+//
+// region | selector | col_a | col_b | col_c
+// -------+----------+-------+-------+--------
+// first  |    1     |   a   |   b   | x = a + b
+// second |    1     |   x   |   b   | y = x + b  -> instance
+//
+// x is computed in col_c of the first region and used in col_a of the second
+// one: a copy between two regions and two columns, which the permutation
+// argument enforces.
+//
+// Witnessing x again in the second region only puts a value in a cell: nothing
+// ties it to the x of the first region, and a prover could put anything there
+// (see test_copy_constraint_unconstrained). The tie is the explicit
+//   region.constrain_equal(x_again.cell(), x.cell())
+// which works between any two cells of columns with equality enabled, whatever
+// the regions. copy_advice, used for b, is the shorthand doing both steps:
+// assign the value of the cell, then constrain_equal with it.
+
+#[derive(Debug, Clone)]
+struct CopyConfig {
+    pub advice: [Column<Advice>; 3],
+    pub instance: Column<Instance>,
+    pub selector: Selector,
+}
+
+struct CopyChip<F: FieldExt> {
+    config: CopyConfig,
+    _marker: PhantomData<F>,
+}
+
+// (b, x) of the first region
+type CellPair<F> = (AssignedCell<F, F>, AssignedCell<F, F>);
+
+impl<F: FieldExt> CopyChip<F> {
+    pub fn construct(config: CopyConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        instance: Column<Instance>,
+    ) -> CopyConfig {
+        let [col_a, col_b, col_c] = advice;
+        let selector = meta.selector();
+
+        for column in advice {
+            meta.enable_equality(column);
+        }
+        meta.enable_equality(instance);
+
+        meta.create_gate("add", |meta| {
+            let s = meta.query_selector(selector);
+            let a = meta.query_advice(col_a, Rotation::cur());
+            let b = meta.query_advice(col_b, Rotation::cur());
+            let c = meta.query_advice(col_c, Rotation::cur());
+            vec![s * (a + b - c)]
+        });
+
+        CopyConfig {
+            advice,
+            instance,
+            selector,
+        }
+    }
+
+    pub fn first_sum(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: Option<F>,
+        b: Option<F>,
+    ) -> Result<CellPair<F>, Error> {
+        layouter.assign_region(
+            || "first",
+            |mut region| {
+                let [col_a, col_b, col_c] = self.config.advice;
+                self.config.selector.enable(&mut region, 0)?;
+
+                region.assign_advice(
+                    || "a",
+                    col_a,
+                    0,
+                    || a.ok_or(CircuitError::MissingWitness.into()),
+                )?;
+                let b_cell = region.assign_advice(
+                    || "b",
+                    col_b,
+                    0,
+                    || b.ok_or(CircuitError::MissingWitness.into()),
+                )?;
+                let x = a.zip(b).map(|(a, b)| a + b);
+                let x_cell = region.assign_advice(
+                    || "x",
+                    col_c,
+                    0,
+                    || x.ok_or(CircuitError::MissingWitness.into()),
+                )?;
+                Ok((b_cell, x_cell))
+            },
+        )
+    }
+
+    pub fn second_sum(
+        &self,
+        layouter: impl Layouter<F>,
+        x: &AssignedCell<F, F>,
+        b: &AssignedCell<F, F>,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        self.assign_second(layouter, x, x.value().copied(), b, true)
+    }
+
+    // the second region with `x_value` witnessed in col_a, tied to the cell `x`
+    // only if `constrain` is set
+    fn assign_second(
+        &self,
+        mut layouter: impl Layouter<F>,
+        x: &AssignedCell<F, F>,
+        x_value: Option<F>,
+        b: &AssignedCell<F, F>,
+        constrain: bool,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        layouter.assign_region(
+            || "second",
+            |mut region| {
+                let [col_a, col_b, col_c] = self.config.advice;
+                self.config.selector.enable(&mut region, 0)?;
+
+                let x_again = region.assign_advice(
+                    || "x",
+                    col_a,
+                    0,
+                    || x_value.ok_or(CircuitError::MissingWitness.into()),
+                )?;
+                if constrain {
+                    region.constrain_equal(x_again.cell(), x.cell())?;
+                }
+                b.copy_advice(|| "b", &mut region, col_b, 0)?;
+
+                let y = x_value.zip(b.value()).map(|(x, b)| x + b);
+                region.assign_advice(
+                    || "y",
+                    col_c,
+                    0,
+                    || y.ok_or(CircuitError::MissingWitness.into()),
+                )
+            },
+        )
+    }
+
+    pub fn expose_public(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cell: &AssignedCell<F, F>,
+        row: usize,
+    ) -> Result<(), Error> {
+        layouter.constrain_instance(cell.cell(), self.config.instance, row)
+    }
+}
+
+#[derive(Default)]
+struct MyCircuit<F> {
+    pub a: Option<F>,
+    pub b: Option<F>,
+}
+
+// computes (a + b) + b
+impl<F: FieldExt> Circuit<F> for MyCircuit<F> {
+    type Config = CopyConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let advice = [
+            meta.advice_column(),
+            meta.advice_column(),
+            meta.advice_column(),
+        ];
+        let instance = meta.instance_column();
+
+        CopyChip::configure(meta, advice, instance)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let chip = CopyChip::construct(config);
+
+        let (b, x) = chip.first_sum(layouter.namespace(|| "first"), self.a, self.b)?;
+        let y = chip.second_sum(layouter.namespace(|| "second"), &x, &b)?;
+        chip.expose_public(layouter.namespace(|| "y"), &y, 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CopyChip, CopyConfig, MyCircuit};
+    use halo2_proofs::{
+        circuit::*,
+        dev::{MockProver, VerifyFailure},
+        pasta::Fp,
+        plonk::*,
+    };
+
+    // the second region starts from `x` instead of a + b
+    struct TamperedCircuit {
+        x: Fp,
+        constrain: bool,
+    }
+
+    impl Circuit<Fp> for TamperedCircuit {
+        type Config = CopyConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self {
+                x: self.x,
+                constrain: self.constrain,
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            MyCircuit::<Fp>::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = CopyChip::construct(config);
+
+            let (b, x) = chip.first_sum(
+                layouter.namespace(|| "first"),
+                Some(Fp::from(2)),
+                Some(Fp::from(3)),
+            )?;
+            let y = chip.assign_second(
+                layouter.namespace(|| "second"),
+                &x,
+                Some(self.x),
+                &b,
+                self.constrain,
+            )?;
+            chip.expose_public(layouter.namespace(|| "y"), &y, 0)
+        }
+    }
+
+    #[test]
+    fn test_copy_constraint() {
+        let circuit = MyCircuit {
+            a: Some(Fp::from(2)),
+            b: Some(Fp::from(3)),
+        };
+
+        // (2 + 3) + 3
+        let prover = MockProver::run(4, &circuit, vec![vec![Fp::from(8)]]).unwrap();
+        prover.assert_satisfied();
+    }
+
+    #[test]
+    fn test_copy_constraint_failed() {
+        // x = 10 instead of 5, only the copy between the regions breaks
+        let circuit = TamperedCircuit {
+            x: Fp::from(10),
+            constrain: true,
+        };
+        let prover = MockProver::run(4, &circuit, vec![vec![Fp::from(13)]]).unwrap();
+        let failures = prover.verify().unwrap_err();
+        assert!(failures
+            .iter()
+            .all(|failure| matches!(failure, VerifyFailure::Permutation { .. })));
+    }
+
+    #[test]
+    fn test_copy_constraint_unconstrained() {
+        // without constrain_equal, the wrong x goes through: both gates hold
+        // and the circuit "proves" (2 + 3) + 3 = 13
+        let circuit = TamperedCircuit {
+            x: Fp::from(10),
+            constrain: false,
+        };
+        let prover = MockProver::run(4, &circuit, vec![vec![Fp::from(13)]]).unwrap();
+        prover.assert_satisfied();
+    }
+}
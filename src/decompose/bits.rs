@@ -0,0 +1,199 @@
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+use crate::error::CircuitError;
+
+//
+// selector | value | bit_0 | bit_1 | ... | bit_{NUM_BITS-1}
+// ---------+-------+-------+-------+-----+------------------
+//   s0     |   v   |  b0   |  b1   | ... |  b_{NUM_BITS-1}
+//
+// e.g. 13 = 0b1101 => b0 = 1, b1 = 0, b2 = 1, b3 = 1
+//
+// Each bit is boolean: b_i * (1 - b_i) = 0
+// and the bits recompose to the value: sum(b_i * 2^i) - v = 0
+// Without the boolean constraints, any field elements summing up to v would pass.
+
+#[derive(Debug, Clone)]
+struct BitDecompConfig<const NUM_BITS: usize> {
+    pub value: Column<Advice>,
+    pub bits: [Column<Advice>; NUM_BITS],
+    pub instance: Column<Instance>,
+    pub selector: Selector,
+}
+
+struct BitDecompChip<F: FieldExt, const NUM_BITS: usize> {
+    config: BitDecompConfig<NUM_BITS>,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt, const NUM_BITS: usize> BitDecompChip<F, NUM_BITS> {
+    pub fn construct(config: BitDecompConfig<NUM_BITS>) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        value: Column<Advice>,
+        bits: [Column<Advice>; NUM_BITS],
+        instance: Column<Instance>,
+    ) -> BitDecompConfig<NUM_BITS> {
+        let selector = meta.selector();
+
+        meta.enable_equality(value);
+        meta.enable_equality(instance);
+
+        meta.create_gate("bit decomposition", |meta| {
+            let s = meta.query_selector(selector);
+            let v = meta.query_advice(value, Rotation::cur());
+            let one = Expression::Constant(F::one());
+
+            let mut constraints = vec![];
+            let mut recomposed = Expression::Constant(F::zero());
+            for (i, bit) in bits.iter().enumerate() {
+                let bit = meta.query_advice(*bit, Rotation::cur());
+                constraints.push(s.clone() * bit.clone() * (one.clone() - bit.clone()));
+
+                recomposed = recomposed + bit * Expression::Constant(F::from(1 << i));
+            }
+            constraints.push(s * (recomposed - v));
+            constraints
+        });
+
+        BitDecompConfig {
+            value,
+            bits,
+            instance,
+            selector,
+        }
+    }
+
+    pub fn assign(
+        &self,
+        mut layouter: impl Layouter<F>,
+        value: Option<F>,
+        bits: [Option<F>; NUM_BITS],
+    ) -> Result<AssignedCell<F, F>, Error> {
+        layouter.assign_region(
+            || "bit decomposition",
+            |mut region| {
+                self.config.selector.enable(&mut region, 0)?;
+
+                for (i, (bit, column)) in bits.iter().zip(self.config.bits.iter()).enumerate() {
+                    region.assign_advice(
+                        || format!("bit {}", i),
+                        *column,
+                        0,
+                        || bit.ok_or(CircuitError::MissingWitness.into()),
+                    )?;
+                }
+
+                region.assign_advice(
+                    || "value",
+                    self.config.value,
+                    0,
+                    || value.ok_or(CircuitError::MissingWitness.into()),
+                )
+            },
+        )
+    }
+
+    pub fn expose_public(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cell: &AssignedCell<F, F>,
+        row: usize,
+    ) -> Result<(), Error> {
+        layouter.constrain_instance(cell.cell(), self.config.instance, row)
+    }
+}
+
+struct MyCircuit<F, const NUM_BITS: usize> {
+    pub value: Option<F>,
+    pub bits: [Option<F>; NUM_BITS],
+}
+
+impl<F: FieldExt, const NUM_BITS: usize> Default for MyCircuit<F, NUM_BITS> {
+    fn default() -> Self {
+        Self {
+            value: None,
+            bits: [None; NUM_BITS],
+        }
+    }
+}
+
+impl<F: FieldExt, const NUM_BITS: usize> Circuit<F> for MyCircuit<F, NUM_BITS> {
+    type Config = BitDecompConfig<NUM_BITS>;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let value = meta.advice_column();
+        let bits = [(); NUM_BITS].map(|_| meta.advice_column());
+        let instance = meta.instance_column();
+
+        BitDecompChip::configure(meta, value, bits, instance)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let chip = BitDecompChip::construct(config);
+
+        let value_cell = chip.assign(layouter.namespace(|| "decompose"), self.value, self.bits)?;
+        chip.expose_public(layouter.namespace(|| "value"), &value_cell, 0)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MyCircuit;
+    use halo2_proofs::{dev::MockProver, pasta::Fp};
+
+    const NUM_BITS: usize = 4;
+
+    fn to_bits(value: u64) -> [Option<Fp>; NUM_BITS] {
+        [0, 1, 2, 3].map(|i| Some(Fp::from((value >> i) & 1)))
+    }
+
+    #[test]
+    fn test_bit_decomposition() {
+        let k = 4;
+
+        for value in [5, 13] {
+            let circuit = MyCircuit::<Fp, NUM_BITS> {
+                value: Some(Fp::from(value)),
+                bits: to_bits(value),
+            };
+
+            let public_input = vec![Fp::from(value)];
+            let prover = MockProver::run(k, &circuit, vec![public_input]).unwrap();
+            prover.assert_satisfied();
+        }
+    }
+
+    #[test]
+    fn test_bit_decomposition_failed() {
+        let k = 4;
+
+        // 0b0101 = 5, not 13
+        let circuit = MyCircuit::<Fp, NUM_BITS> {
+            value: Some(Fp::from(13)),
+            bits: to_bits(5),
+        };
+
+        let public_input = vec![Fp::from(13)];
+        let prover = MockProver::run(k, &circuit, vec![public_input]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}
@@ -0,0 +1,34 @@
+use halo2_proofs::plonk::Error;
+
+// Errors raised by the chips themselves, as opposed to the ones halo2 returns.
+//
+// plonk::Error in halo2_proofs 0.1.0 has no variant to carry our own errors, so
+// they all end up as Error::Synthesis once they leave synthesize. Where the
+// variant matters, it's checked before the circuit is built (see the builders).
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitError {
+    // a witness needed for an assignment is None
+    MissingWitness,
+    // the layout needs at least `min` terms
+    IterTooSmall { iter_num: usize, min: usize },
+    // the chip is built for `expected` terms, e.g. example1's const N
+    IterMismatch { iter_num: usize, expected: usize },
+    // the layout needs more rows than 2^k leaves for the assignments
+    TooManyRows { needed: usize, available: usize },
+    // two inputs that go together have different lengths, e.g. a Merkle path
+    // and its position bits
+    LengthMismatch { expected: usize, actual: usize },
+    // an input that needs at least one element is empty
+    EmptyInput,
+    // a value that has to be nonzero, e.g. one that gets inverted, is 0
+    ZeroValue,
+    // a fixed divisor outside of (0, max], see DivModChip::assign
+    DivisorOutOfRange { d: u64, max: usize },
+}
+
+impl From<CircuitError> for Error {
+    fn from(_: CircuitError) -> Self {
+        Error::Synthesis
+    }
+}
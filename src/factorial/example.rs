@@ -0,0 +1,187 @@
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+use crate::error::CircuitError;
+
+//
+// selector |   acc  | factor (fixed) |
+// ---------+--------+----------------|
+//   s0     |   1    |       1        |
+//   s1     |   1!   |       2        |
+//   s2     |   2!   |       3        |
+//   ...    |   ...  |      ...       |
+//          |   n!   |                |
+//
+// acc_next = acc * factor, so after n rows acc holds n!
+//
+// The factors 1..=n are the same for every prover, so they live in a fixed
+// column (part of the circuit, committed at keygen) instead of being witnessed.
+// The initial 1 is a constant as well, assigned through `enable_constant`.
+
+#[derive(Debug, Clone)]
+struct FactorialConfig {
+    pub acc: Column<Advice>,
+    pub factor: Column<Fixed>,
+    pub instance: Column<Instance>,
+    pub selector: Selector,
+}
+
+struct FactorialChip<F: FieldExt> {
+    config: FactorialConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> FactorialChip<F> {
+    pub fn construct(config: FactorialConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        acc: Column<Advice>,
+        factor: Column<Fixed>,
+        constant: Column<Fixed>,
+        instance: Column<Instance>,
+    ) -> FactorialConfig {
+        let selector = meta.selector();
+
+        meta.enable_equality(acc);
+        meta.enable_equality(instance);
+        meta.enable_constant(constant);
+
+        meta.create_gate("mul", |meta| {
+            let s = meta.query_selector(selector);
+            let acc_cur = meta.query_advice(acc, Rotation::cur());
+            let factor = meta.query_fixed(factor, Rotation::cur());
+            let acc_next = meta.query_advice(acc, Rotation::next());
+            vec![s * (acc_cur * factor - acc_next)]
+        });
+
+        FactorialConfig {
+            acc,
+            factor,
+            instance,
+            selector,
+        }
+    }
+
+    pub fn assign(
+        &self,
+        mut layouter: impl Layouter<F>,
+        n: usize,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        layouter.assign_region(
+            || "factorial region",
+            |mut region| {
+                // 0! = 1
+                let mut acc_cell =
+                    region.assign_advice_from_constant(|| "1", self.config.acc, 0, F::one())?;
+
+                for row in 0..n {
+                    self.config.selector.enable(&mut region, row)?;
+
+                    let factor = F::from(row as u64 + 1);
+                    region.assign_fixed(|| "factor", self.config.factor, row, || Ok(factor))?;
+
+                    let acc = acc_cell.value().map(|acc| *acc * factor);
+                    acc_cell = region.assign_advice(
+                        || "acc",
+                        self.config.acc,
+                        row + 1,
+                        || acc.ok_or(CircuitError::MissingWitness.into()),
+                    )?;
+                }
+                Ok(acc_cell)
+            },
+        )
+    }
+
+    pub fn expose_public(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cell: &AssignedCell<F, F>,
+        row: usize,
+    ) -> Result<(), Error> {
+        layouter.constrain_instance(cell.cell(), self.config.instance, row)
+    }
+}
+
+#[derive(Default)]
+struct MyCircuit<F> {
+    pub n: usize,
+    _marker: PhantomData<F>,
+}
+
+impl<F> MyCircuit<F> {
+    pub fn new(n: usize) -> Self {
+        Self {
+            n,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<F: FieldExt> Circuit<F> for MyCircuit<F> {
+    type Config = FactorialConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    // there are no witnesses, n only decides the layout
+    fn without_witnesses(&self) -> Self {
+        Self::new(self.n)
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let acc = meta.advice_column();
+        let factor = meta.fixed_column();
+        let constant = meta.fixed_column();
+        let instance = meta.instance_column();
+
+        FactorialChip::configure(meta, acc, factor, constant, instance)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let chip = FactorialChip::construct(config);
+
+        let out = chip.assign(layouter.namespace(|| "factorial"), self.n)?;
+        chip.expose_public(layouter.namespace(|| "out"), &out, 0)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MyCircuit;
+    use halo2_proofs::{dev::MockProver, pasta::Fp};
+
+    #[test]
+    fn test_factorial() {
+        let k = 4;
+
+        for (n, out) in [(5, 120), (0, 1)] {
+            let circuit = MyCircuit::<Fp>::new(n);
+
+            let public_input = vec![Fp::from(out)];
+            let prover = MockProver::run(k, &circuit, vec![public_input]).unwrap();
+            prover.assert_satisfied();
+        }
+    }
+
+    #[test]
+    fn test_factorial_failed() {
+        let k = 4;
+
+        let circuit = MyCircuit::<Fp>::new(5);
+
+        let public_input = vec![Fp::from(119)];
+        let prover = MockProver::run(k, &circuit, vec![public_input]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}
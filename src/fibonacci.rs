@@ -1,2 +1,206 @@
-mod example1;
-mod example2;
+//! The same Fibonacci sequence, laid out in different ways. Each example
+//! takes the two initial terms and exposes a later term as public output.
+//!
+//! - [`example1`]: three advice columns, one row per term, rows chained by copies
+//! - [`example2`]: one advice column, the gate looks two rows ahead
+//! - [`example2_wide`]: two columns, two terms per row
+//! - [`example3`]: one column without any copy, the output checked by a gate
+//! - [`fixed_enable`]: example1 gated by a fixed column instead of a selector
+//! - [`floor_planner`]: example2 laid out by the V1 floor planner
+//! - [`lucas`]: a gate over two columns and three rotations, and example2
+//!   seeded with the Lucas numbers
+//! - [`multi_region`]: example2 with a region per step
+//! - [`packed`]: two terms per row, rows chained by rotations
+//! - [`prev_rotation`]: example2 with the gate centered on the middle term
+//! - [`public_seed`]: both initial terms read from the instance column
+//! - [`single_region`]: example1's table in a single region
+//! - [`squared`]: example1 with a degree 3 gate, a^2 + b^2 = c
+//! - [`two_sequences`]: two chips sharing example2's config, two sequences
+//! - [`weighted`]: a recurrence with a fixed coefficient per step
+//! - [`prove`]: the real prover and verifier, beyond `MockProver`
+
+pub mod example1;
+pub mod example2;
+pub mod example2_wide;
+pub mod example3;
+pub mod fixed_enable;
+pub mod floor_planner;
+pub mod lucas;
+pub mod multi_region;
+pub mod packed;
+pub mod prev_rotation;
+pub mod prove;
+pub mod public_seed;
+pub mod single_region;
+pub mod squared;
+pub mod two_sequences;
+pub mod weighted;
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{AssignedCell, Layouter},
+    plonk::{Advice, Column, ConstraintSystem, Error, Fixed, Instance, Selector},
+};
+
+/// The common interface of the Fibonacci chips in this module.
+///
+/// Each example lays the sequence out differently (and so has its own
+/// `configure`), but all of them take the two initial terms, assign the
+/// sequence up to the `iter_num`-th term, and expose a cell as public output.
+///
+/// ```
+/// use fibonacci::fibonacci::FiboInstructions;
+/// use halo2_proofs::{arithmetic::FieldExt, circuit::Layouter, plonk::Error};
+///
+/// // computes the 10th term with any Fibonacci chip and exposes it to instance row 0
+/// fn tenth_term<F: FieldExt, Chip: FiboInstructions<F>>(
+///     chip: &Chip,
+///     mut layouter: impl Layouter<F>,
+///     a: Option<F>,
+///     b: Option<F>,
+/// ) -> Result<(), Error> {
+///     let out = chip.assign(layouter.namespace(|| "fibonacci"), a, b, 10)?;
+///     chip.expose_public(layouter.namespace(|| "out"), &out, 0)
+/// }
+/// ```
+pub trait FiboInstructions<F: FieldExt> {
+    /// Assigns the sequence starting from `init_a`, `init_b` and returns the
+    /// cell holding the `iter_num`-th term.
+    fn assign(
+        &self,
+        layouter: impl Layouter<F>,
+        init_a: Option<F>,
+        init_b: Option<F>,
+        iter_num: usize,
+    ) -> Result<AssignedCell<F, F>, Error>;
+
+    /// Constrains `cell` to equal the given row of the instance column.
+    fn expose_public(
+        &self,
+        layouter: impl Layouter<F>,
+        cell: &AssignedCell<F, F>,
+        row: usize,
+    ) -> Result<(), Error>;
+}
+
+/// How many columns of each kind a Fibonacci config uses, to compare the
+/// layout costs of the examples.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ColumnSummary {
+    pub advice: usize,
+    pub fixed: usize,
+    pub instance: usize,
+    pub selectors: usize,
+}
+
+impl ColumnSummary {
+    /// Counts the columns and selectors a config holds. One listed twice,
+    /// e.g. a selector shared by two gates, is counted once.
+    pub fn of(
+        advice: &[Column<Advice>],
+        fixed: &[Column<Fixed>],
+        instance: &[Column<Instance>],
+        selectors: &[Selector],
+    ) -> Self {
+        Self {
+            advice: distinct(advice),
+            fixed: distinct(fixed),
+            instance: distinct(instance),
+            selectors: distinct(selectors),
+        }
+    }
+}
+
+fn distinct<T: PartialEq>(items: &[T]) -> usize {
+    items
+        .iter()
+        .enumerate()
+        .filter(|(i, item)| !items[..*i].contains(item))
+        .count()
+}
+
+/// The number of rows a circuit with this constraint system can assign in 2^k.
+///
+/// halo2 keeps the last rows of every column for itself: `blinding_factors()`
+/// rows of random values, which hide the witnesses (more queries of a column
+/// need more of them), plus one row for the permutation argument. So the
+/// 10-row table of example2 needs k = 4: 2^4 = 16 minus the reserved rows
+/// still leaves the 10 rows, 2^3 = 8 doesn't. `minimum_rows()` is the reserved
+/// rows plus one of breathing room for the permutation and one row of circuit,
+/// the smallest table halo2 accepts at all.
+///
+/// A k too large for 2^k to fit in a `usize` counts as `usize::MAX` rows.
+pub fn usable_rows<F: FieldExt>(k: u32, cs: &ConstraintSystem<F>) -> usize {
+    1usize
+        .checked_shl(k)
+        .unwrap_or(usize::MAX)
+        .saturating_sub(cs.blinding_factors() + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{example1, example2, ColumnSummary};
+    use crate::testing::dump_regions;
+    use halo2_proofs::{
+        pasta::Fp,
+        plonk::{Circuit, ConstraintSystem},
+    };
+
+    // What plot_fibonacci1 and plot_fibonacci2 draw for 10 terms, as (occupied
+    // rows, occupied columns), so that a layout change is caught without
+    // comparing images. The columns are the advice, instance and selector ones;
+    // example2's constant column stays empty in MyCircuit.
+    const EXAMPLE1_LAYOUT: (usize, usize) = (8, 5);
+    const EXAMPLE2_LAYOUT: (usize, usize) = (10, 3);
+
+    fn layout<C: Circuit<Fp>>(circuit: &C, summary: ColumnSummary) -> (usize, usize) {
+        let rows = dump_regions(4, circuit)
+            .into_iter()
+            .map(|(_, rows)| rows.end)
+            .max()
+            .unwrap_or(0);
+        (rows, summary.advice + summary.instance + summary.selectors)
+    }
+
+    #[test]
+    fn test_layout_snapshot() {
+        let mut meta = ConstraintSystem::<Fp>::default();
+        let summary1 = example1::MyCircuit::<Fp, 10>::configure(&mut meta).column_summary();
+        let circuit1 = example1::MyCircuit::<Fp, 10>::from_u64(1, 1);
+        assert_eq!(layout(&circuit1, summary1), EXAMPLE1_LAYOUT);
+
+        let mut meta = ConstraintSystem::<Fp>::default();
+        let summary2 = example2::MyCircuit::<Fp>::configure(&mut meta).column_summary();
+        let circuit2 = example2::MyCircuit::<Fp>::from_u64(1, 1);
+        assert_eq!(layout(&circuit2, summary2), EXAMPLE2_LAYOUT);
+    }
+
+    #[test]
+    fn test_column_summary() {
+        let mut meta = ConstraintSystem::<Fp>::default();
+        let summary1 = example1::MyCircuit::<Fp, 10>::configure(&mut meta).column_summary();
+        let mut meta = ConstraintSystem::<Fp>::default();
+        let summary2 = example2::MyCircuit::<Fp>::configure(&mut meta).column_summary();
+        println!("example1: {:?}", summary1);
+        println!("example2: {:?}", summary2);
+
+        assert_eq!(
+            summary1,
+            ColumnSummary {
+                advice: 3,
+                fixed: 0,
+                instance: 1,
+                selectors: 1,
+            }
+        );
+        assert_eq!(
+            summary2,
+            ColumnSummary {
+                advice: 1,
+                fixed: 1,
+                instance: 1,
+                selectors: 1,
+            }
+        );
+    }
+}
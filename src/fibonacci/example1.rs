@@ -1,6 +1,9 @@
 use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
 use std::marker::PhantomData;
 
+use super::{ColumnSummary, FiboInstructions};
+use crate::error::CircuitError;
+
 //
 // selector | col_a | col_b | col_c
 // ---------+-------+-------+-------
@@ -10,20 +13,44 @@ use std::marker::PhantomData;
 // here, we copy the values from previous row(b and c) to the next row(a and b)
 // ==> a1 = b0, b1 = c0
 // So, we need to turn on permutation check on a, b and c
+//
+// The values themselves are computed by compute_witnesses, in plain Rust and
+// before any region exists. Working out the witnesses is the prover's business
+// and happens outside the constraint system: the gate in configure only states
+// what the values must satisfy, and the assign_* functions only put the values
+// in cells. A wrong value placed by the prover isn't fixed by the circuit, it
+// just fails to verify.
+
+// the first n terms of the sequence starting from a, b
+pub fn compute_witnesses<F: FieldExt>(a: F, b: F, n: usize) -> Vec<F> {
+    let mut terms = vec![a, b];
+    for i in 2..n {
+        terms.push(terms[i - 2] + terms[i - 1]);
+    }
+    terms.truncate(n);
+    terms
+}
 
 #[derive(Debug, Clone)]
-struct FiboConfig {
+pub struct FiboConfig {
     pub advice: [Column<Advice>; 3],
     pub instance: Column<Instance>,
     pub selector: Selector,
 }
 
-struct FiboChip<F: FieldExt> {
+impl FiboConfig {
+    pub fn column_summary(&self) -> ColumnSummary {
+        ColumnSummary::of(&self.advice, &[], &[self.instance], &[self.selector])
+    }
+}
+
+// N is the index of the last Fibonacci term, e.g. N = 10 => the 10th term
+pub struct FiboChip<F: FieldExt, const N: usize> {
     config: FiboConfig,
     _marker: PhantomData<F>,
 }
 
-impl<F: FieldExt> FiboChip<F> {
+impl<F: FieldExt, const N: usize> FiboChip<F, N> {
     pub fn construct(config: FiboConfig) -> Self {
         Self {
             config,
@@ -62,11 +89,47 @@ impl<F: FieldExt> FiboChip<F> {
         }
     }
 
+    pub fn assign_first_row(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: Option<F>,
+        b: Option<F>,
+        c: Option<F>,
+    ) -> Result<[AssignedCell<F, F>; 3], Error> {
+        layouter.assign_region(
+            || "first row",
+            |mut region| {
+                self.config.selector.enable(&mut region, 0)?;
+                let a_cell = region.assign_advice(
+                    || "a",
+                    self.config.advice[0],
+                    0,
+                    || a.ok_or(CircuitError::MissingWitness.into()),
+                )?;
+                let b_cell = region.assign_advice(
+                    || "b",
+                    self.config.advice[1],
+                    0,
+                    || b.ok_or(CircuitError::MissingWitness.into()),
+                )?;
+                let c_cell = region.assign_advice(
+                    || "c",
+                    self.config.advice[2],
+                    0,
+                    || c.ok_or(CircuitError::MissingWitness.into()),
+                )?;
+
+                Ok([a_cell, b_cell, c_cell])
+            },
+        )
+    }
+
     pub fn assign_row(
         &self,
         mut layouter: impl Layouter<F>,
-        prev_b: Option<F>,
-        prev_c: Option<F>,
+        prev_b: &AssignedCell<F, F>,
+        prev_c: &AssignedCell<F, F>,
+        c: Option<F>,
     ) -> Result<AssignedCell<F, F>, Error> {
         // selector | col_a | col_b | col_c
         // ---------+-------+-------+-------
@@ -77,34 +140,127 @@ impl<F: FieldExt> FiboChip<F> {
             || "row",
             |mut region| {
                 self.config.selector.enable(&mut region, 0)?;
-                let c_val = prev_b.and_then(|b| prev_c.map(|c| b + c));
 
-                // println!("a:{:?}, b:{:?}", prev_b, prev_c);
+                // copy_advice assigns the value and adds the equality constraint
+                prev_b.copy_advice(|| "a", &mut region, self.config.advice[0], 0)?;
+                prev_c.copy_advice(|| "b", &mut region, self.config.advice[1], 0)?;
+
+                // c is precomputed, the gate is what ties it to a and b
                 region.assign_advice(
-                    || "a",
-                    self.config.advice[0],
+                    || "c",
+                    self.config.advice[2],
                     0,
-                    || prev_b.ok_or(Error::Synthesis),
-                )?;
+                    || c.ok_or(CircuitError::MissingWitness.into()),
+                )
+            },
+        )
+    }
+
+    // same as assign_row, but witnesses `a` instead of copying it from prev_b,
+    // so a dishonest prover can be simulated. The equality constraint is still
+    // added, and it's what catches the wrong value.
+    #[cfg(test)]
+    pub fn assign_row_with_override(
+        &self,
+        mut layouter: impl Layouter<F>,
+        prev_b: &AssignedCell<F, F>,
+        prev_c: &AssignedCell<F, F>,
+        a: F,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        layouter.assign_region(
+            || "row",
+            |mut region| {
+                self.config.selector.enable(&mut region, 0)?;
+
+                let a_cell = region.assign_advice(|| "a", self.config.advice[0], 0, || Ok(a))?;
+                region.constrain_equal(a_cell.cell(), prev_b.cell())?;
+                let b = prev_c.copy_advice(|| "b", &mut region, self.config.advice[1], 0)?;
+
+                let c_val = b.value().map(|b| a + *b);
                 region.assign_advice(
-                    || "b",
-                    self.config.advice[1],
-                    0,
-                    || prev_c.ok_or(Error::Synthesis),
-                )?;
-                let c_cell = region.assign_advice(
                     || "c",
                     self.config.advice[2],
                     0,
-                    || c_val.ok_or(Error::Synthesis),
-                )?;
-
-                Ok(c_cell)
+                    || c_val.ok_or(CircuitError::MissingWitness.into()),
+                )
             },
         )
     }
 
-    pub fn expose_public(
+    // assigns the N terms and returns all of their cells, in order, so other
+    // chips can copy any of them
+    pub fn assign_sequence(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: Option<F>,
+        b: Option<F>,
+    ) -> Result<[AssignedCell<F, F>; N], Error> {
+        // the first row alone holds 3 terms
+        if N < 3 {
+            return Err(CircuitError::IterTooSmall {
+                iter_num: N,
+                min: 3,
+            }
+            .into());
+        }
+
+        // None when synthesizing without witnesses, e.g. at keygen
+        let terms = a.zip(b).map(|(a, b)| compute_witnesses(a, b, N));
+        let term = |i: usize| terms.as_ref().map(|terms| terms[i]);
+
+        let mut cells = Vec::with_capacity(N);
+        cells.extend(self.assign_first_row(
+            layouter.namespace(|| "first row"),
+            term(0),
+            term(1),
+            term(2),
+        )?);
+        for i in 3..N {
+            // the previous row's b and c are the last two terms
+            let (prev_b, prev_c) = (&cells[i - 2], &cells[i - 1]);
+            let c_cell =
+                self.assign_row(layouter.namespace(|| "next row"), prev_b, prev_c, term(i))?;
+            cells.push(c_cell);
+        }
+        // one cell per term, so exactly N of them
+        Ok(cells.try_into().unwrap_or_else(|_| unreachable!()))
+    }
+
+    // exposes cells[i] to row i of the instance column
+    pub fn expose_all(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cells: &[AssignedCell<F, F>],
+    ) -> Result<(), Error> {
+        for (row, cell) in cells.iter().enumerate() {
+            layouter.constrain_instance(cell.cell(), self.config.instance, row)?;
+        }
+        Ok(())
+    }
+}
+
+impl<F: FieldExt, const N: usize> FiboInstructions<F> for FiboChip<F, N> {
+    fn assign(
+        &self,
+        layouter: impl Layouter<F>,
+        init_a: Option<F>,
+        init_b: Option<F>,
+        iter_num: usize,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        // the number of terms is part of the chip's type
+        if iter_num != N {
+            return Err(CircuitError::IterMismatch {
+                iter_num,
+                expected: N,
+            }
+            .into());
+        }
+
+        let cells = self.assign_sequence(layouter, init_a, init_b)?;
+        Ok(cells[N - 1].clone())
+    }
+
+    fn expose_public(
         &self,
         mut layouter: impl Layouter<F>,
         cell: &AssignedCell<F, F>,
@@ -114,18 +270,75 @@ impl<F: FieldExt> FiboChip<F> {
     }
 }
 
-#[derive(Default)]
-struct MyCircuit<F> {
+// N is the index of the Fibonacci term to compute, e.g. MyCircuit<F, 10> => the 10th term
+#[derive(Debug, Default)]
+pub struct MyCircuit<F, const N: usize> {
     pub a: Option<F>,
     pub b: Option<F>,
+    // expose every computed term (c of each row) instead of only the last one
+    pub expose_all: bool,
+}
+
+// builds a MyCircuit field by field, anything not set keeps its default.
+// There's no iter_num here, the number of terms is the const N.
+pub struct MyCircuitBuilder<F, const N: usize> {
+    circuit: MyCircuit<F, N>,
+}
+
+impl<F, const N: usize> MyCircuit<F, N> {
+    pub fn builder() -> MyCircuitBuilder<F, N> {
+        MyCircuitBuilder {
+            circuit: Self {
+                a: None,
+                b: None,
+                expose_all: false,
+            },
+        }
+    }
 }
 
-impl<F: FieldExt> Circuit<F> for MyCircuit<F> {
+impl<F: FieldExt, const N: usize> MyCircuit<F, N> {
+    // a and b from plain integers, only the last term is exposed
+    pub fn from_u64(a: u64, b: u64) -> Self {
+        Self {
+            a: Some(F::from(a)),
+            b: Some(F::from(b)),
+            expose_all: false,
+        }
+    }
+}
+
+impl<F, const N: usize> MyCircuitBuilder<F, N> {
+    pub fn a(mut self, a: F) -> Self {
+        self.circuit.a = Some(a);
+        self
+    }
+
+    pub fn b(mut self, b: F) -> Self {
+        self.circuit.b = Some(b);
+        self
+    }
+
+    pub fn expose_all(mut self, expose_all: bool) -> Self {
+        self.circuit.expose_all = expose_all;
+        self
+    }
+
+    pub fn build(self) -> MyCircuit<F, N> {
+        self.circuit
+    }
+}
+
+impl<F: FieldExt, const N: usize> Circuit<F> for MyCircuit<F, N> {
     type Config = FiboConfig;
     type FloorPlanner = SimpleFloorPlanner;
 
     fn without_witnesses(&self) -> Self {
-        Self::default()
+        // `expose_all` decides how many instance rows are used, so keep it
+        Self {
+            expose_all: self.expose_all,
+            ..Self::default()
+        }
     }
 
     fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
@@ -134,7 +347,7 @@ impl<F: FieldExt> Circuit<F> for MyCircuit<F> {
         let col_c = meta.advice_column();
         let instance = meta.instance_column();
 
-        FiboChip::configure(meta, [col_a, col_b, col_c], instance)
+        FiboChip::<F, N>::configure(meta, [col_a, col_b, col_c], instance)
     }
 
     fn synthesize(
@@ -142,18 +355,17 @@ impl<F: FieldExt> Circuit<F> for MyCircuit<F> {
         config: Self::Config,
         mut layouter: impl Layouter<F>,
     ) -> Result<(), Error> {
-        let chip = FiboChip::construct(config);
+        let chip = FiboChip::<F, N>::construct(config);
 
-        let mut prev_b = self.a;
-        let mut prev_c = self.b;
-        let mut c_cell = chip.assign_row(layouter.namespace(|| "next row"), prev_b, prev_c)?;
-        for _i in 3..10 {
-            prev_b = prev_c;
-            prev_c = c_cell.value().map(|v| *v);
-            c_cell = chip.assign_row(layouter.namespace(|| "next row"), prev_b, prev_c)?;
-        }
+        let cells =
+            chip.assign_sequence(layouter.namespace(|| "fibonacci table"), self.a, self.b)?;
 
-        chip.expose_public(layouter.namespace(|| "out"), &c_cell, 0)?;
+        if self.expose_all {
+            // the computed terms, i.e. the c of every row
+            chip.expose_all(layouter.namespace(|| "all terms"), &cells[2..])?;
+        } else {
+            chip.expose_public(layouter.namespace(|| "out"), &cells[N - 1], 0)?;
+        }
 
         Ok(())
     }
@@ -161,19 +373,130 @@ impl<F: FieldExt> Circuit<F> for MyCircuit<F> {
 
 #[cfg(test)]
 mod tests {
-    use super::MyCircuit;
-    use halo2_proofs::{dev::MockProver, pasta::Fp};
+    use super::{compute_witnesses, FiboChip, FiboConfig, MyCircuit};
+    use crate::testing::{assert_fails_with, assert_unsatisfied, nth_fibonacci, proptest_config};
+    use halo2_proofs::{
+        arithmetic::FieldExt,
+        circuit::*,
+        dev::{MockProver, VerifyFailure},
+        pasta::{EqAffine, Fp, Fq},
+        plonk::*,
+        poly::commitment::Params,
+    };
+    use proptest::prelude::*;
+
+    // lays out a single row where c != a + b
+    struct WrongSumCircuit;
+
+    impl Circuit<Fp> for WrongSumCircuit {
+        type Config = FiboConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            MyCircuit::<Fp, 10>::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            layouter.assign_region(
+                || "wrong row",
+                |mut region| {
+                    config.selector.enable(&mut region, 0)?;
+                    for (column, value) in config.advice.iter().zip([1, 2, 4]) {
+                        region.assign_advice(|| "advice", *column, 0, || Ok(Fp::from(value)))?;
+                    }
+                    Ok(())
+                },
+            )
+        }
+    }
+
+    // chains the rows like MyCircuit does, except that the `a` of one row
+    // doesn't match the `b` of the previous row
+    struct BrokenChainCircuit;
+
+    impl Circuit<Fp> for BrokenChainCircuit {
+        type Config = FiboConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            MyCircuit::<Fp, 10>::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = FiboChip::<Fp, 10>::construct(config);
+            let terms = compute_witnesses(Fp::from(1), Fp::from(2), 10);
+
+            let [_, mut prev_b, mut prev_c] = chip.assign_first_row(
+                layouter.namespace(|| "first row"),
+                Some(terms[0]),
+                Some(terms[1]),
+                Some(terms[2]),
+            )?;
+            for i in 3..10 {
+                let c_cell = if i == 5 {
+                    // should be 3
+                    chip.assign_row_with_override(
+                        layouter.namespace(|| "broken row"),
+                        &prev_b,
+                        &prev_c,
+                        Fp::from(100),
+                    )?
+                } else {
+                    // carry on from the wrong value, so only the copy fails
+                    let c = prev_b.value().zip(prev_c.value()).map(|(b, c)| *b + *c);
+                    chip.assign_row(layouter.namespace(|| "next row"), &prev_b, &prev_c, c)?
+                };
+                prev_b = prev_c;
+                prev_c = c_cell;
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_compute_witnesses() {
+        let terms = compute_witnesses(Fp::from(1), Fp::from(1), 10);
+        let expected: Vec<_> = [1, 1, 2, 3, 5, 8, 13, 21, 34, 55]
+            .into_iter()
+            .map(Fp::from)
+            .collect();
+        assert_eq!(terms, expected);
+
+        for n in 1..=10 {
+            let terms = compute_witnesses(Fp::from(1), Fp::from(2), n);
+            assert_eq!(terms.len(), n);
+            assert_eq!(terms[n - 1], Fp::from(nth_fibonacci(1, 2, n)));
+        }
+    }
+
     #[test]
     fn test_example1() {
         let k = 4;
 
         let a = Fp::from(1);
         let b = Fp::from(2);
-        let out = Fp::from(89);
+        let out = Fp::from(nth_fibonacci(1, 2, 10));
 
-        let circuit = MyCircuit {
+        let circuit = MyCircuit::<_, 10> {
             a: Some(a),
             b: Some(b),
+            expose_all: false,
         };
 
         let public_input = vec![out];
@@ -181,22 +504,255 @@ mod tests {
         prover.assert_satisfied();
     }
 
+    #[test]
+    fn test_example1_n() {
+        let a = Fp::from(1);
+        let b = Fp::from(2);
+
+        // 1, 2, 3, 5, 8, 13, ...
+        let circuit = MyCircuit::<_, 6> {
+            a: Some(a),
+            b: Some(b),
+            expose_all: false,
+        };
+        let prover = MockProver::run(4, &circuit, vec![vec![Fp::from(13)]]).unwrap();
+        prover.assert_satisfied();
+
+        // one region per term, 13 rows no longer fit in k = 4
+        let circuit = MyCircuit::<_, 15> {
+            a: Some(a),
+            b: Some(b),
+            expose_all: false,
+        };
+        let prover = MockProver::run(5, &circuit, vec![vec![Fp::from(987)]]).unwrap();
+        prover.assert_satisfied();
+    }
+
+    #[test]
+    fn test_example1_n_too_small() {
+        // the first row alone holds 3 terms, so N = 2 is rejected instead of
+        // indexing past the sequence
+        let circuit = MyCircuit::<Fp, 2>::from_u64(1, 2);
+        assert!(MockProver::run(4, &circuit, vec![vec![Fp::from(2)]]).is_err());
+    }
+
+    #[test]
+    fn test_example1_expose_all() {
+        let k = 4;
+
+        let a = Fp::from(1);
+        let b = Fp::from(2);
+
+        let circuit = MyCircuit::<_, 10> {
+            a: Some(a),
+            b: Some(b),
+            expose_all: true,
+        };
+
+        // every c from the 3rd term to the 10th term
+        let public_input: Vec<_> = (3..=10).map(|n| Fp::from(nth_fibonacci(1, 2, n))).collect();
+        let prover = MockProver::run(k, &circuit, vec![public_input.clone()]).unwrap();
+        prover.assert_satisfied();
+    }
+
+    // the chip is generic over the field, so the same 10 terms should work in any of them
+    fn run_fib<F: FieldExt>(a: u64, b: u64, out: u64, k: u32) {
+        let circuit = MyCircuit::<F, 10> {
+            a: Some(F::from(a)),
+            b: Some(F::from(b)),
+            expose_all: false,
+        };
+
+        let public_input = vec![F::from(out)];
+        let prover = MockProver::run(k, &circuit, vec![public_input]).unwrap();
+        prover.assert_satisfied();
+    }
+
+    #[test]
+    fn test_example1_fields() {
+        run_fib::<Fp>(1, 2, nth_fibonacci(1, 2, 10), 4);
+        run_fib::<Fq>(1, 2, nth_fibonacci(1, 2, 10), 4);
+    }
+
+    // exposes the last of the cells returned by assign_sequence, after
+    // checking them against the plain Rust sequence
+    struct SequenceCircuit;
+
+    impl Circuit<Fp> for SequenceCircuit {
+        type Config = FiboConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            MyCircuit::<Fp, 10>::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = FiboChip::<Fp, 10>::construct(config.clone());
+
+            let cells = chip.assign_sequence(
+                layouter.namespace(|| "fibonacci table"),
+                Some(Fp::from(1)),
+                Some(Fp::from(2)),
+            )?;
+            for (i, cell) in cells.iter().enumerate() {
+                assert_eq!(cell.value(), Some(&Fp::from(nth_fibonacci(1, 2, i + 1))));
+            }
+
+            layouter.constrain_instance(cells[9].cell(), config.instance, 0)
+        }
+    }
+
+    #[test]
+    fn test_example1_sequence() {
+        let k = 4;
+
+        let public_input = vec![Fp::from(nth_fibonacci(1, 2, 10))];
+        let prover = MockProver::run(k, &SequenceCircuit, vec![public_input]).unwrap();
+        prover.assert_satisfied();
+    }
+
+    #[test]
+    fn test_example1_keygen_unknown() {
+        let k = 4;
+
+        // keygen only looks at the shape of the circuit and never evaluates the
+        // advice assignments, so the `ok_or(CircuitError::MissingWitness.into())`
+        // on the missing witnesses isn't hit
+        let params: Params<EqAffine> = Params::new(k);
+        let circuit = MyCircuit::<Fp, 10>::default();
+        let vk = keygen_vk(&params, &circuit).unwrap();
+        assert!(keygen_pk(&params, vk, &circuit).is_ok());
+    }
+
+    #[test]
+    fn test_example1_without_witnesses() {
+        let k = 4;
+        let params: Params<EqAffine> = Params::new(k);
+
+        // VerifyingKey has no `write` in halo2_proofs 0.1.0, but the Debug output
+        // of its pinned form covers the domain, the constraint system and the
+        // fixed and permutation commitments
+        let pinned_vk = |circuit: &MyCircuit<Fp, 10>| {
+            let vk = keygen_vk(&params, circuit).unwrap();
+            format!("{:?}", vk.pinned())
+        };
+
+        let mut vks = vec![];
+        for expose_all in [false, true] {
+            let circuit = MyCircuit::<Fp, 10> {
+                a: Some(Fp::from(1)),
+                b: Some(Fp::from(2)),
+                expose_all,
+            };
+
+            let vk = pinned_vk(&circuit);
+            assert_eq!(vk, pinned_vk(&circuit.without_witnesses()));
+            vks.push(vk);
+        }
+
+        // expose_all changes the copies to the instance column, so dropping it
+        // in without_witnesses would give the wrong keys
+        assert_ne!(vks[0], vks[1]);
+    }
+
+    #[test]
+    fn test_example1_wraparound() {
+        let k = 4;
+
+        // All the arithmetic is mod p, the modulus of Fp:
+        //   p = 0x40000000000000000000000000000000224698fc094cf91b992d30ed00000001
+        // so -1 is the field element p - 1, and the first row
+        //   a + b = (p - 1) + 2 = p + 1 = 1 (mod p)
+        // holds for c = 1, although as integers (p - 1) + 2 is a 255-bit number.
+        let a = -Fp::one();
+        let b = Fp::from(2);
+        assert_eq!(a + b, Fp::one());
+
+        // -1, 2, 1, 3, 4, 7, 11, 18, 29, 47
+        let circuit = MyCircuit::<_, 10> {
+            a: Some(a),
+            b: Some(b),
+            expose_all: false,
+        };
+
+        let public_input = vec![Fp::from(47)];
+        let prover = MockProver::run(k, &circuit, vec![public_input]).unwrap();
+        prover.assert_satisfied();
+    }
+
     #[test]
     fn test_example1_failed() {
         let k = 4;
 
         let a = Fp::from(1);
         let b = Fp::from(2);
-        let out = Fp::from(90);
+        let out = Fp::from(nth_fibonacci(1, 2, 10) + 1);
 
-        let circuit = MyCircuit {
+        let circuit = MyCircuit::<_, 10> {
             a: Some(a),
             b: Some(b),
+            expose_all: false,
         };
 
         let public_input = vec![out];
-        let prover = MockProver::run(k, &circuit, vec![public_input.clone()]).unwrap();
-        // prover.assert_satisfied();
+        // the 10th term is 89, so the copy constraint between the last c and
+        // the instance column fails and the wrong public input is rejected
+        assert_unsatisfied(k, &circuit, vec![public_input]);
+    }
+
+    #[test]
+    fn test_example1_wrong_sum() {
+        let k = 4;
+
+        // 1 + 2 != 4
+        let prover = MockProver::run(k, &WrongSumCircuit, vec![vec![]]).unwrap();
+        assert_fails_with(&prover, "add");
+    }
+
+    #[test]
+    fn test_example1_broken_chain() {
+        let k = 4;
+
+        // every row still satisfies a + b = c, only the equality constraint
+        // between the rows (enabled by `enable_equality`) catches the wrong value
+        let prover = MockProver::run(k, &BrokenChainCircuit, vec![vec![]]).unwrap();
+        let failures = prover.verify().unwrap_err();
+        assert!(!failures.is_empty());
+        assert!(failures
+            .iter()
+            .all(|failure| matches!(failure, VerifyFailure::Permutation { .. })));
+    }
+
+    proptest! {
+        // every case runs MockProver twice, so keep the count down
+        #![proptest_config(proptest_config(32))]
+
+        #[test]
+        fn test_example1_prop(a in 0u64..1000, b in 0u64..1000, delta in 1u64..1000) {
+            let k = 4;
+
+            let out = Fp::from(nth_fibonacci(a, b, 10));
+
+            let circuit = MyCircuit::<_, 10> {
+                a: Some(Fp::from(a)),
+                b: Some(Fp::from(b)),
+                expose_all: false,
+            };
+
+            let prover = MockProver::run(k, &circuit, vec![vec![out]]).unwrap();
+            prop_assert!(prover.verify().is_ok());
+
+            let prover = MockProver::run(k, &circuit, vec![vec![out + Fp::from(delta)]]).unwrap();
+            prop_assert!(prover.verify().is_err());
+        }
     }
 
     #[cfg(feature = "dev-graph")]
@@ -210,9 +766,10 @@ mod tests {
 
         let a = Fp::from(1);
         let b = Fp::from(1);
-        let circuit = MyCircuit {
+        let circuit = MyCircuit::<_, 10> {
             a: Some(a),
             b: Some(b),
+            expose_all: false,
         };
         halo2_proofs::dev::CircuitLayout::default()
             .render(4, &circuit, &root)
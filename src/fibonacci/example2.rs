@@ -1,6 +1,9 @@
-use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, pasta::Fp, plonk::*, poly::Rotation};
 use std::marker::PhantomData;
 
+use super::{prove::MAX_K, usable_rows, ColumnSummary, FiboInstructions};
+use crate::error::CircuitError;
+
 //
 // selector |       col    |
 // ---------+--------------|
@@ -10,15 +13,45 @@ use std::marker::PhantomData;
 //   s3     | a3 = a1 + a2 |
 //
 // In this example, we only use one advice column
+//
+// The fixed column `constant` is only there so that a0 and a1 can be hardcoded
+// (see load_constant and ConstantCircuit): halo2 puts the constants in it and
+// copies them into the advice column.
 
 #[derive(Debug, Clone)]
-struct FiboConfig {
+pub struct FiboConfig {
     pub advice: Column<Advice>,
     pub instance: Column<Instance>,
+    pub constant: Column<Fixed>,
     pub selector: Selector,
 }
 
-struct FiboChip<F: FieldExt> {
+impl FiboConfig {
+    pub fn column_summary(&self) -> ColumnSummary {
+        ColumnSummary::of(
+            &[self.advice],
+            &[self.constant],
+            &[self.instance],
+            &[self.selector],
+        )
+    }
+}
+
+// rows 0 and 1 always have the selector on, and each enabled row also
+// touches the next two, so the table needs at least 4 rows
+const MIN_ITER_NUM: usize = 4;
+
+fn check_iter_num(iter_num: usize) -> Result<(), CircuitError> {
+    if iter_num < MIN_ITER_NUM {
+        return Err(CircuitError::IterTooSmall {
+            iter_num,
+            min: MIN_ITER_NUM,
+        });
+    }
+    Ok(())
+}
+
+pub struct FiboChip<F: FieldExt> {
     config: FiboConfig,
     _marker: PhantomData<F>,
 }
@@ -35,12 +68,15 @@ impl<F: FieldExt> FiboChip<F> {
         meta: &mut ConstraintSystem<F>,
         advice: Column<Advice>,
         instance: Column<Instance>,
+        constant: Column<Fixed>,
     ) -> FiboConfig {
         let selector = meta.selector();
 
         // for permutation check
         meta.enable_equality(advice);
         meta.enable_equality(instance);
+        // also enables equality on it
+        meta.enable_constant(constant);
 
         meta.create_gate("add", |meta| {
             let s = meta.query_selector(selector);
@@ -53,61 +89,259 @@ impl<F: FieldExt> FiboChip<F> {
         FiboConfig {
             advice,
             instance,
+            constant,
             selector,
         }
     }
 
-    pub fn assign(
+    // assigns `value` to an advice cell and constrains it to be equal to the
+    // same value in the fixed column
+    pub fn load_constant(
         &self,
         mut layouter: impl Layouter<F>,
-        init_a: Option<F>,
+        value: F,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        layouter.assign_region(
+            || "load constant",
+            |mut region| {
+                region.assign_advice_from_constant(|| "constant", self.config.advice, 0, value)
+            },
+        )
+    }
+
+    pub fn load_private(
+        &self,
+        mut layouter: impl Layouter<F>,
+        value: Option<F>,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        layouter.assign_region(
+            || "load private",
+            |mut region| {
+                region.assign_advice(
+                    || "private input",
+                    self.config.advice,
+                    0,
+                    || value.ok_or(CircuitError::MissingWitness.into()),
+                )
+            },
+        )
+    }
+
+    // constrains a and b to rows 1 and 2 of the instance column, row 0 is left
+    // for the output
+    pub fn expose_inputs(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a_cell: &AssignedCell<F, F>,
+        b_cell: &AssignedCell<F, F>,
+    ) -> Result<(), Error> {
+        layouter.constrain_instance(a_cell.cell(), self.config.instance, 1)?;
+        layouter.constrain_instance(b_cell.cell(), self.config.instance, 2)
+    }
+
+    // same as `assign`, but a and b are copied from cells assigned elsewhere
+    pub fn assign_from_cells(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &AssignedCell<F, F>,
+        b: &AssignedCell<F, F>,
+        iter_num: usize,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        check_iter_num(iter_num)?;
+
+        layouter.assign_region(
+            || "fibonacci region",
+            |mut region| {
+                let a_cell = a.copy_advice(|| "a", &mut region, self.config.advice, 0)?;
+                let b_cell = b.copy_advice(|| "b", &mut region, self.config.advice, 1)?;
+                self.assign_rest(&mut region, a_cell.value().copied(), b_cell, iter_num)
+            },
+        )
+    }
+
+    // same as `assign`, but a is read from the given row of the instance
+    // column instead of being witnessed. assign_advice_from_instance copies the
+    // public value into the advice column (with an equality constraint), so the
+    // first gate uses it like any other advice cell; the other direction of
+    // constrain_instance.
+    pub fn assign_with_public_a(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a_row: usize,
         init_b: Option<F>,
         iter_num: usize,
     ) -> Result<AssignedCell<F, F>, Error> {
+        check_iter_num(iter_num)?;
+
         layouter.assign_region(
             || "fibonacci region",
             |mut region| {
-                self.config.selector.enable(&mut region, 0)?;
-                self.config.selector.enable(&mut region, 1)?;
+                let a_cell = region.assign_advice_from_instance(
+                    || "a",
+                    self.config.instance,
+                    a_row,
+                    self.config.advice,
+                    0,
+                )?;
+                let b_cell = region.assign_advice(
+                    || "b",
+                    self.config.advice,
+                    1,
+                    || init_b.ok_or(CircuitError::MissingWitness.into()),
+                )?;
+                self.assign_rest(&mut region, a_cell.value().copied(), b_cell, iter_num)
+            },
+        )
+    }
 
-                let mut a = init_a.clone();
-                let mut b = init_b.clone();
+    // same as `assign_with_public_a`, with b read from the instance column too,
+    // so the table doesn't take any witness
+    pub fn assign_with_public_seed(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a_row: usize,
+        b_row: usize,
+        iter_num: usize,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        check_iter_num(iter_num)?;
 
-                region.assign_advice(
+        layouter.assign_region(
+            || "fibonacci region",
+            |mut region| {
+                let a_cell = region.assign_advice_from_instance(
                     || "a",
+                    self.config.instance,
+                    a_row,
                     self.config.advice,
                     0,
-                    || a.ok_or(Error::Synthesis),
                 )?;
-                let mut b_cell = region.assign_advice(
+                let b_cell = region.assign_advice_from_instance(
                     || "b",
+                    self.config.instance,
+                    b_row,
                     self.config.advice,
                     1,
-                    || b.ok_or(Error::Synthesis),
                 )?;
+                self.assign_rest(&mut region, a_cell.value().copied(), b_cell, iter_num)
+            },
+        )
+    }
 
-                for row in 2..iter_num {
-                    // not to enable selector in the last two rows
-                    if row < iter_num - 2 {
-                        self.config.selector.enable(&mut region, row)?;
-                    }
+    // same table as `assign`, laid out in two separate steps.
+    //
+    // assign_rest enables the selector while it walks the rows: rows 0 and 1
+    // before the loop, then the row it's assigning, unless it's one of the last
+    // two. That's because the selector on row r checks rows r, r + 1 and r + 2,
+    // so it can only go on rows 0..iter_num - 2, but the loop runs over
+    // 2..iter_num and mixes "which term goes in this row" with "which gate
+    // starts at this row". Getting the bound wrong by one either leaves the
+    // last term unchecked or turns on a gate reading past the table.
+    //
+    // Here the iter_num - 2 gates are enabled up front, then the terms are
+    // assigned, with nothing shared between the two loops.
+    pub fn assign_clean(
+        &self,
+        mut layouter: impl Layouter<F>,
+        init_a: Option<F>,
+        init_b: Option<F>,
+        iter_num: usize,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        check_iter_num(iter_num)?;
+
+        let mut terms = vec![init_a, init_b];
+        for i in 2..iter_num {
+            terms.push(terms[i - 2].zip(terms[i - 1]).map(|(a, b)| a + b));
+        }
 
-                    b_cell = region.assign_advice(
+        layouter.assign_region(
+            || "fibonacci region",
+            |mut region| {
+                // one gate per computed term, starting two rows above it
+                let gates = iter_num - 2;
+                for row in 0..gates {
+                    self.config.selector.enable(&mut region, row)?;
+                }
+
+                let mut cells = Vec::with_capacity(iter_num);
+                for (row, term) in terms.iter().enumerate() {
+                    cells.push(region.assign_advice(
                         || "advice",
                         self.config.advice,
                         row,
-                        || b.and_then(|b| a.map(|a| a + b)).ok_or(Error::Synthesis),
-                    )?;
-
-                    a = b;
-                    b = b_cell.value().map(|v| *v);
+                        || term.ok_or(CircuitError::MissingWitness.into()),
+                    )?);
                 }
-                Ok(b_cell)
+                Ok(cells.pop().unwrap())
+            },
+        )
+    }
+
+    // turns the selector on and fills rows 2..iter_num, given rows 0 and 1
+    fn assign_rest(
+        &self,
+        region: &mut Region<'_, F>,
+        mut a: Option<F>,
+        mut b_cell: AssignedCell<F, F>,
+        iter_num: usize,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        self.config.selector.enable(region, 0)?;
+        self.config.selector.enable(region, 1)?;
+
+        let mut b = b_cell.value().copied();
+        for row in 2..iter_num {
+            // not to enable selector in the last two rows
+            if row < iter_num - 2 {
+                self.config.selector.enable(region, row)?;
+            }
+
+            b_cell = region.assign_advice(
+                || "advice",
+                self.config.advice,
+                row,
+                || {
+                    b.and_then(|b| a.map(|a| a + b))
+                        .ok_or(CircuitError::MissingWitness.into())
+                },
+            )?;
+
+            a = b;
+            b = b_cell.value().copied();
+        }
+        Ok(b_cell)
+    }
+}
+
+impl<F: FieldExt> FiboInstructions<F> for FiboChip<F> {
+    fn assign(
+        &self,
+        mut layouter: impl Layouter<F>,
+        init_a: Option<F>,
+        init_b: Option<F>,
+        iter_num: usize,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        check_iter_num(iter_num)?;
+
+        layouter.assign_region(
+            || "fibonacci region",
+            |mut region| {
+                region.assign_advice(
+                    || "a",
+                    self.config.advice,
+                    0,
+                    || init_a.ok_or(CircuitError::MissingWitness.into()),
+                )?;
+                let b_cell = region.assign_advice(
+                    || "b",
+                    self.config.advice,
+                    1,
+                    || init_b.ok_or(CircuitError::MissingWitness.into()),
+                )?;
+                self.assign_rest(&mut region, init_a, b_cell, iter_num)
             },
         )
     }
 
-    pub fn expose_public(
+    fn expose_public(
         &self,
         mut layouter: impl Layouter<F>,
         cell: &AssignedCell<F, F>,
@@ -117,10 +351,99 @@ impl<F: FieldExt> FiboChip<F> {
     }
 }
 
-#[derive(Default)]
-struct MyCircuit<F> {
+#[derive(Debug)]
+pub struct MyCircuit<F> {
     pub a: Option<F>,
     pub b: Option<F>,
+    // the number of rows in the table, which is also the index of the output term
+    pub iter_num: usize,
+}
+
+impl<F> Default for MyCircuit<F> {
+    fn default() -> Self {
+        Self {
+            a: None,
+            b: None,
+            iter_num: 10,
+        }
+    }
+}
+
+// builds a MyCircuit field by field, anything not set keeps its default
+pub struct MyCircuitBuilder<F> {
+    circuit: MyCircuit<F>,
+}
+
+impl<F> MyCircuit<F> {
+    pub fn builder() -> MyCircuitBuilder<F> {
+        MyCircuitBuilder {
+            circuit: Self::default(),
+        }
+    }
+}
+
+impl<F: FieldExt> MyCircuit<F> {
+    // a and b from plain integers, iter_num keeps its default
+    pub fn from_u64(a: u64, b: u64) -> Self {
+        Self {
+            a: Some(F::from(a)),
+            b: Some(F::from(b)),
+            ..Self::default()
+        }
+    }
+
+    // checks that the table fits in 2^k rows before running a prover on it, as
+    // halo2 would only fail with Error::NotEnoughRowsAvailable in the layouter.
+    // The rows halo2 reserves can't hold the table, see usable_rows.
+    pub fn validate(&self, k: u32) -> Result<(), CircuitError> {
+        check_iter_num(self.iter_num)?;
+
+        let mut meta = ConstraintSystem::<F>::default();
+        Self::configure(&mut meta);
+        let available = usable_rows(k, &meta);
+
+        if self.iter_num > available {
+            return Err(CircuitError::TooManyRows {
+                needed: self.iter_num,
+                available,
+            });
+        }
+        Ok(())
+    }
+}
+
+// the smallest k whose 2^k rows fit a table of iter_num rows, once halo2 has
+// reserved its rows (see usable_rows). The height of the circuit is 2^k, so
+// every term past a power of two, minus the reserved rows, costs one more k:
+// 10 terms fit in k = 4, 11 need k = 5.
+// None if the table doesn't fit in 2^MAX_K rows.
+pub fn min_k_for(iter_num: usize) -> Option<u32> {
+    let mut meta = ConstraintSystem::<Fp>::default();
+    MyCircuit::<Fp>::configure(&mut meta);
+
+    (1..=MAX_K).find(|&k| usable_rows(k, &meta) >= iter_num)
+}
+
+impl<F> MyCircuitBuilder<F> {
+    pub fn a(mut self, a: F) -> Self {
+        self.circuit.a = Some(a);
+        self
+    }
+
+    pub fn b(mut self, b: F) -> Self {
+        self.circuit.b = Some(b);
+        self
+    }
+
+    pub fn iter_num(mut self, iter_num: usize) -> Self {
+        self.circuit.iter_num = iter_num;
+        self
+    }
+
+    pub fn build(self) -> Result<MyCircuit<F>, CircuitError> {
+        check_iter_num(self.circuit.iter_num)?;
+        Ok(self.circuit)
+    }
 }
 
 impl<F: FieldExt> Circuit<F> for MyCircuit<F> {
@@ -128,13 +451,17 @@ impl<F: FieldExt> Circuit<F> for MyCircuit<F> {
     type FloorPlanner = SimpleFloorPlanner;
 
     fn without_witnesses(&self) -> Self {
-        Self::default()
+        Self {
+            iter_num: self.iter_num,
+            ..Self::default()
+        }
     }
 
     fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
         let advice = meta.advice_column();
         let instance = meta.instance_column();
-        FiboChip::configure(meta, advice, instance)
+        let constant = meta.fixed_column();
+        FiboChip::configure(meta, advice, instance, constant)
     }
 
     fn synthesize(
@@ -143,28 +470,317 @@ impl<F: FieldExt> Circuit<F> for MyCircuit<F> {
         mut layouter: impl Layouter<F>,
     ) -> Result<(), Error> {
         let chip = FiboChip::construct(config);
-        let c_cell = chip.assign(layouter.namespace(|| "fibonacci table"), self.a, self.b, 10)?;
+        let c_cell = chip.assign(
+            layouter.namespace(|| "fibonacci table"),
+            self.a,
+            self.b,
+            self.iter_num,
+        )?;
         chip.expose_public(layouter.namespace(|| "out"), &c_cell, 0)?;
 
         Ok(())
     }
 }
 
+// the sequence starting from the hardcoded a = 1, b = 1 instead of witnesses
+pub struct ConstantCircuit {
+    pub iter_num: usize,
+}
+
+impl Default for ConstantCircuit {
+    fn default() -> Self {
+        Self { iter_num: 10 }
+    }
+}
+
+impl<F: FieldExt> Circuit<F> for ConstantCircuit {
+    type Config = FiboConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            iter_num: self.iter_num,
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        MyCircuit::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let chip = FiboChip::construct(config);
+        let a = chip.load_constant(layouter.namespace(|| "a"), F::one())?;
+        let b = chip.load_constant(layouter.namespace(|| "b"), F::one())?;
+        let c_cell = chip.assign_from_cells(
+            layouter.namespace(|| "fibonacci table"),
+            &a,
+            &b,
+            self.iter_num,
+        )?;
+        chip.expose_public(layouter.namespace(|| "out"), &c_cell, 0)?;
+
+        Ok(())
+    }
+}
+
+// the same table as MyCircuit, but a and b are public too: the instance
+// column holds [out, a, b]
+pub struct PublicInputsCircuit<F> {
+    pub a: Option<F>,
+    pub b: Option<F>,
+    pub iter_num: usize,
+}
+
+impl<F> Default for PublicInputsCircuit<F> {
+    fn default() -> Self {
+        Self {
+            a: None,
+            b: None,
+            iter_num: 10,
+        }
+    }
+}
+
+impl<F: FieldExt> Circuit<F> for PublicInputsCircuit<F> {
+    type Config = FiboConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            iter_num: self.iter_num,
+            ..Self::default()
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        MyCircuit::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let chip = FiboChip::construct(config);
+        let a = chip.load_private(layouter.namespace(|| "a"), self.a)?;
+        let b = chip.load_private(layouter.namespace(|| "b"), self.b)?;
+        chip.expose_inputs(layouter.namespace(|| "inputs"), &a, &b)?;
+
+        let c_cell = chip.assign_from_cells(
+            layouter.namespace(|| "fibonacci table"),
+            &a,
+            &b,
+            self.iter_num,
+        )?;
+        chip.expose_public(layouter.namespace(|| "out"), &c_cell, 0)?;
+
+        Ok(())
+    }
+}
+
+// the same table as MyCircuit, but a is public and read from the instance
+// column during assignment: the instance column holds [a, out]
+pub struct InstanceInputCircuit<F> {
+    pub b: Option<F>,
+    pub iter_num: usize,
+}
+
+impl<F> Default for InstanceInputCircuit<F> {
+    fn default() -> Self {
+        Self {
+            b: None,
+            iter_num: 10,
+        }
+    }
+}
+
+impl<F: FieldExt> Circuit<F> for InstanceInputCircuit<F> {
+    type Config = FiboConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            iter_num: self.iter_num,
+            ..Self::default()
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        MyCircuit::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let chip = FiboChip::construct(config);
+        let c_cell = chip.assign_with_public_a(
+            layouter.namespace(|| "fibonacci table"),
+            0,
+            self.b,
+            self.iter_num,
+        )?;
+        chip.expose_public(layouter.namespace(|| "out"), &c_cell, 1)?;
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MultiInstanceConfig {
+    pub fib: FiboConfig,
+    // the second instance column
+    pub iter_num: Column<Instance>,
+}
+
+// MyCircuit with iter_num as a second public input, in its own instance column.
+//
+// MockProver::run (and create_proof/verify_proof) take the instances as a
+// Vec<Vec<F>>: one inner vector per instance column, in the order the columns
+// were created in configure, and each inner vector lists that column's rows.
+// Here that's
+//   vec![vec![out], vec![iter_num]]
+pub struct MultiInstanceCircuit<F> {
+    pub a: Option<F>,
+    pub b: Option<F>,
+    pub iter_num: usize,
+}
+
+impl<F> Default for MultiInstanceCircuit<F> {
+    fn default() -> Self {
+        Self {
+            a: None,
+            b: None,
+            iter_num: 10,
+        }
+    }
+}
+
+impl<F: FieldExt> Circuit<F> for MultiInstanceCircuit<F> {
+    type Config = MultiInstanceConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            iter_num: self.iter_num,
+            ..Self::default()
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        // creates the first instance column
+        let fib = MyCircuit::configure(meta);
+        let iter_num = meta.instance_column();
+        meta.enable_equality(iter_num);
+
+        MultiInstanceConfig { fib, iter_num }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let chip = FiboChip::construct(config.fib);
+
+        let c_cell = chip.assign(
+            layouter.namespace(|| "fibonacci table"),
+            self.a,
+            self.b,
+            self.iter_num,
+        )?;
+        chip.expose_public(layouter.namespace(|| "out"), &c_cell, 0)?;
+
+        // iter_num is part of the circuit itself, so it's a constant rather than
+        // a witness, and the public one has to agree with it
+        let iter_num = chip.load_constant(
+            layouter.namespace(|| "iter_num"),
+            F::from(self.iter_num as u64),
+        )?;
+        layouter.constrain_instance(iter_num.cell(), config.iter_num, 0)?;
+
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::MyCircuit;
-    use halo2_proofs::{dev::MockProver, pasta::Fp};
+    use super::{
+        min_k_for, ConstantCircuit, FiboChip, FiboConfig, InstanceInputCircuit,
+        MultiInstanceCircuit, MyCircuit, PublicInputsCircuit,
+    };
+    use crate::error::CircuitError;
+    use crate::fibonacci::prove::prove_and_verify;
+    use crate::fibonacci::FiboInstructions;
+    use crate::testing::{
+        assert_fails_with, assert_unsatisfied, dump_regions, enabled_rows, nth_fibonacci,
+        proptest_config,
+    };
+    use halo2_proofs::{
+        arithmetic::FieldExt,
+        circuit::*,
+        dev::MockProver,
+        pasta::{Fp, Fq},
+        plonk::*,
+    };
+    use proptest::prelude::*;
+
+    // lays out 1, 2, 3, 6 where the last term should be 5
+    struct WrongSumCircuit;
+
+    impl Circuit<Fp> for WrongSumCircuit {
+        type Config = FiboConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            MyCircuit::<Fp>::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            layouter.assign_region(
+                || "wrong table",
+                |mut region| {
+                    config.selector.enable(&mut region, 0)?;
+                    config.selector.enable(&mut region, 1)?;
+                    for (row, value) in [1, 2, 3, 6].into_iter().enumerate() {
+                        region.assign_advice(
+                            || "advice",
+                            config.advice,
+                            row,
+                            || Ok(Fp::from(value)),
+                        )?;
+                    }
+                    Ok(())
+                },
+            )
+        }
+    }
+
     #[test]
     fn test_example2() {
-        let k = 2;
+        let k = min_k_for(10).unwrap();
 
         let a = Fp::from(1);
         let b = Fp::from(2);
-        let out = Fp::from(89);
+        let out = Fp::from(nth_fibonacci(1, 2, 10));
 
         let circuit = MyCircuit {
             a: Some(a),
             b: Some(b),
+            iter_num: 10,
         };
 
         let public_input = vec![out];
@@ -172,22 +788,385 @@ mod tests {
         prover.assert_satisfied();
     }
 
+    #[test]
+    fn test_example2_iter_num() {
+        let a = Fp::from(1);
+        let b = Fp::from(2);
+
+        // 1, 2, 3, 5, 8, 13, ...
+        // iter_num = 4 is the smallest table, where the selector is only on in rows 0 and 1
+        for (iter_num, out) in [(4, 5), (6, 13)] {
+            let circuit = MyCircuit {
+                a: Some(a),
+                b: Some(b),
+                iter_num,
+            };
+
+            let public_input = vec![Fp::from(out)];
+            let prover = MockProver::run(
+                min_k_for(iter_num).unwrap(),
+                &circuit,
+                vec![public_input.clone()],
+            )
+            .unwrap();
+            prover.assert_satisfied();
+        }
+    }
+
+    // the chip is generic over the field, so the same 10 terms should work in any of them
+    fn run_fib<F: FieldExt>(a: u64, b: u64, out: u64, k: u32) {
+        let circuit = MyCircuit::<F>::from_u64(a, b);
+
+        let public_input = vec![F::from(out)];
+        let prover = MockProver::run(k, &circuit, vec![public_input]).unwrap();
+        prover.assert_satisfied();
+    }
+
+    #[test]
+    fn test_example2_fields() {
+        run_fib::<Fp>(1, 2, nth_fibonacci(1, 2, 10), 4);
+        run_fib::<Fq>(1, 2, nth_fibonacci(1, 2, 10), 4);
+    }
+
+    #[test]
+    fn test_example2_from_u64() {
+        let k = 4;
+
+        let circuit = MyCircuit::from_u64(1, 2);
+        assert_eq!(circuit.a, Some(Fp::from(1)));
+        assert_eq!(circuit.iter_num, 10);
+
+        let public_input = vec![Fp::from(nth_fibonacci(1, 2, 10))];
+        assert!(prove_and_verify(k, circuit, public_input).unwrap());
+    }
+
+    #[test]
+    fn test_example2_iter_too_small() {
+        let k = 4;
+
+        let result = MyCircuit::builder()
+            .a(Fp::from(1))
+            .b(Fp::from(2))
+            .iter_num(2)
+            .build();
+        assert_eq!(
+            result.err(),
+            Some(CircuitError::IterTooSmall {
+                iter_num: 2,
+                min: 4
+            })
+        );
+
+        // built directly, the chip rejects it during synthesis
+        let circuit = MyCircuit {
+            a: Some(Fp::from(1)),
+            b: Some(Fp::from(2)),
+            iter_num: 2,
+        };
+        assert!(matches!(
+            MockProver::run(k, &circuit, vec![vec![]]),
+            Err(Error::Synthesis)
+        ));
+    }
+
+    #[test]
+    fn test_example2_too_many_rows() {
+        let circuit = MyCircuit {
+            a: Some(Fp::from(1)),
+            b: Some(Fp::from(2)),
+            iter_num: 100,
+        };
+
+        // 2^2 rows don't even cover the rows halo2 reserves
+        assert_eq!(
+            circuit.validate(2),
+            Err(CircuitError::TooManyRows {
+                needed: 100,
+                available: 0
+            })
+        );
+        assert!(matches!(
+            circuit.validate(6),
+            Err(CircuitError::TooManyRows { needed: 100, .. })
+        ));
+        assert_eq!(circuit.validate(7), Ok(()));
+
+        // what the layouter returns without the check
+        assert!(matches!(
+            MockProver::run(6, &circuit, vec![vec![]]),
+            Err(Error::NotEnoughRowsAvailable { .. })
+        ));
+    }
+
+    #[test]
+    fn test_example2_selector_rows() {
+        // a gate on every row but the last two, which only hold inputs of it
+        for iter_num in [4, 10, 26] {
+            let circuit = MyCircuit::<Fp> {
+                iter_num,
+                ..MyCircuit::default()
+            };
+            let rows = enabled_rows(min_k_for(iter_num).unwrap(), &circuit, |config| {
+                config.selector
+            });
+            assert_eq!(rows, (0..iter_num - 2).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn test_example2_min_k_for() {
+        // 2^4 = 16 rows minus the 6 halo2 reserves
+        assert_eq!(min_k_for(10).unwrap(), 4);
+        assert_eq!(min_k_for(11).unwrap(), 5);
+        assert_eq!(min_k_for(100).unwrap(), 7);
+
+        // more rows than 2^MAX_K, no k to find
+        assert_eq!(min_k_for(usize::MAX), None);
+
+        // the same bound as validate
+        for iter_num in [4, 10, 11, 26, 27, 100] {
+            let circuit = MyCircuit::<Fp> {
+                iter_num,
+                ..MyCircuit::default()
+            };
+            let k = min_k_for(iter_num).unwrap();
+            assert_eq!(circuit.validate(k), Ok(()));
+            assert!(circuit.validate(k - 1).is_err());
+        }
+    }
+
+    // MyCircuit laid out with assign_clean
+    struct CleanCircuit(MyCircuit<Fp>);
+
+    impl Circuit<Fp> for CleanCircuit {
+        type Config = FiboConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self(self.0.without_witnesses())
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            MyCircuit::<Fp>::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = FiboChip::construct(config);
+            let out = chip.assign_clean(
+                layouter.namespace(|| "fibonacci table"),
+                self.0.a,
+                self.0.b,
+                self.0.iter_num,
+            )?;
+            chip.expose_public(layouter.namespace(|| "out"), &out, 0)
+        }
+    }
+
+    #[test]
+    fn test_example2_assign_clean() {
+        for iter_num in [4, 5, 10] {
+            let circuit = MyCircuit {
+                iter_num,
+                ..MyCircuit::from_u64(1, 2)
+            };
+            let clean = CleanCircuit(MyCircuit {
+                iter_num,
+                ..MyCircuit::from_u64(1, 2)
+            });
+            let k = min_k_for(iter_num).unwrap();
+
+            // the same output...
+            let public_input = vec![Fp::from(nth_fibonacci(1, 2, iter_num))];
+            for prover in [
+                MockProver::run(k, &circuit, vec![public_input.clone()]).unwrap(),
+                MockProver::run(k, &clean, vec![public_input]).unwrap(),
+            ] {
+                prover.assert_satisfied();
+            }
+
+            // ...from the same layout
+            assert_eq!(dump_regions(k, &circuit), dump_regions(k, &clean));
+        }
+
+        let public_input = vec![Fp::from(nth_fibonacci(1, 2, 10) + 1)];
+        let clean = CleanCircuit(MyCircuit::from_u64(1, 2));
+        assert_unsatisfied(4, &clean, vec![public_input]);
+    }
+
     #[test]
     fn test_example2_failed() {
-        let k = 2;
+        let k = min_k_for(10).unwrap();
 
         let a = Fp::from(1);
         let b = Fp::from(2);
-        let out = Fp::from(90);
+        let out = Fp::from(nth_fibonacci(1, 2, 10) + 1);
 
         let circuit = MyCircuit {
             a: Some(a),
             b: Some(b),
+            iter_num: 10,
         };
 
         let public_input = vec![out];
+        // the 10th term is 89, so the copy constraint between the last c and
+        // the instance column fails and the wrong public input is rejected
+        assert_unsatisfied(k, &circuit, vec![public_input]);
+    }
+
+    #[test]
+    fn test_example2_wrong_sum() {
+        let k = 4;
+
+        // 2 + 3 != 6
+        let prover = MockProver::run(k, &WrongSumCircuit, vec![vec![]]).unwrap();
+        assert_fails_with(&prover, "add");
+    }
+
+    #[test]
+    fn test_example2_constant() {
+        // the two constants take a row each before the table, which doesn't
+        // fit in 2^4 rows anymore
+        let k = 5;
+
+        // 1, 1, 2, 3, 5, 8, 13, 21, 34, 55
+        let circuit = ConstantCircuit { iter_num: 10 };
+
+        let public_input = vec![Fp::from(nth_fibonacci(1, 1, 10))];
         let prover = MockProver::run(k, &circuit, vec![public_input.clone()]).unwrap();
-        // prover.assert_satisfied();
+        prover.assert_satisfied();
+        assert!(prove_and_verify(k, circuit, public_input).unwrap());
+    }
+
+    #[test]
+    fn test_example2_constant_failed() {
+        let k = 5;
+
+        // the right output for a = 1, b = 2, but a and b are fixed to 1
+        let circuit = ConstantCircuit { iter_num: 10 };
+
+        let public_input = vec![Fp::from(nth_fibonacci(1, 2, 10))];
+        assert_unsatisfied(k, &circuit, vec![public_input]);
+    }
+
+    #[test]
+    fn test_example2_public_inputs() {
+        // a and b take a row each before the table, like in test_example2_constant
+        let k = 5;
+
+        let circuit = PublicInputsCircuit {
+            a: Some(Fp::from(1)),
+            b: Some(Fp::from(2)),
+            iter_num: 10,
+        };
+
+        // [out, a, b]
+        let public_input = vec![Fp::from(nth_fibonacci(1, 2, 10)), Fp::from(1), Fp::from(2)];
+        let prover = MockProver::run(k, &circuit, vec![public_input]).unwrap();
+        prover.assert_satisfied();
+    }
+
+    #[test]
+    fn test_example2_public_inputs_failed() {
+        let k = 5;
+
+        let circuit = PublicInputsCircuit {
+            a: Some(Fp::from(1)),
+            b: Some(Fp::from(2)),
+            iter_num: 10,
+        };
+
+        // the output matches the witnesses, but the public a doesn't
+        let public_input = vec![Fp::from(nth_fibonacci(1, 2, 10)), Fp::from(5), Fp::from(2)];
+        assert_unsatisfied(k, &circuit, vec![public_input]);
+    }
+
+    #[test]
+    fn test_example2_instance_input() {
+        let k = 4;
+
+        let circuit = InstanceInputCircuit {
+            b: Some(Fp::from(2)),
+            iter_num: 10,
+        };
+
+        // a = 3 only comes from here
+        let public_input = vec![Fp::from(3), Fp::from(nth_fibonacci(3, 2, 10))];
+        let prover = MockProver::run(k, &circuit, vec![public_input]).unwrap();
+        prover.assert_satisfied();
+    }
+
+    #[test]
+    fn test_example2_instance_input_failed() {
+        let k = 4;
+
+        let circuit = InstanceInputCircuit {
+            b: Some(Fp::from(2)),
+            iter_num: 10,
+        };
+
+        // the output for a = 1, with a = 3 in the instance column
+        let public_input = vec![Fp::from(3), Fp::from(nth_fibonacci(1, 2, 10))];
+        assert_unsatisfied(k, &circuit, vec![public_input]);
+    }
+
+    #[test]
+    fn test_example2_multi_instance() {
+        // iter_num takes a row before the table
+        let k = 5;
+
+        let circuit = MultiInstanceCircuit {
+            a: Some(Fp::from(1)),
+            b: Some(Fp::from(2)),
+            iter_num: 10,
+        };
+
+        // one vector per instance column
+        let instances = vec![vec![Fp::from(nth_fibonacci(1, 2, 10))], vec![Fp::from(10)]];
+        let prover = MockProver::run(k, &circuit, instances).unwrap();
+        prover.assert_satisfied();
+    }
+
+    #[test]
+    fn test_example2_multi_instance_failed() {
+        let k = 5;
+
+        let circuit = MultiInstanceCircuit {
+            a: Some(Fp::from(1)),
+            b: Some(Fp::from(2)),
+            iter_num: 10,
+        };
+
+        // the output is right, the number of iterations isn't
+        let instances = vec![vec![Fp::from(nth_fibonacci(1, 2, 10))], vec![Fp::from(11)]];
+        assert_unsatisfied(k, &circuit, instances);
+    }
+
+    proptest! {
+        // every case runs MockProver twice, so keep the count down
+        #![proptest_config(proptest_config(32))]
+
+        #[test]
+        fn test_example2_prop(a in 0u64..1000, b in 0u64..1000, delta in 1u64..1000) {
+            let k = min_k_for(10).unwrap();
+
+            let out = Fp::from(nth_fibonacci(a, b, 10));
+
+            let circuit = MyCircuit {
+                a: Some(Fp::from(a)),
+                b: Some(Fp::from(b)),
+                iter_num: 10,
+            };
+
+            let prover = MockProver::run(k, &circuit, vec![vec![out]]).unwrap();
+            prop_assert!(prover.verify().is_ok());
+
+            let prover = MockProver::run(k, &circuit, vec![vec![out + Fp::from(delta)]]).unwrap();
+            prop_assert!(prover.verify().is_err());
+        }
     }
 
     #[cfg(feature = "dev-graph")]
@@ -203,6 +1182,7 @@ mod tests {
         let circuit = MyCircuit {
             a: Some(a),
             b: Some(b),
+            iter_num: 10,
         };
         halo2_proofs::dev::CircuitLayout::default()
             .render(4, &circuit, &root)
@@ -0,0 +1,270 @@
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+use super::{ColumnSummary, FiboInstructions};
+use crate::error::CircuitError;
+
+//
+// selector | col_a | col_b |
+// ---------+-------+-------|
+//   s0     |  a0   |  a1   |
+//   s1     |  a2   |  a3   |    a2 = a0 + a1, a3 = a1 + a2
+//          |  a4   |  a5   |    a4 = a2 + a3, a5 = a3 + a4
+//
+// example2 with the sequence spread over two advice columns, so every row
+// advances two terms:
+//   s * (a + b - a_next) = 0
+//   s * (b + a_next - b_next) = 0
+// The gate relates both columns of the current row to both of the next one.
+//
+// The tradeoff: half the rows (iter_num / 2 rounded up) for twice the columns.
+// Fewer rows only lowers k once the table is long enough, the rows halo2
+// reserves at the end stay the same, so 10 terms still need k = 4 but 20 terms
+// fit in k = 4 where example2 needs k = 5 (see test_example2_wide_smaller_k).
+
+#[derive(Debug, Clone)]
+pub struct FiboConfig {
+    pub advice: [Column<Advice>; 2],
+    pub instance: Column<Instance>,
+    pub selector: Selector,
+}
+
+impl FiboConfig {
+    pub fn column_summary(&self) -> ColumnSummary {
+        ColumnSummary::of(&self.advice, &[], &[self.instance], &[self.selector])
+    }
+}
+
+pub struct FiboChip<F: FieldExt> {
+    config: FiboConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> FiboChip<F> {
+    pub fn construct(config: FiboConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 2],
+        instance: Column<Instance>,
+    ) -> FiboConfig {
+        let [col_a, col_b] = advice;
+        let selector = meta.selector();
+
+        // only to expose the output, which can be in either column
+        meta.enable_equality(col_a);
+        meta.enable_equality(col_b);
+        meta.enable_equality(instance);
+
+        meta.create_gate("add", |meta| {
+            let s = meta.query_selector(selector);
+            let a = meta.query_advice(col_a, Rotation::cur());
+            let b = meta.query_advice(col_b, Rotation::cur());
+            let a_next = meta.query_advice(col_a, Rotation::next());
+            let b_next = meta.query_advice(col_b, Rotation::next());
+            vec![
+                s.clone() * (a + b.clone() - a_next.clone()),
+                s * (b + a_next - b_next),
+            ]
+        });
+
+        FiboConfig {
+            advice,
+            instance,
+            selector,
+        }
+    }
+}
+
+impl<F: FieldExt> FiboInstructions<F> for FiboChip<F> {
+    fn assign(
+        &self,
+        mut layouter: impl Layouter<F>,
+        init_a: Option<F>,
+        init_b: Option<F>,
+        iter_num: usize,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        if iter_num < 3 {
+            return Err(CircuitError::IterTooSmall { iter_num, min: 3 }.into());
+        }
+        let rows = iter_num.div_ceil(2);
+
+        layouter.assign_region(
+            || "fibonacci region",
+            |mut region| {
+                let (mut a, mut b) = (init_a, init_b);
+                // the cells in term order
+                let mut cells = vec![];
+
+                for row in 0..rows {
+                    // the last row has no next row to compute
+                    if row + 1 < rows {
+                        self.config.selector.enable(&mut region, row)?;
+                    }
+
+                    for (column, value) in self.config.advice.into_iter().zip([a, b]) {
+                        cells.push(region.assign_advice(
+                            || "advice",
+                            column,
+                            row,
+                            || value.ok_or(CircuitError::MissingWitness.into()),
+                        )?);
+                    }
+
+                    let a_next = a.zip(b).map(|(a, b)| a + b);
+                    (a, b) = (a_next, b.zip(a_next).map(|(b, a_next)| b + a_next));
+                }
+                Ok(cells.swap_remove(iter_num - 1))
+            },
+        )
+    }
+
+    fn expose_public(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cell: &AssignedCell<F, F>,
+        row: usize,
+    ) -> Result<(), Error> {
+        layouter.constrain_instance(cell.cell(), self.config.instance, row)
+    }
+}
+
+pub struct MyCircuit<F> {
+    pub a: Option<F>,
+    pub b: Option<F>,
+    // the index of the output term, as in example2
+    pub iter_num: usize,
+}
+
+impl<F> Default for MyCircuit<F> {
+    fn default() -> Self {
+        Self {
+            a: None,
+            b: None,
+            iter_num: 10,
+        }
+    }
+}
+
+impl<F: FieldExt> MyCircuit<F> {
+    // a and b from plain integers, iter_num keeps its default
+    pub fn from_u64(a: u64, b: u64) -> Self {
+        Self {
+            a: Some(F::from(a)),
+            b: Some(F::from(b)),
+            ..Self::default()
+        }
+    }
+}
+
+impl<F: FieldExt> Circuit<F> for MyCircuit<F> {
+    type Config = FiboConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            iter_num: self.iter_num,
+            ..Self::default()
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let advice = [meta.advice_column(), meta.advice_column()];
+        let instance = meta.instance_column();
+        FiboChip::configure(meta, advice, instance)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let chip = FiboChip::construct(config);
+        let out = chip.assign(
+            layouter.namespace(|| "fibonacci table"),
+            self.a,
+            self.b,
+            self.iter_num,
+        )?;
+        chip.expose_public(layouter.namespace(|| "out"), &out, 0)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MyCircuit;
+    use crate::fibonacci::{example2, ColumnSummary};
+    use crate::testing::nth_fibonacci;
+    use halo2_proofs::{dev::MockProver, pasta::Fp, plonk::*};
+
+    #[test]
+    fn test_example2_wide() {
+        let k = 4;
+
+        // both an odd and an even number of terms
+        for iter_num in [9, 10] {
+            let circuit = MyCircuit {
+                a: Some(Fp::from(1)),
+                b: Some(Fp::from(2)),
+                iter_num,
+            };
+
+            let public_input = vec![Fp::from(nth_fibonacci(1, 2, iter_num))];
+            let prover = MockProver::run(k, &circuit, vec![public_input]).unwrap();
+            prover.assert_satisfied();
+        }
+    }
+
+    #[test]
+    fn test_example2_wide_failed() {
+        let k = 4;
+
+        let circuit = MyCircuit {
+            a: Some(Fp::from(1)),
+            b: Some(Fp::from(2)),
+            iter_num: 10,
+        };
+
+        let public_input = vec![Fp::from(nth_fibonacci(1, 2, 10) + 1)];
+        let prover = MockProver::run(k, &circuit, vec![public_input]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn test_example2_wide_smaller_k() {
+        let k = 4;
+        let (a, b, iter_num) = (Some(Fp::from(1)), Some(Fp::from(1)), 20);
+        let public_input = vec![Fp::from(nth_fibonacci(1, 1, iter_num))];
+
+        // 10 rows fit in 2^4, example2's 20 rows don't
+        let wide = MyCircuit { a, b, iter_num };
+        let prover = MockProver::run(k, &wide, vec![public_input.clone()]).unwrap();
+        prover.assert_satisfied();
+
+        let narrow = example2::MyCircuit { a, b, iter_num };
+        assert!(MockProver::run(k, &narrow, vec![public_input.clone()]).is_err());
+        let prover = MockProver::run(k + 1, &narrow, vec![public_input]).unwrap();
+        prover.assert_satisfied();
+
+        // paid for with an advice column (example2's fixed column only holds
+        // the constants of load_constant, which this example doesn't have)
+        let mut cs = ConstraintSystem::<Fp>::default();
+        let wide = MyCircuit::<Fp>::configure(&mut cs).column_summary();
+        let narrow = example2::MyCircuit::<Fp>::configure(&mut cs).column_summary();
+        assert_eq!(
+            wide,
+            ColumnSummary {
+                advice: narrow.advice + 1,
+                fixed: 0,
+                ..narrow
+            }
+        );
+    }
+}
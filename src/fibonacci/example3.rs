@@ -0,0 +1,297 @@
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+use super::ColumnSummary;
+use crate::error::CircuitError;
+
+//
+// s_add | s_out | advice | instance
+// ------+-------+--------+----------
+//   1   |   1   |   t9   |   out      t9 = t8 + t7, t9 = out
+//   1   |   0   |   t8   |            t8 = t7 + t6
+//  ...  |  ...  |  ...   |
+//   1   |   0   |   t2   |            t2 = t1 + t0
+//   0   |   0   |   t1   |            (b)
+//   0   |   0   |   t0   |            (a)
+//
+// Like example2, all the terms are in one column, but nothing is ever copied:
+// every constraint is a gate over adjacent rows, and the output is checked by
+// a gate that queries the instance column directly
+//   s_add * (next + next_next - cur) = 0
+//   s_out * (cur - instance) = 0
+// There's no enable_equality call, so the circuit has no permutation argument.
+//
+// The sequence is laid out bottom-up so that the output ends up in row 0,
+// where it lines up with the first row of the instance column.
+//
+// NOTE: MockProver in halo2_proofs 0.1.0 reports the instance cell queried by
+// the "out" gate as not assigned, so this example is checked with the real
+// prover instead (see the tests).
+
+#[derive(Debug, Clone)]
+pub struct FiboConfig {
+    pub advice: Column<Advice>,
+    pub instance: Column<Instance>,
+    pub s_add: Selector,
+    pub s_out: Selector,
+}
+
+impl FiboConfig {
+    pub fn column_summary(&self) -> ColumnSummary {
+        ColumnSummary::of(
+            &[self.advice],
+            &[],
+            &[self.instance],
+            &[self.s_add, self.s_out],
+        )
+    }
+}
+
+pub struct FiboChip<F: FieldExt> {
+    config: FiboConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> FiboChip<F> {
+    pub fn construct(config: FiboConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: Column<Advice>,
+        instance: Column<Instance>,
+    ) -> FiboConfig {
+        let s_add = meta.selector();
+        let s_out = meta.selector();
+
+        meta.create_gate("add", |meta| {
+            let s = meta.query_selector(s_add);
+            let cur = meta.query_advice(advice, Rotation::cur());
+            let next = meta.query_advice(advice, Rotation::next());
+            let next_next = meta.query_advice(advice, Rotation(2));
+            vec![s * (next + next_next - cur)]
+        });
+
+        meta.create_gate("out", |meta| {
+            let s = meta.query_selector(s_out);
+            let cur = meta.query_advice(advice, Rotation::cur());
+            let out = meta.query_instance(instance, Rotation::cur());
+            vec![s * (cur - out)]
+        });
+
+        FiboConfig {
+            advice,
+            instance,
+            s_add,
+            s_out,
+        }
+    }
+
+    // assigns the iter_num terms, the last one in row 0
+    pub fn assign(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: Option<F>,
+        b: Option<F>,
+        iter_num: usize,
+    ) -> Result<(), Error> {
+        // a and b are always assigned, the last row is iter_num - 1
+        if iter_num < 2 {
+            return Err(CircuitError::IterTooSmall { iter_num, min: 2 }.into());
+        }
+
+        layouter.assign_region(
+            || "entire fibonacci table",
+            |mut region| {
+                let mut terms = vec![a, b];
+                for i in 2..iter_num {
+                    let t = terms[i - 2].and_then(|x| terms[i - 1].map(|y| x + y));
+                    terms.push(t);
+                }
+
+                for (i, term) in terms.iter().enumerate() {
+                    let row = iter_num - 1 - i;
+                    if i >= 2 {
+                        self.config.s_add.enable(&mut region, row)?;
+                    }
+                    region.assign_advice(
+                        || "term",
+                        self.config.advice,
+                        row,
+                        || term.ok_or(CircuitError::MissingWitness.into()),
+                    )?;
+                }
+                self.config.s_out.enable(&mut region, 0)?;
+
+                Ok(())
+            },
+        )
+    }
+}
+
+pub struct MyCircuit<F> {
+    pub a: Option<F>,
+    pub b: Option<F>,
+    // the number of terms, a and b included
+    pub iter_num: usize,
+}
+
+impl<F> Default for MyCircuit<F> {
+    fn default() -> Self {
+        Self {
+            a: None,
+            b: None,
+            iter_num: 10,
+        }
+    }
+}
+
+impl<F: FieldExt> MyCircuit<F> {
+    // a and b from plain integers, iter_num keeps its default
+    pub fn from_u64(a: u64, b: u64) -> Self {
+        Self {
+            a: Some(F::from(a)),
+            b: Some(F::from(b)),
+            ..Self::default()
+        }
+    }
+}
+
+impl<F: FieldExt> Circuit<F> for MyCircuit<F> {
+    type Config = FiboConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            iter_num: self.iter_num,
+            ..Self::default()
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let advice = meta.advice_column();
+        let instance = meta.instance_column();
+
+        FiboChip::configure(meta, advice, instance)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let chip = FiboChip::construct(config);
+
+        chip.assign(
+            layouter.namespace(|| "fibonacci"),
+            self.a,
+            self.b,
+            self.iter_num,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MyCircuit;
+    use crate::fibonacci::{example2, prove::prove_and_verify, ColumnSummary};
+    use crate::testing::nth_fibonacci;
+    use halo2_proofs::{
+        dev::{MockProver, VerifyFailure},
+        pasta::Fp,
+        plonk::*,
+    };
+
+    #[test]
+    fn test_example3() {
+        let k = 4;
+
+        let circuit = MyCircuit {
+            a: Some(Fp::from(1)),
+            b: Some(Fp::from(2)),
+            iter_num: 10,
+        };
+
+        let public_input = vec![Fp::from(nth_fibonacci(1, 2, 10))];
+        assert!(prove_and_verify(k, circuit, public_input).unwrap());
+    }
+
+    #[test]
+    fn test_example3_failed() {
+        let k = 4;
+
+        let circuit = MyCircuit {
+            a: Some(Fp::from(1)),
+            b: Some(Fp::from(2)),
+            iter_num: 10,
+        };
+
+        let public_input = vec![Fp::from(nth_fibonacci(1, 2, 10) + 1)];
+        assert!(!prove_and_verify(k, circuit, public_input).unwrap());
+    }
+
+    #[test]
+    fn test_example3_iter_too_small() {
+        let k = 4;
+
+        // a single term has no row for b
+        let circuit = MyCircuit {
+            a: Some(Fp::from(1)),
+            b: Some(Fp::from(2)),
+            iter_num: 1,
+        };
+        assert!(MockProver::run(k, &circuit, vec![vec![Fp::from(1)]]).is_err());
+    }
+
+    #[test]
+    fn test_example3_mock_prover() {
+        let k = 4;
+
+        let circuit = MyCircuit {
+            a: Some(Fp::from(1)),
+            b: Some(Fp::from(2)),
+            iter_num: 10,
+        };
+
+        // MockProver in halo2_proofs 0.1.0 expects every cell a gate queries to be
+        // assigned in the region, instance cells included, so it flags the "out"
+        // gate even though the circuit is fine (the tests above use the real prover)
+        let public_input = vec![Fp::from(nth_fibonacci(1, 2, 10))];
+        let prover = MockProver::run(k, &circuit, vec![public_input]).unwrap();
+        let failures = prover.verify().unwrap_err();
+        assert_eq!(failures.len(), 1);
+        assert!(matches!(
+            failures[0],
+            VerifyFailure::CellNotAssigned { offset: 0, .. }
+        ));
+    }
+
+    #[test]
+    fn test_example3_column_summary() {
+        let mut meta = ConstraintSystem::<Fp>::default();
+        let summary2 = example2::MyCircuit::<Fp>::configure(&mut meta).column_summary();
+        let mut meta = ConstraintSystem::<Fp>::default();
+        let summary3 = MyCircuit::<Fp>::configure(&mut meta).column_summary();
+
+        // one more selector, for the output gate
+        assert_eq!(summary3.advice, summary2.advice);
+        assert_eq!(summary3.instance, summary2.instance);
+        assert_eq!(
+            summary3,
+            ColumnSummary {
+                advice: 1,
+                fixed: 0,
+                instance: 1,
+                selectors: 2,
+            }
+        );
+
+        // no column takes part in the permutation argument
+        let pinned = format!("{:?}", meta.pinned());
+        assert!(pinned.contains("permutation: Argument { columns: [] }"));
+    }
+}
@@ -0,0 +1,305 @@
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+use super::{ColumnSummary, FiboInstructions};
+use crate::error::CircuitError;
+
+//
+// q (fixed) | col_a | col_b | col_c
+// ----------+-------+-------+-------
+//     1     |   a0  |   b0  |   c0
+//     1     |   a1  |   b1  |   c1
+//
+// example1's table and gate, q * (a + b - c) = 0, but q is a fixed column
+// assigned by hand instead of a Selector: a selector is a fixed column of 0s
+// and 1s, `enable` just assigns a 1 in it.
+//
+// What the Selector type adds on top:
+// - halo2 can combine simple selectors into fewer fixed columns at keygen, an
+//   explicit fixed column always costs a column (and a commitment) of its own
+// - a selector can only be queried at the current row and is guaranteed to be
+//   boolean, so it can't be misused as a coefficient
+// A plain fixed column is more flexible: it can be queried at any rotation and
+// hold any value (see weighted.rs). Either way, the rows left unassigned are 0,
+// i.e. the gate is off there.
+
+#[derive(Debug, Clone)]
+pub struct FiboConfig {
+    pub advice: [Column<Advice>; 3],
+    pub q: Column<Fixed>,
+    pub instance: Column<Instance>,
+}
+
+impl FiboConfig {
+    pub fn column_summary(&self) -> ColumnSummary {
+        ColumnSummary::of(&self.advice, &[self.q], &[self.instance], &[])
+    }
+}
+
+pub struct FiboChip<F: FieldExt> {
+    config: FiboConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> FiboChip<F> {
+    pub fn construct(config: FiboConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        q: Column<Fixed>,
+        instance: Column<Instance>,
+    ) -> FiboConfig {
+        let [col_a, col_b, col_c] = advice;
+
+        // for permutation check
+        for column in advice {
+            meta.enable_equality(column);
+        }
+        meta.enable_equality(instance);
+
+        meta.create_gate("add", |meta| {
+            let q = meta.query_fixed(q, Rotation::cur());
+            let a = meta.query_advice(col_a, Rotation::cur());
+            let b = meta.query_advice(col_b, Rotation::cur());
+            let c = meta.query_advice(col_c, Rotation::cur());
+            vec![q * (a + b - c)]
+        });
+
+        FiboConfig {
+            advice,
+            q,
+            instance,
+        }
+    }
+}
+
+impl<F: FieldExt> FiboInstructions<F> for FiboChip<F> {
+    fn assign(
+        &self,
+        mut layouter: impl Layouter<F>,
+        init_a: Option<F>,
+        init_b: Option<F>,
+        iter_num: usize,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        if iter_num < 3 {
+            return Err(CircuitError::IterTooSmall { iter_num, min: 3 }.into());
+        }
+
+        layouter.assign_region(
+            || "fibonacci region",
+            |mut region| {
+                let [col_a, col_b, col_c] = self.config.advice;
+                let (mut a, mut b) = (init_a, init_b);
+                let mut prev: Option<(AssignedCell<F, F>, AssignedCell<F, F>)> = None;
+
+                for row in 0..iter_num - 2 {
+                    // what Selector::enable would do
+                    region.assign_fixed(|| "q", self.config.q, row, || Ok(F::one()))?;
+
+                    let a_cell = region.assign_advice(
+                        || "a",
+                        col_a,
+                        row,
+                        || a.ok_or(CircuitError::MissingWitness.into()),
+                    )?;
+                    let b_cell = region.assign_advice(
+                        || "b",
+                        col_b,
+                        row,
+                        || b.ok_or(CircuitError::MissingWitness.into()),
+                    )?;
+                    let c = a.zip(b).map(|(a, b)| a + b);
+                    let c_cell = region.assign_advice(
+                        || "c",
+                        col_c,
+                        row,
+                        || c.ok_or(CircuitError::MissingWitness.into()),
+                    )?;
+
+                    // a = previous b, b = previous c
+                    if let Some((prev_b, prev_c)) = prev {
+                        region.constrain_equal(a_cell.cell(), prev_b.cell())?;
+                        region.constrain_equal(b_cell.cell(), prev_c.cell())?;
+                    }
+
+                    prev = Some((b_cell, c_cell));
+                    (a, b) = (b, c);
+                }
+                Ok(prev.unwrap().1)
+            },
+        )
+    }
+
+    fn expose_public(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cell: &AssignedCell<F, F>,
+        row: usize,
+    ) -> Result<(), Error> {
+        layouter.constrain_instance(cell.cell(), self.config.instance, row)
+    }
+}
+
+pub struct MyCircuit<F> {
+    pub a: Option<F>,
+    pub b: Option<F>,
+    // the index of the output term, as in example2
+    pub iter_num: usize,
+}
+
+impl<F> Default for MyCircuit<F> {
+    fn default() -> Self {
+        Self {
+            a: None,
+            b: None,
+            iter_num: 10,
+        }
+    }
+}
+
+impl<F: FieldExt> MyCircuit<F> {
+    // a and b from plain integers, iter_num keeps its default
+    pub fn from_u64(a: u64, b: u64) -> Self {
+        Self {
+            a: Some(F::from(a)),
+            b: Some(F::from(b)),
+            ..Self::default()
+        }
+    }
+}
+
+impl<F: FieldExt> Circuit<F> for MyCircuit<F> {
+    type Config = FiboConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            iter_num: self.iter_num,
+            ..Self::default()
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let advice = [
+            meta.advice_column(),
+            meta.advice_column(),
+            meta.advice_column(),
+        ];
+        let q = meta.fixed_column();
+        let instance = meta.instance_column();
+        FiboChip::configure(meta, advice, q, instance)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let chip = FiboChip::construct(config);
+        let out = chip.assign(
+            layouter.namespace(|| "fibonacci table"),
+            self.a,
+            self.b,
+            self.iter_num,
+        )?;
+        chip.expose_public(layouter.namespace(|| "out"), &out, 0)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MyCircuit;
+    use crate::fibonacci::example1;
+    use crate::testing::{assert_fails_with, nth_fibonacci};
+    use halo2_proofs::{dev::MockProver, pasta::Fp};
+
+    #[test]
+    fn test_fixed_enable() {
+        let k = 4;
+
+        let circuit = MyCircuit {
+            a: Some(Fp::from(1)),
+            b: Some(Fp::from(2)),
+            iter_num: 10,
+        };
+
+        // the same output as example1 with the same inputs
+        let public_input = vec![Fp::from(nth_fibonacci(1, 2, 10))];
+        let prover = MockProver::run(k, &circuit, vec![public_input.clone()]).unwrap();
+        prover.assert_satisfied();
+
+        let example1 = example1::MyCircuit::<_, 10> {
+            a: Some(Fp::from(1)),
+            b: Some(Fp::from(2)),
+            expose_all: false,
+        };
+        let prover = MockProver::run(k, &example1, vec![public_input]).unwrap();
+        prover.assert_satisfied();
+    }
+
+    #[test]
+    fn test_fixed_enable_failed() {
+        let k = 4;
+
+        let circuit = MyCircuit {
+            a: Some(Fp::from(1)),
+            b: Some(Fp::from(2)),
+            iter_num: 10,
+        };
+
+        let public_input = vec![Fp::from(nth_fibonacci(1, 2, 10) + 1)];
+        let prover = MockProver::run(k, &circuit, vec![public_input]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn test_fixed_enable_gate() {
+        use super::FiboConfig;
+        use halo2_proofs::{circuit::*, plonk::*};
+
+        // a single row with c != a + b, q on
+        struct WrongSumCircuit;
+
+        impl Circuit<Fp> for WrongSumCircuit {
+            type Config = FiboConfig;
+            type FloorPlanner = SimpleFloorPlanner;
+
+            fn without_witnesses(&self) -> Self {
+                Self
+            }
+
+            fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+                MyCircuit::<Fp>::configure(meta)
+            }
+
+            fn synthesize(
+                &self,
+                config: Self::Config,
+                mut layouter: impl Layouter<Fp>,
+            ) -> Result<(), Error> {
+                layouter.assign_region(
+                    || "wrong sum",
+                    |mut region| {
+                        region.assign_fixed(|| "q", config.q, 0, || Ok(Fp::one()))?;
+                        for (column, value) in config.advice.into_iter().zip([1, 2, 4]) {
+                            region.assign_advice(|| "advice", column, 0, || Ok(Fp::from(value)))?;
+                        }
+                        Ok(())
+                    },
+                )
+            }
+        }
+
+        // the fixed column switches the gate on exactly like a selector
+        let prover = MockProver::run(4, &WrongSumCircuit, vec![vec![]]).unwrap();
+        assert_fails_with(&prover, "add");
+    }
+}
@@ -0,0 +1,197 @@
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+use std::marker::PhantomData;
+
+use super::example2::{self, FiboChip, FiboConfig};
+use super::FiboInstructions;
+
+//
+//        SimpleFloorPlanner                      V1
+// row | region             |        row | region             |
+// ----+--------------------|        ----+--------------------|
+//  0  | load a             |         0  | fibonacci region   |
+//  1  | load b             |        ... |   (copies a, b)    |
+//  2  | fibonacci region   |         9  |                    |
+// ... |   (copies a, b)    |        10  | load b             |
+// 11  |                    |        11  | load a             |
+//
+// example2's chip and gate as they are, with a and b loaded in regions of their
+// own and copied into the table, so that there are several regions to place.
+// Only the floor planner changes, it's a type parameter of the circuit.
+//
+// SimpleFloorPlanner places the regions in the order they're assigned, each
+// one on the first row after the previous regions using any of its columns.
+// V1 synthesizes the circuit twice: a first pass only measures the regions,
+// then it places them biggest first, each in the first gap of its columns that
+// is tall enough, and the second pass assigns them there. The constants are
+// also only placed once all the regions are, in the rows left free.
+//
+// Everything here is in the one advice column, so the regions have to be
+// stacked either way and both tables are 12 rows high; V1 pays off when
+// small regions can fill the gaps left next to bigger ones in other columns.
+// The cells move, the constraints don't: the copies follow the cells, and the
+// same witnesses and public inputs verify (or fail) with both planners.
+
+pub struct MyCircuit<F, P = floor_planner::V1> {
+    pub a: Option<F>,
+    pub b: Option<F>,
+    pub iter_num: usize,
+    _marker: PhantomData<P>,
+}
+
+impl<F, P> Default for MyCircuit<F, P> {
+    fn default() -> Self {
+        Self::new(None, None, 10)
+    }
+}
+
+impl<F, P> MyCircuit<F, P> {
+    pub fn new(a: Option<F>, b: Option<F>, iter_num: usize) -> Self {
+        Self {
+            a,
+            b,
+            iter_num,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<F: FieldExt, P> MyCircuit<F, P> {
+    // a and b from plain integers, iter_num keeps its default
+    pub fn from_u64(a: u64, b: u64) -> Self {
+        Self {
+            a: Some(F::from(a)),
+            b: Some(F::from(b)),
+            ..Self::default()
+        }
+    }
+}
+
+impl<F: FieldExt, P: FloorPlanner> Circuit<F> for MyCircuit<F, P> {
+    type Config = FiboConfig;
+    type FloorPlanner = P;
+
+    fn without_witnesses(&self) -> Self {
+        Self::new(None, None, self.iter_num)
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        example2::MyCircuit::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let chip = FiboChip::construct(config);
+
+        let a = chip.load_private(layouter.namespace(|| "a"), self.a)?;
+        let b = chip.load_private(layouter.namespace(|| "b"), self.b)?;
+        let out = chip.assign_from_cells(
+            layouter.namespace(|| "fibonacci table"),
+            &a,
+            &b,
+            self.iter_num,
+        )?;
+        chip.expose_public(layouter.namespace(|| "out"), &out, 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MyCircuit;
+    use crate::testing::{assert_unsatisfied, dump_regions, nth_fibonacci};
+    use halo2_proofs::{
+        circuit::{floor_planner::V1, SimpleFloorPlanner},
+        dev::MockProver,
+        pasta::Fp,
+    };
+
+    // the 10 rows of the table and a row for each of a and b
+    const K: u32 = 5;
+
+    #[test]
+    fn test_floor_planner() {
+        let circuit = MyCircuit::<Fp>::from_u64(1, 2);
+
+        let public_input = vec![Fp::from(nth_fibonacci(1, 2, 10))];
+        let prover = MockProver::run(K, &circuit, vec![public_input]).unwrap();
+        prover.assert_satisfied();
+    }
+
+    #[test]
+    fn test_floor_planner_failed() {
+        let circuit = MyCircuit::<Fp>::from_u64(1, 2);
+
+        let public_input = vec![Fp::from(nth_fibonacci(1, 2, 10) + 1)];
+        assert_unsatisfied(K, &circuit, vec![public_input]);
+    }
+
+    #[test]
+    fn test_floor_planner_same_output() {
+        let v1 = MyCircuit::<Fp, V1>::from_u64(1, 2);
+        let simple = MyCircuit::<Fp, SimpleFloorPlanner>::from_u64(1, 2);
+
+        let public_input = vec![Fp::from(nth_fibonacci(1, 2, 10))];
+        MockProver::run(K, &v1, vec![public_input.clone()])
+            .unwrap()
+            .assert_satisfied();
+        MockProver::run(K, &simple, vec![public_input])
+            .unwrap()
+            .assert_satisfied();
+
+        let wrong_input = vec![Fp::from(nth_fibonacci(1, 2, 10) + 1)];
+        assert_unsatisfied(K, &v1, vec![wrong_input.clone()]);
+        assert_unsatisfied(K, &simple, vec![wrong_input]);
+    }
+
+    #[test]
+    fn test_floor_planner_regions() {
+        // in the order they're assigned
+        let simple = dump_regions(K, &MyCircuit::<Fp, SimpleFloorPlanner>::default());
+        assert_eq!(
+            simple,
+            vec![
+                ("load private".to_string(), 0..1),
+                ("load private".to_string(), 1..2),
+                ("fibonacci region".to_string(), 2..12),
+            ]
+        );
+
+        // the biggest region first, then the others (a is assigned first, so
+        // it's listed first, but it lands on the last row)
+        let v1 = dump_regions(K, &MyCircuit::<Fp, V1>::default());
+        assert_eq!(
+            v1,
+            vec![
+                ("load private".to_string(), 11..12),
+                ("load private".to_string(), 10..11),
+                ("fibonacci region".to_string(), 0..10),
+            ]
+        );
+    }
+
+    #[cfg(feature = "dev-graph")]
+    #[test]
+    fn plot_floor_planner() {
+        use plotters::prelude::*;
+
+        let root = BitMapBackend::new("floor-planner-layout.png", (2048, 1024)).into_drawing_area();
+        root.fill(&WHITE).unwrap();
+        let root = root
+            .titled("SimpleFloorPlanner vs V1", ("sans-serif", 60))
+            .unwrap();
+        let (simple_area, v1_area) = root.split_horizontally(1024);
+
+        halo2_proofs::dev::CircuitLayout::default()
+            .render(
+                K,
+                &MyCircuit::<Fp, SimpleFloorPlanner>::from_u64(1, 2),
+                &simple_area,
+            )
+            .unwrap();
+        halo2_proofs::dev::CircuitLayout::default()
+            .render(K, &MyCircuit::<Fp, V1>::from_u64(1, 2), &v1_area)
+            .unwrap();
+    }
+}
@@ -0,0 +1,312 @@
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+use super::{example2, FiboInstructions};
+use crate::error::CircuitError;
+
+//
+// selector | col_a |       col_b       |
+// ---------+-------+-------------------|
+//    s     |  a0   |                   |
+//    s     |  a1   |  b1 = a0 + a2     |
+//    s     |  a2   |  b2 = a1 + a3     |
+//          |  a3   |  b3 = a2 + a4     |
+//          |  a4   |                   |
+//
+// A gate over two columns and three rotations, between example1 (three
+// columns, all in the current row) and example2 (one column, three rows):
+//   s * (a + a_next - a_2) = 0         col_a is the Fibonacci sequence
+//   s * (a + a_2 - b_next) = 0         col_b is its companion sequence
+// where a = col_a at Rotation::cur(), a_next = col_a at Rotation::next(),
+// a_2 = col_a at Rotation(2) and b_next = col_b at Rotation::next().
+//
+// Starting from 0, 1, col_a holds the Fibonacci numbers F0, F1, ... and col_b
+// the Lucas numbers L1, L2, ..., from the identity L(n) = F(n - 1) + F(n + 1).
+// The first and last rows of col_b are left empty, no gate reaches them.
+//
+// The Lucas numbers are also a plain c = a + b sequence, seeded with 2, 1
+// instead of 0, 1: SeededCircuit below gets them from example2's chip as is,
+// only the witnesses change.
+
+#[derive(Debug, Clone)]
+pub struct LucasConfig {
+    pub advice: [Column<Advice>; 2],
+    pub instance: Column<Instance>,
+    pub selector: Selector,
+}
+
+pub struct LucasChip<F: FieldExt> {
+    config: LucasConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> LucasChip<F> {
+    pub fn construct(config: LucasConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 2],
+        instance: Column<Instance>,
+    ) -> LucasConfig {
+        let [col_a, col_b] = advice;
+        let selector = meta.selector();
+
+        // only to expose the output
+        meta.enable_equality(col_b);
+        meta.enable_equality(instance);
+
+        meta.create_gate("lucas", |meta| {
+            let s = meta.query_selector(selector);
+            let a = meta.query_advice(col_a, Rotation::cur());
+            let a_next = meta.query_advice(col_a, Rotation::next());
+            let a_2 = meta.query_advice(col_a, Rotation(2));
+            let b_next = meta.query_advice(col_b, Rotation::next());
+            vec![
+                ("fibonacci", s.clone() * (a.clone() + a_next - a_2.clone())),
+                ("companion", s * (a + a_2 - b_next)),
+            ]
+        });
+
+        LucasConfig {
+            advice,
+            instance,
+            selector,
+        }
+    }
+
+    // assigns iter_num terms to col_a and returns the last cell of col_b, in
+    // row iter_num - 2
+    pub fn assign(
+        &self,
+        mut layouter: impl Layouter<F>,
+        init_a: Option<F>,
+        init_b: Option<F>,
+        iter_num: usize,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        if iter_num < 3 {
+            return Err(CircuitError::IterTooSmall { iter_num, min: 3 }.into());
+        }
+
+        let mut terms = vec![init_a, init_b];
+        for i in 2..iter_num {
+            terms.push(terms[i - 2].zip(terms[i - 1]).map(|(a, b)| a + b));
+        }
+
+        layouter.assign_region(
+            || "lucas region",
+            |mut region| {
+                let [col_a, col_b] = self.config.advice;
+
+                for (row, term) in terms.iter().enumerate() {
+                    region.assign_advice(
+                        || "a",
+                        col_a,
+                        row,
+                        || term.ok_or(CircuitError::MissingWitness.into()),
+                    )?;
+                }
+
+                let mut b_cell = None;
+                for row in 0..iter_num - 2 {
+                    self.config.selector.enable(&mut region, row)?;
+
+                    let b = terms[row].zip(terms[row + 2]).map(|(a, a_2)| a + a_2);
+                    b_cell = Some(region.assign_advice(
+                        || "b",
+                        col_b,
+                        row + 1,
+                        || b.ok_or(CircuitError::MissingWitness.into()),
+                    )?);
+                }
+                Ok(b_cell.unwrap())
+            },
+        )
+    }
+
+    pub fn expose_public(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cell: &AssignedCell<F, F>,
+        row: usize,
+    ) -> Result<(), Error> {
+        layouter.constrain_instance(cell.cell(), self.config.instance, row)
+    }
+}
+
+pub struct MyCircuit<F> {
+    pub a: Option<F>,
+    pub b: Option<F>,
+    // the number of rows in the table, i.e. of terms in col_a
+    pub iter_num: usize,
+}
+
+impl<F> Default for MyCircuit<F> {
+    fn default() -> Self {
+        Self {
+            a: None,
+            b: None,
+            iter_num: 10,
+        }
+    }
+}
+
+impl<F: FieldExt> MyCircuit<F> {
+    // a and b from plain integers, iter_num keeps its default
+    pub fn from_u64(a: u64, b: u64) -> Self {
+        Self {
+            a: Some(F::from(a)),
+            b: Some(F::from(b)),
+            ..Self::default()
+        }
+    }
+}
+
+impl<F: FieldExt> Circuit<F> for MyCircuit<F> {
+    type Config = LucasConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            iter_num: self.iter_num,
+            ..Self::default()
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let advice = [meta.advice_column(), meta.advice_column()];
+        let instance = meta.instance_column();
+        LucasChip::configure(meta, advice, instance)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let chip = LucasChip::construct(config);
+        let out = chip.assign(
+            layouter.namespace(|| "lucas table"),
+            self.a,
+            self.b,
+            self.iter_num,
+        )?;
+        chip.expose_public(layouter.namespace(|| "out"), &out, 0)?;
+
+        Ok(())
+    }
+}
+
+// L0 = 2, L1 = 1 in example2's table, exposing the iter_num-th Lucas number
+pub struct SeededCircuit<F> {
+    pub iter_num: usize,
+    _marker: PhantomData<F>,
+}
+
+impl<F> SeededCircuit<F> {
+    pub fn new(iter_num: usize) -> Self {
+        Self {
+            iter_num,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<F: FieldExt> Circuit<F> for SeededCircuit<F> {
+    type Config = example2::FiboConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::new(self.iter_num)
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        example2::MyCircuit::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let chip = example2::FiboChip::construct(config);
+        let out = chip.assign(
+            layouter.namespace(|| "lucas table"),
+            Some(F::from(2)),
+            Some(F::one()),
+            self.iter_num,
+        )?;
+        chip.expose_public(layouter.namespace(|| "out"), &out, 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MyCircuit, SeededCircuit};
+    use crate::fibonacci::example2::min_k_for;
+    use crate::testing::{assert_unsatisfied, nth_fibonacci};
+    use halo2_proofs::{dev::MockProver, pasta::Fp};
+
+    #[test]
+    fn test_lucas() {
+        let k = 4;
+
+        // F0..F9 in col_a, L1..L8 in col_b: 1, 3, 4, 7, 11, 18, 29, 47
+        let circuit = MyCircuit::from_u64(0, 1);
+
+        let public_input = vec![Fp::from(47)];
+        let prover = MockProver::run(k, &circuit, vec![public_input]).unwrap();
+        prover.assert_satisfied();
+    }
+
+    #[test]
+    fn test_lucas_any_start() {
+        let k = 4;
+
+        // the companion of the sequence is the sum of the terms two apart
+        let circuit = MyCircuit::from_u64(1, 2);
+
+        let out = nth_fibonacci(1, 2, 8) + nth_fibonacci(1, 2, 10);
+        let public_input = vec![Fp::from(out)];
+        let prover = MockProver::run(k, &circuit, vec![public_input]).unwrap();
+        prover.assert_satisfied();
+    }
+
+    #[test]
+    fn test_lucas_failed() {
+        let k = 4;
+
+        let circuit = MyCircuit::from_u64(0, 1);
+
+        // F9, not L8
+        let public_input = vec![Fp::from(nth_fibonacci(0, 1, 10))];
+        let prover = MockProver::run(k, &circuit, vec![public_input]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn test_lucas_seeded() {
+        let k = min_k_for(10).unwrap();
+
+        // 2, 1, 3, 4, 7, 11, 18, 29, 47, 76
+        let circuit = SeededCircuit::new(10);
+
+        let public_input = vec![Fp::from(76)];
+        let prover = MockProver::run(k, &circuit, vec![public_input]).unwrap();
+        prover.assert_satisfied();
+        assert_eq!(nth_fibonacci(2, 1, 10), 76);
+    }
+
+    #[test]
+    fn test_lucas_seeded_failed() {
+        let k = min_k_for(10).unwrap();
+
+        // the 10th Fibonacci number from the same chip, not the Lucas one
+        let circuit = SeededCircuit::new(10);
+        assert_unsatisfied(k, &circuit, vec![vec![Fp::from(nth_fibonacci(0, 1, 10))]]);
+    }
+}
@@ -0,0 +1,308 @@
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+use super::{ColumnSummary, FiboInstructions};
+use crate::error::CircuitError;
+
+//
+// region   | selector |  col
+// ---------+----------+------
+// step 2   |    1     |  a0
+//          |          |  a1
+//          |          |  a2 = a0 + a1
+// step 3   |    1     |  a1   (copied from step 2)
+//          |          |  a2   (copied from step 2)
+//          |          |  a3 = a1 + a2
+// ...
+//
+// The same gate as example2, a + b = c over three consecutive rows, but every
+// step is assigned in its own region (with its own namespace) and chained to
+// the previous one with copy constraints.
+//
+// In example2's layout plot the whole table is one region of iter_num rows,
+// and consecutive gates overlap: the c of one step is the b of the next one.
+// Here regions can't overlap, so each step brings its own copies of a and b
+// and the table takes 3 * (iter_num - 2) rows. The floor planner only packs
+// regions side by side when they use different columns; all the regions here
+// share the one advice column, so they're stacked one after the other and the
+// plot shows a tall column of small regions, with the copies between them.
+
+// (b, c) of a step
+type CellPair<F> = (AssignedCell<F, F>, AssignedCell<F, F>);
+
+#[derive(Debug, Clone)]
+pub struct FiboConfig {
+    pub advice: Column<Advice>,
+    pub instance: Column<Instance>,
+    pub selector: Selector,
+}
+
+impl FiboConfig {
+    pub fn column_summary(&self) -> ColumnSummary {
+        ColumnSummary::of(&[self.advice], &[], &[self.instance], &[self.selector])
+    }
+}
+
+pub struct FiboChip<F: FieldExt> {
+    config: FiboConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> FiboChip<F> {
+    pub fn construct(config: FiboConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: Column<Advice>,
+        instance: Column<Instance>,
+    ) -> FiboConfig {
+        let selector = meta.selector();
+
+        // for the copies between the regions
+        meta.enable_equality(advice);
+        meta.enable_equality(instance);
+
+        meta.create_gate("add", |meta| {
+            let s = meta.query_selector(selector);
+            let a = meta.query_advice(advice, Rotation::cur());
+            let b = meta.query_advice(advice, Rotation::next());
+            let c = meta.query_advice(advice, Rotation(2));
+            vec![s * (a + b - c)]
+        });
+
+        FiboConfig {
+            advice,
+            instance,
+            selector,
+        }
+    }
+
+    // one step in its own region, a and b are either witnessed (the first
+    // step) or copied from the previous step
+    fn assign_step(
+        &self,
+        mut layouter: impl Layouter<F>,
+        step: usize,
+        a: StepInput<F>,
+        b: StepInput<F>,
+    ) -> Result<CellPair<F>, Error> {
+        layouter.assign_region(
+            || format!("step {}", step),
+            |mut region| {
+                self.config.selector.enable(&mut region, 0)?;
+
+                let a = a.assign(&mut region, self.config.advice, 0)?;
+                let b = b.assign(&mut region, self.config.advice, 1)?;
+
+                let c = a.value().zip(b.value()).map(|(a, b)| *a + *b);
+                let c = region.assign_advice(
+                    || "c",
+                    self.config.advice,
+                    2,
+                    || c.ok_or(CircuitError::MissingWitness.into()),
+                )?;
+                Ok((b, c))
+            },
+        )
+    }
+}
+
+// where a step gets its a and b from
+#[derive(Clone, Copy)]
+enum StepInput<'a, F: FieldExt> {
+    Witness(Option<F>),
+    Copy(&'a AssignedCell<F, F>),
+}
+
+impl<F: FieldExt> StepInput<'_, F> {
+    fn assign(
+        self,
+        region: &mut Region<'_, F>,
+        column: Column<Advice>,
+        offset: usize,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        match self {
+            StepInput::Witness(value) => region.assign_advice(
+                || "witness",
+                column,
+                offset,
+                || value.ok_or(CircuitError::MissingWitness.into()),
+            ),
+            StepInput::Copy(cell) => cell.copy_advice(|| "copy", region, column, offset),
+        }
+    }
+}
+
+impl<F: FieldExt> FiboInstructions<F> for FiboChip<F> {
+    fn assign(
+        &self,
+        mut layouter: impl Layouter<F>,
+        init_a: Option<F>,
+        init_b: Option<F>,
+        iter_num: usize,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        if iter_num < 3 {
+            return Err(CircuitError::IterTooSmall { iter_num, min: 3 }.into());
+        }
+
+        // step i computes the term in row i of example2's table
+        let (mut b, mut c) = self.assign_step(
+            layouter.namespace(|| "step 2"),
+            2,
+            StepInput::Witness(init_a),
+            StepInput::Witness(init_b),
+        )?;
+        for step in 3..iter_num {
+            (b, c) = self.assign_step(
+                layouter.namespace(|| format!("step {}", step)),
+                step,
+                StepInput::Copy(&b),
+                StepInput::Copy(&c),
+            )?;
+        }
+        Ok(c)
+    }
+
+    fn expose_public(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cell: &AssignedCell<F, F>,
+        row: usize,
+    ) -> Result<(), Error> {
+        layouter.constrain_instance(cell.cell(), self.config.instance, row)
+    }
+}
+
+pub struct MyCircuit<F> {
+    pub a: Option<F>,
+    pub b: Option<F>,
+    // the index of the output term, as in example2
+    pub iter_num: usize,
+}
+
+impl<F> Default for MyCircuit<F> {
+    fn default() -> Self {
+        Self {
+            a: None,
+            b: None,
+            iter_num: 10,
+        }
+    }
+}
+
+impl<F: FieldExt> MyCircuit<F> {
+    // a and b from plain integers, iter_num keeps its default
+    pub fn from_u64(a: u64, b: u64) -> Self {
+        Self {
+            a: Some(F::from(a)),
+            b: Some(F::from(b)),
+            ..Self::default()
+        }
+    }
+}
+
+impl<F: FieldExt> Circuit<F> for MyCircuit<F> {
+    type Config = FiboConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            iter_num: self.iter_num,
+            ..Self::default()
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let advice = meta.advice_column();
+        let instance = meta.instance_column();
+        FiboChip::configure(meta, advice, instance)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let chip = FiboChip::construct(config);
+        let out = chip.assign(
+            layouter.namespace(|| "fibonacci table"),
+            self.a,
+            self.b,
+            self.iter_num,
+        )?;
+        chip.expose_public(layouter.namespace(|| "out"), &out, 0)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MyCircuit;
+    use crate::fibonacci::example2;
+    use crate::testing::nth_fibonacci;
+    use halo2_proofs::{dev::MockProver, pasta::Fp};
+
+    fn circuit() -> MyCircuit<Fp> {
+        MyCircuit {
+            a: Some(Fp::from(1)),
+            b: Some(Fp::from(2)),
+            iter_num: 10,
+        }
+    }
+
+    #[test]
+    fn test_multi_region() {
+        // 8 regions of 3 rows
+        let k = 5;
+
+        let public_input = vec![Fp::from(nth_fibonacci(1, 2, 10))];
+        let prover = MockProver::run(k, &circuit(), vec![public_input]).unwrap();
+        prover.assert_satisfied();
+    }
+
+    #[test]
+    fn test_multi_region_failed() {
+        let k = 5;
+
+        let public_input = vec![Fp::from(nth_fibonacci(1, 2, 10) + 1)];
+        let prover = MockProver::run(k, &circuit(), vec![public_input]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn test_multi_region_rows() {
+        let k = 4;
+        let public_input = vec![Fp::from(nth_fibonacci(1, 2, 10))];
+
+        // the single region of example2 fits in 2^4 rows, the stacked regions don't
+        let single = example2::MyCircuit {
+            a: Some(Fp::from(1)),
+            b: Some(Fp::from(2)),
+            iter_num: 10,
+        };
+        assert!(MockProver::run(k, &single, vec![public_input.clone()]).is_ok());
+        assert!(MockProver::run(k, &circuit(), vec![public_input]).is_err());
+    }
+
+    #[cfg(feature = "dev-graph")]
+    #[test]
+    fn plot_multi_region() {
+        use plotters::prelude::*;
+
+        let root = BitMapBackend::new("multi-region-layout.png", (1024, 3096)).into_drawing_area();
+        root.fill(&WHITE).unwrap();
+        let root = root
+            .titled("Multi Region Layout", ("sans-serif", 60))
+            .unwrap();
+
+        halo2_proofs::dev::CircuitLayout::default()
+            .render(5, &circuit(), &root)
+            .unwrap();
+    }
+}
@@ -0,0 +1,307 @@
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+use super::{ColumnSummary, FiboInstructions};
+use crate::error::CircuitError;
+
+//
+// s_add | s_next | col_a | col_b | col_c
+// ------+--------+-------+-------+-------
+//   1   |   1    |  t0   |  t1   |  t2
+//   1   |   1    |  t2   |  t3   |  t4
+//   1   |   0    |  t4   |  t5   |  t6
+//
+// Each row adds two new terms: col_c in the same row, and col_b in the next one.
+//   s_add  * (a + b - c) = 0              t2 = t0 + t1
+//   s_next * (b + c - b_next) = 0         t3 = t1 + t2
+//   s_next * (c - a_next) = 0             the next row starts from t2
+// Since the next row is reached with Rotation::next(), not a single copy
+// constraint is needed between the rows, equality is only enabled to expose
+// the output.
+//
+// A sequence of iter_num terms takes ceil((iter_num - 1) / 2) rows instead of
+// example2's iter_num, which can make the difference of one k (see
+// test_packed_smaller_k). When iter_num is even, one extra term is computed in
+// the last row.
+
+#[derive(Debug, Clone)]
+pub struct FiboConfig {
+    pub advice: [Column<Advice>; 3],
+    pub instance: Column<Instance>,
+    pub s_add: Selector,
+    pub s_next: Selector,
+}
+
+impl FiboConfig {
+    pub fn column_summary(&self) -> ColumnSummary {
+        ColumnSummary::of(
+            &self.advice,
+            &[],
+            &[self.instance],
+            &[self.s_add, self.s_next],
+        )
+    }
+}
+
+pub struct FiboChip<F: FieldExt> {
+    config: FiboConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> FiboChip<F> {
+    pub fn construct(config: FiboConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        instance: Column<Instance>,
+    ) -> FiboConfig {
+        let [col_a, col_b, col_c] = advice;
+        let s_add = meta.selector();
+        let s_next = meta.selector();
+
+        // only to expose the output, the output can be in any column
+        for column in advice {
+            meta.enable_equality(column);
+        }
+        meta.enable_equality(instance);
+
+        meta.create_gate("add", |meta| {
+            let s = meta.query_selector(s_add);
+            let a = meta.query_advice(col_a, Rotation::cur());
+            let b = meta.query_advice(col_b, Rotation::cur());
+            let c = meta.query_advice(col_c, Rotation::cur());
+            vec![s * (a + b - c)]
+        });
+
+        meta.create_gate("next row", |meta| {
+            let s = meta.query_selector(s_next);
+            let b = meta.query_advice(col_b, Rotation::cur());
+            let c = meta.query_advice(col_c, Rotation::cur());
+            let a_next = meta.query_advice(col_a, Rotation::next());
+            let b_next = meta.query_advice(col_b, Rotation::next());
+            vec![s.clone() * (b + c.clone() - b_next), s * (c - a_next)]
+        });
+
+        FiboConfig {
+            advice,
+            instance,
+            s_add,
+            s_next,
+        }
+    }
+}
+
+impl<F: FieldExt> FiboInstructions<F> for FiboChip<F> {
+    fn assign(
+        &self,
+        mut layouter: impl Layouter<F>,
+        init_a: Option<F>,
+        init_b: Option<F>,
+        iter_num: usize,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        if iter_num < 3 {
+            return Err(CircuitError::IterTooSmall { iter_num, min: 3 }.into());
+        }
+        let rows = iter_num / 2;
+
+        // t0..t(2 * rows), which covers the iter_num terms
+        let mut terms = vec![init_a, init_b];
+        for i in 2..=2 * rows {
+            terms.push(terms[i - 2].zip(terms[i - 1]).map(|(a, b)| a + b));
+        }
+
+        layouter.assign_region(
+            || "packed fibonacci",
+            |mut region| {
+                let [col_a, col_b, col_c] = self.config.advice;
+
+                // the cells in term order: t0, t1, t2, t3, ...
+                let mut cells = vec![];
+                for row in 0..rows {
+                    self.config.s_add.enable(&mut region, row)?;
+                    if row + 1 < rows {
+                        self.config.s_next.enable(&mut region, row)?;
+                    }
+
+                    let t = 2 * row;
+                    let a = region.assign_advice(
+                        || "a",
+                        col_a,
+                        row,
+                        || terms[t].ok_or(CircuitError::MissingWitness.into()),
+                    )?;
+                    let b = region.assign_advice(
+                        || "b",
+                        col_b,
+                        row,
+                        || terms[t + 1].ok_or(CircuitError::MissingWitness.into()),
+                    )?;
+                    let c = region.assign_advice(
+                        || "c",
+                        col_c,
+                        row,
+                        || terms[t + 2].ok_or(CircuitError::MissingWitness.into()),
+                    )?;
+
+                    // a is the previous row's c
+                    if row == 0 {
+                        cells.push(a);
+                    }
+                    cells.push(b);
+                    cells.push(c);
+                }
+                Ok(cells.swap_remove(iter_num - 1))
+            },
+        )
+    }
+
+    fn expose_public(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cell: &AssignedCell<F, F>,
+        row: usize,
+    ) -> Result<(), Error> {
+        layouter.constrain_instance(cell.cell(), self.config.instance, row)
+    }
+}
+
+pub struct MyCircuit<F> {
+    pub a: Option<F>,
+    pub b: Option<F>,
+    // the index of the output term, as in example2
+    pub iter_num: usize,
+}
+
+impl<F> Default for MyCircuit<F> {
+    fn default() -> Self {
+        Self {
+            a: None,
+            b: None,
+            iter_num: 10,
+        }
+    }
+}
+
+impl<F: FieldExt> MyCircuit<F> {
+    // a and b from plain integers, iter_num keeps its default
+    pub fn from_u64(a: u64, b: u64) -> Self {
+        Self {
+            a: Some(F::from(a)),
+            b: Some(F::from(b)),
+            ..Self::default()
+        }
+    }
+}
+
+impl<F: FieldExt> Circuit<F> for MyCircuit<F> {
+    type Config = FiboConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            iter_num: self.iter_num,
+            ..Self::default()
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let advice = [
+            meta.advice_column(),
+            meta.advice_column(),
+            meta.advice_column(),
+        ];
+        let instance = meta.instance_column();
+        FiboChip::configure(meta, advice, instance)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let chip = FiboChip::construct(config);
+        let out = chip.assign(
+            layouter.namespace(|| "fibonacci table"),
+            self.a,
+            self.b,
+            self.iter_num,
+        )?;
+        chip.expose_public(layouter.namespace(|| "out"), &out, 0)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MyCircuit;
+    use crate::fibonacci::example2;
+    use crate::testing::nth_fibonacci;
+    use halo2_proofs::{dev::MockProver, pasta::Fp, plonk::Circuit};
+
+    // the smallest k the circuit can be laid out in
+    fn min_k<C: Circuit<Fp>>(circuit: &C, public_input: Vec<Fp>) -> u32 {
+        (1..10)
+            .find(|k| MockProver::run(*k, circuit, vec![public_input.clone()]).is_ok())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_packed() {
+        let k = 4;
+
+        // both an even and an odd number of terms
+        for iter_num in [9, 10] {
+            let circuit = MyCircuit {
+                a: Some(Fp::from(1)),
+                b: Some(Fp::from(2)),
+                iter_num,
+            };
+
+            let public_input = vec![Fp::from(nth_fibonacci(1, 2, iter_num))];
+            let prover = MockProver::run(k, &circuit, vec![public_input]).unwrap();
+            prover.assert_satisfied();
+        }
+    }
+
+    #[test]
+    fn test_packed_failed() {
+        let k = 4;
+
+        let circuit = MyCircuit {
+            a: Some(Fp::from(1)),
+            b: Some(Fp::from(2)),
+            iter_num: 10,
+        };
+
+        let public_input = vec![Fp::from(nth_fibonacci(1, 2, 10) + 1)];
+        let prover = MockProver::run(k, &circuit, vec![public_input]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn test_packed_smaller_k() {
+        let (a, b, iter_num) = (Some(Fp::from(1)), Some(Fp::from(1)), 20);
+        let public_input = vec![Fp::from(nth_fibonacci(1, 1, iter_num))];
+
+        let packed = MyCircuit { a, b, iter_num };
+        let single = example2::MyCircuit { a, b, iter_num };
+
+        let prover = MockProver::run(
+            min_k(&packed, public_input.clone()),
+            &packed,
+            vec![public_input.clone()],
+        )
+        .unwrap();
+        prover.assert_satisfied();
+
+        // 10 rows against 20
+        assert!(min_k(&packed, public_input.clone()) < min_k(&single, public_input));
+    }
+}
@@ -0,0 +1,268 @@
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+use super::{ColumnSummary, FiboInstructions};
+use crate::error::CircuitError;
+
+//
+// selector |       col    |
+// ---------+--------------|
+//          |      a0      |
+//   s1     |      a1      |
+//   s2     | a2 = a0 + a1 |
+//          | a3 = a1 + a2 |
+//
+// The same table as example2, but the gate is centered on the middle term
+//   a = query(col, Rotation::prev())
+//   b = query(col, Rotation::cur())
+//   c = query(col, Rotation::next())
+// instead of looking two rows ahead with Rotation::next() and Rotation(2).
+//
+// The selector moves down a row with it: example2 enables it on rows
+// 0..iter_num - 2, here it's on rows 1..iter_num - 1. Row 0 can't have it,
+// since its previous row would wrap around to the end of the table.
+
+#[derive(Debug, Clone)]
+pub struct FiboConfig {
+    pub advice: Column<Advice>,
+    pub instance: Column<Instance>,
+    pub selector: Selector,
+}
+
+impl FiboConfig {
+    pub fn column_summary(&self) -> ColumnSummary {
+        ColumnSummary::of(&[self.advice], &[], &[self.instance], &[self.selector])
+    }
+}
+
+pub struct FiboChip<F: FieldExt> {
+    config: FiboConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> FiboChip<F> {
+    pub fn construct(config: FiboConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: Column<Advice>,
+        instance: Column<Instance>,
+    ) -> FiboConfig {
+        let selector = meta.selector();
+
+        // for permutation check
+        meta.enable_equality(advice);
+        meta.enable_equality(instance);
+
+        meta.create_gate("add", |meta| {
+            let s = meta.query_selector(selector);
+            let a = meta.query_advice(advice, Rotation::prev());
+            let b = meta.query_advice(advice, Rotation::cur());
+            let c = meta.query_advice(advice, Rotation::next());
+            vec![s * (a + b - c)]
+        });
+
+        FiboConfig {
+            advice,
+            instance,
+            selector,
+        }
+    }
+}
+
+impl<F: FieldExt> FiboInstructions<F> for FiboChip<F> {
+    fn assign(
+        &self,
+        mut layouter: impl Layouter<F>,
+        init_a: Option<F>,
+        init_b: Option<F>,
+        iter_num: usize,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        if iter_num < 3 {
+            return Err(CircuitError::IterTooSmall { iter_num, min: 3 }.into());
+        }
+
+        layouter.assign_region(
+            || "fibonacci region",
+            |mut region| {
+                let mut a = init_a;
+                let mut b = init_b;
+
+                region.assign_advice(
+                    || "a",
+                    self.config.advice,
+                    0,
+                    || a.ok_or(CircuitError::MissingWitness.into()),
+                )?;
+                let mut b_cell = region.assign_advice(
+                    || "b",
+                    self.config.advice,
+                    1,
+                    || b.ok_or(CircuitError::MissingWitness.into()),
+                )?;
+
+                for row in 2..iter_num {
+                    // the term in `row` is checked by the gate one row above it
+                    self.config.selector.enable(&mut region, row - 1)?;
+
+                    b_cell = region.assign_advice(
+                        || "advice",
+                        self.config.advice,
+                        row,
+                        || {
+                            b.and_then(|b| a.map(|a| a + b))
+                                .ok_or(CircuitError::MissingWitness.into())
+                        },
+                    )?;
+
+                    a = b;
+                    b = b_cell.value().copied();
+                }
+                Ok(b_cell)
+            },
+        )
+    }
+
+    fn expose_public(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cell: &AssignedCell<F, F>,
+        row: usize,
+    ) -> Result<(), Error> {
+        layouter.constrain_instance(cell.cell(), self.config.instance, row)
+    }
+}
+
+pub struct MyCircuit<F> {
+    pub a: Option<F>,
+    pub b: Option<F>,
+    // the number of rows in the table, which is also the index of the output term
+    pub iter_num: usize,
+}
+
+impl<F> Default for MyCircuit<F> {
+    fn default() -> Self {
+        Self {
+            a: None,
+            b: None,
+            iter_num: 10,
+        }
+    }
+}
+
+impl<F: FieldExt> MyCircuit<F> {
+    // a and b from plain integers, iter_num keeps its default
+    pub fn from_u64(a: u64, b: u64) -> Self {
+        Self {
+            a: Some(F::from(a)),
+            b: Some(F::from(b)),
+            ..Self::default()
+        }
+    }
+}
+
+impl<F: FieldExt> Circuit<F> for MyCircuit<F> {
+    type Config = FiboConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            iter_num: self.iter_num,
+            ..Self::default()
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let advice = meta.advice_column();
+        let instance = meta.instance_column();
+        FiboChip::configure(meta, advice, instance)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let chip = FiboChip::construct(config);
+        let c_cell = chip.assign(
+            layouter.namespace(|| "fibonacci table"),
+            self.a,
+            self.b,
+            self.iter_num,
+        )?;
+        chip.expose_public(layouter.namespace(|| "out"), &c_cell, 0)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MyCircuit;
+    use crate::testing::{nth_fibonacci, proptest_config};
+    use halo2_proofs::{dev::MockProver, pasta::Fp};
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_prev_rotation() {
+        let k = 4;
+
+        let circuit = MyCircuit {
+            a: Some(Fp::from(1)),
+            b: Some(Fp::from(2)),
+            iter_num: 10,
+        };
+
+        let public_input = vec![Fp::from(nth_fibonacci(1, 2, 10))];
+        let prover = MockProver::run(k, &circuit, vec![public_input]).unwrap();
+        prover.assert_satisfied();
+    }
+
+    #[test]
+    fn test_prev_rotation_failed() {
+        let k = 4;
+
+        let circuit = MyCircuit {
+            a: Some(Fp::from(1)),
+            b: Some(Fp::from(2)),
+            iter_num: 10,
+        };
+
+        let public_input = vec![Fp::from(nth_fibonacci(1, 2, 10) + 1)];
+        let prover = MockProver::run(k, &circuit, vec![public_input]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    proptest! {
+        // every case runs MockProver twice, so keep the count down
+        #![proptest_config(proptest_config(32))]
+
+        #[test]
+        fn test_prev_rotation_prop(a in 0u64..1000, b in 0u64..1000, delta in 1u64..1000) {
+            let k = 4;
+
+            let mut terms = (Fp::from(a), Fp::from(b));
+            for _ in 2..10 {
+                terms = (terms.1, terms.0 + terms.1);
+            }
+            let out = terms.1;
+
+            let circuit = MyCircuit {
+                a: Some(Fp::from(a)),
+                b: Some(Fp::from(b)),
+                iter_num: 10,
+            };
+
+            let prover = MockProver::run(k, &circuit, vec![vec![out]]).unwrap();
+            prop_assert!(prover.verify().is_ok());
+
+            let prover = MockProver::run(k, &circuit, vec![vec![out + Fp::from(delta)]]).unwrap();
+            prop_assert!(prover.verify().is_err());
+        }
+    }
+}
@@ -0,0 +1,372 @@
+use halo2_proofs::{
+    dev::MockProver,
+    pasta::{EqAffine, Fp},
+    plonk::*,
+    poly::commitment::Params,
+    transcript::{Blake2bRead, Blake2bWrite, Challenge255},
+};
+use rand_core::{OsRng, RngCore};
+
+use crate::error::CircuitError;
+
+// MockProver only checks that the constraints are satisfied, here we go through
+// the real flow instead:
+//   keygen_vk -> keygen_pk -> create_proof -> verify_proof
+// using the IPA commitment scheme over the Pasta curves (Fp is the scalar field of EqAffine).
+//
+// Errors from key generation and proving are returned as-is, while a proof that
+// fails to verify yields Ok(false).
+pub fn prove_and_verify<C: Circuit<Fp>>(
+    k: u32,
+    circuit: C,
+    public: Vec<Fp>,
+) -> Result<bool, Error> {
+    let params: Params<EqAffine> = Params::new(k);
+
+    // keys only depend on the circuit shape, not on the witnesses
+    let vk = keygen_vk(&params, &circuit)?;
+    let pk = keygen_pk(&params, vk, &circuit)?;
+
+    let proof = prove(&params, &pk, circuit, &public)?;
+    Ok(verify(&params, pk.get_vk(), &proof, &public))
+}
+
+// the largest k min_k and example2's min_k_for try, 2^18 rows is already a
+// slow MockProver run
+pub const MAX_K: u32 = 18;
+
+// The smallest k for which the circuit lays out, i.e. for which MockProver
+// (and so keygen and the prover) doesn't return NotEnoughRowsAvailable.
+//
+// This works for any circuit, without knowing how its size depends on its
+// parameters (example2 has min_k_for, computed from iter_num): every k is
+// tried from the smallest table halo2 accepts, which is quick since the
+// failing runs stop as soon as a cell falls outside of the usable rows.
+pub fn min_k<C: Circuit<Fp>>(circuit: &C, public: &[Fp]) -> Result<u32, Error> {
+    for k in 1..=MAX_K {
+        match MockProver::run(k, circuit, vec![public.to_vec()]) {
+            Ok(_) => return Ok(k),
+            Err(Error::NotEnoughRowsAvailable { .. }) | Err(Error::InstanceTooLarge) => {}
+            Err(err) => return Err(err),
+        }
+    }
+    Err(Error::NotEnoughRowsAvailable { current_k: MAX_K })
+}
+
+// same as `prove`, with k picked by min_k and the params and keys generated
+// for it. The verifier needs the same k to rebuild the params, so it's
+// returned with the proof.
+pub fn prove_auto_k<C: Circuit<Fp>>(circuit: C, public: &[Fp]) -> Result<(u32, Vec<u8>), Error> {
+    let k = min_k(&circuit, public)?;
+    let params: Params<EqAffine> = Params::new(k);
+
+    let vk = keygen_vk(&params, &circuit)?;
+    let pk = keygen_pk(&params, vk, &circuit)?;
+
+    let proof = prove(&params, &pk, circuit, public)?;
+    Ok((k, proof))
+}
+
+// creates a proof and returns the transcript bytes
+pub fn prove<C: Circuit<Fp>>(
+    params: &Params<EqAffine>,
+    pk: &ProvingKey<EqAffine>,
+    circuit: C,
+    public: &[Fp],
+) -> Result<Vec<u8>, Error> {
+    prove_with_rng(params, pk, circuit, public, OsRng)
+}
+
+// same as `prove`, with the randomness taken from `rng`.
+//
+// The params (from k alone), the keys and the Blake2b transcript are all
+// deterministic; the only randomness is what create_proof draws from its rng,
+// to blind the advice polynomials and the commitments so that the proof hides
+// the witnesses. With OsRng every proof is different, with a seeded rng the
+// same inputs give the same bytes. That's only meant for reproducible tests:
+// a predictable rng leaks the witnesses, real proofs should use OsRng.
+pub fn prove_with_rng<C: Circuit<Fp>>(
+    params: &Params<EqAffine>,
+    pk: &ProvingKey<EqAffine>,
+    circuit: C,
+    public: &[Fp],
+    rng: impl RngCore,
+) -> Result<Vec<u8>, Error> {
+    let mut transcript = Blake2bWrite::<_, _, Challenge255<_>>::init(vec![]);
+    create_proof(params, pk, &[circuit], &[&[public]], rng, &mut transcript)?;
+    Ok(transcript.finalize())
+}
+
+// verify_proof stops reading once it has what it needs, so the proof must
+// also have been read to its end: trailing bytes would make another valid
+// encoding of the same proof.
+pub fn verify(
+    params: &Params<EqAffine>,
+    vk: &VerifyingKey<EqAffine>,
+    proof: &[u8],
+    public: &[Fp],
+) -> bool {
+    let strategy = SingleVerifier::new(params);
+    let mut rest = proof;
+    let mut transcript = Blake2bRead::<_, _, Challenge255<_>>::init(&mut rest);
+    verify_proof(params, vk, strategy, &[&[public]], &mut transcript).is_ok() && rest.is_empty()
+}
+
+// same as `prove_and_verify`, with several instances of the circuit in one proof,
+// `publics[i]` being the instance column of `circuits[i]`.
+pub fn prove_and_verify_batch<C: Circuit<Fp>>(
+    k: u32,
+    circuits: Vec<C>,
+    publics: Vec<Vec<Fp>>,
+) -> Result<bool, Error> {
+    // the keys come from the first circuit, and each one needs its public input
+    if circuits.is_empty() {
+        return Err(CircuitError::EmptyInput.into());
+    }
+    if publics.len() != circuits.len() {
+        return Err(CircuitError::LengthMismatch {
+            expected: circuits.len(),
+            actual: publics.len(),
+        }
+        .into());
+    }
+
+    let params: Params<EqAffine> = Params::new(k);
+
+    // all the circuits have the same shape, any of them gives the keys
+    let vk = keygen_vk(&params, &circuits[0])?;
+    let pk = keygen_pk(&params, vk, &circuits[0])?;
+
+    let proof = prove_batch(&params, &pk, circuits, &publics)?;
+    Ok(verify_batch(&params, pk.get_vk(), &proof, &publics))
+}
+
+// proves all the circuits at once, they must share the proving key.
+//
+// create_proof commits to the advice of every circuit separately, but they
+// all go into the same transcript: the challenges, the quotient polynomial
+// (one for all the circuits) and above all the final multiopen argument are
+// shared. The proof is thus smaller than the separate proofs put together and
+// verify_batch does a single multi-scalar multiplication, the expensive part
+// of IPA verification, instead of one per proof (see test_prove_batch).
+pub fn prove_batch<C: Circuit<Fp>>(
+    params: &Params<EqAffine>,
+    pk: &ProvingKey<EqAffine>,
+    circuits: Vec<C>,
+    publics: &[Vec<Fp>],
+) -> Result<Vec<u8>, Error> {
+    let instances: Vec<[&[Fp]; 1]> = publics.iter().map(|public| [&public[..]]).collect();
+    let instances: Vec<&[&[Fp]]> = instances.iter().map(|columns| &columns[..]).collect();
+
+    let mut transcript = Blake2bWrite::<_, _, Challenge255<_>>::init(vec![]);
+    create_proof(params, pk, &circuits, &instances, OsRng, &mut transcript)?;
+    Ok(transcript.finalize())
+}
+
+pub fn verify_batch(
+    params: &Params<EqAffine>,
+    vk: &VerifyingKey<EqAffine>,
+    proof: &[u8],
+    publics: &[Vec<Fp>],
+) -> bool {
+    let instances: Vec<[&[Fp]; 1]> = publics.iter().map(|public| [&public[..]]).collect();
+    let instances: Vec<&[&[Fp]]> = instances.iter().map(|columns| &columns[..]).collect();
+
+    let strategy = SingleVerifier::new(params);
+    let mut rest = proof;
+    let mut transcript = Blake2bRead::<_, _, Challenge255<_>>::init(&mut rest);
+    verify_proof(params, vk, strategy, &instances, &mut transcript).is_ok() && rest.is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        min_k, prove, prove_and_verify, prove_and_verify_batch, prove_auto_k, prove_batch,
+        prove_with_rng, verify, verify_batch,
+    };
+    use crate::fibonacci::example2::{min_k_for, MyCircuit};
+    use crate::testing::nth_fibonacci;
+    use halo2_proofs::{
+        pasta::{EqAffine, Fp},
+        plonk::{keygen_pk, keygen_vk},
+        poly::commitment::Params,
+    };
+    use rand_chacha::ChaCha20Rng;
+    use rand_core::SeedableRng;
+
+    #[test]
+    fn test_prove_example2() {
+        let k = 4;
+
+        let circuit = MyCircuit {
+            a: Some(Fp::from(1)),
+            b: Some(Fp::from(2)),
+            iter_num: 10,
+        };
+
+        let public_input = vec![Fp::from(nth_fibonacci(1, 2, 10))];
+        assert!(prove_and_verify(k, circuit, public_input).unwrap());
+    }
+
+    #[test]
+    fn test_prove_example2_tampered() {
+        let k = 4;
+
+        let circuit = MyCircuit {
+            a: Some(Fp::from(1)),
+            b: Some(Fp::from(2)),
+            iter_num: 10,
+        };
+
+        // the prover doesn't check the instance, the proof just won't verify
+        let public_input = vec![Fp::from(nth_fibonacci(1, 2, 10) + 1)];
+        assert!(!prove_and_verify(k, circuit, public_input).unwrap());
+    }
+
+    #[test]
+    fn test_prove_example2_builder() {
+        let k = 4;
+
+        let circuit = MyCircuit::builder()
+            .a(Fp::from(1))
+            .b(Fp::from(2))
+            .iter_num(10)
+            .build()
+            .unwrap();
+
+        let public_input = vec![Fp::from(nth_fibonacci(1, 2, 10))];
+        assert!(prove_and_verify(k, circuit, public_input).unwrap());
+    }
+
+    #[test]
+    fn test_prove_auto_k() {
+        let circuit = MyCircuit {
+            a: Some(Fp::from(1)),
+            b: Some(Fp::from(2)),
+            iter_num: 50,
+        };
+        let public_input = vec![Fp::from(nth_fibonacci(1, 2, 50))];
+
+        // 50 rows don't fit in 2^5 = 32, they do in 2^6 = 64
+        let (k, proof) = prove_auto_k(circuit, &public_input).unwrap();
+        assert_eq!(k, 6);
+        assert_eq!(k, min_k_for(50).unwrap());
+
+        let params: Params<EqAffine> = Params::new(k);
+        let shape = MyCircuit::<Fp> {
+            iter_num: 50,
+            ..MyCircuit::default()
+        };
+        let vk = keygen_vk(&params, &shape).unwrap();
+        assert!(verify(&params, &vk, &proof, &public_input));
+    }
+
+    #[test]
+    fn test_min_k() {
+        // the same k as example2's own bound, around the jump at 11 terms
+        for iter_num in [4, 10, 11, 26, 27] {
+            let circuit = MyCircuit::<Fp> {
+                iter_num,
+                ..MyCircuit::from_u64(1, 2)
+            };
+            let public_input = vec![Fp::from(nth_fibonacci(1, 2, iter_num))];
+            assert_eq!(
+                min_k(&circuit, &public_input).unwrap(),
+                min_k_for(iter_num).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_prove_deterministic() {
+        let k = 4;
+
+        let circuit = || MyCircuit {
+            a: Some(Fp::from(1)),
+            b: Some(Fp::from(2)),
+            iter_num: 10,
+        };
+        let public_input = vec![Fp::from(nth_fibonacci(1, 2, 10))];
+
+        let params: Params<EqAffine> = Params::new(k);
+        let vk = keygen_vk(&params, &circuit()).unwrap();
+        let pk = keygen_pk(&params, vk, &circuit()).unwrap();
+
+        // the same seed gives the same bytes
+        let seeded = |seed| {
+            let rng = ChaCha20Rng::seed_from_u64(seed);
+            prove_with_rng(&params, &pk, circuit(), &public_input, rng).unwrap()
+        };
+        let proof = seeded(42);
+        assert_eq!(proof, seeded(42));
+        assert_ne!(proof, seeded(43));
+        assert!(verify(&params, pk.get_vk(), &proof, &public_input));
+
+        // OsRng blinds every proof differently, both still verify
+        let proof1 = prove(&params, &pk, circuit(), &public_input).unwrap();
+        let proof2 = prove(&params, &pk, circuit(), &public_input).unwrap();
+        assert_ne!(proof1, proof2);
+        assert!(verify(&params, pk.get_vk(), &proof1, &public_input));
+        assert!(verify(&params, pk.get_vk(), &proof2, &public_input));
+    }
+
+    #[test]
+    fn test_prove_batch() {
+        let k = 4;
+
+        let inputs = [(1, 2), (1, 1), (3, 5)];
+        let circuits = || {
+            inputs
+                .iter()
+                .map(|&(a, b)| MyCircuit {
+                    a: Some(Fp::from(a)),
+                    b: Some(Fp::from(b)),
+                    iter_num: 10,
+                })
+                .collect::<Vec<_>>()
+        };
+        let publics: Vec<_> = inputs
+            .iter()
+            .map(|&(a, b)| vec![Fp::from(nth_fibonacci(a, b, 10))])
+            .collect();
+
+        assert!(prove_and_verify_batch(k, circuits(), publics.clone()).unwrap());
+
+        let params: Params<EqAffine> = Params::new(k);
+        let vk = keygen_vk(&params, &circuits()[0]).unwrap();
+        let pk = keygen_pk(&params, vk, &circuits()[0]).unwrap();
+
+        let batch = prove_batch(&params, &pk, circuits(), &publics).unwrap();
+        assert!(verify_batch(&params, pk.get_vk(), &batch, &publics));
+
+        // one wrong output fails the whole batch
+        let mut tampered = publics.clone();
+        tampered[1][0] += Fp::one();
+        assert!(!verify_batch(&params, pk.get_vk(), &batch, &tampered));
+
+        // smaller than the three separate proofs
+        let separate: usize = circuits()
+            .into_iter()
+            .zip(&publics)
+            .map(|(circuit, public)| prove(&params, &pk, circuit, public).unwrap().len())
+            .sum();
+        assert!(batch.len() < separate);
+    }
+
+    #[test]
+    fn test_prove_batch_mismatched() {
+        let k = 4;
+        let circuit = || MyCircuit {
+            a: Some(Fp::from(1)),
+            b: Some(Fp::from(2)),
+            iter_num: 10,
+        };
+        let public = vec![Fp::from(nth_fibonacci(1, 2, 10))];
+
+        // nothing to take the keys from
+        assert!(prove_and_verify_batch::<MyCircuit<Fp>>(k, vec![], vec![]).is_err());
+
+        // two circuits, one public input
+        assert!(prove_and_verify_batch(k, vec![circuit(), circuit()], vec![public]).is_err());
+    }
+}
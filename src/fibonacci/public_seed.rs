@@ -0,0 +1,105 @@
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    example2::{self, FiboChip, FiboConfig},
+    FiboInstructions,
+};
+
+//
+// selector |       col    |   instance
+// ---------+--------------+-------------
+//   s0     |      a0      |   out
+//   s1     |      a1      |   a0   -> copied to row 0 of col
+//   s2     | a2 = a0 + a1 |   a1   -> copied to row 1 of col
+//   ...    |     ...      |
+//          |      out     |   -> constrained to instance row 0
+//
+// example2's table, where neither of the initial terms is a witness: both are
+// copied from the instance column with assign_advice_from_instance. The copies
+// land in rows 0 and 1 of the advice column, the same cells the gate reads as
+// a and b, so the whole sequence is fixed by the public inputs and the circuit
+// has nothing private left. The verifier picks the start of the sequence,
+// and the proof only shows that `out` is its iter_num-th term.
+
+pub struct MyCircuit {
+    // the number of rows in the table, which is also the index of the output term
+    pub iter_num: usize,
+}
+
+impl Default for MyCircuit {
+    fn default() -> Self {
+        Self { iter_num: 10 }
+    }
+}
+
+impl<F: FieldExt> Circuit<F> for MyCircuit {
+    type Config = FiboConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    // there aren't any witnesses to drop
+    fn without_witnesses(&self) -> Self {
+        Self {
+            iter_num: self.iter_num,
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        example2::MyCircuit::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let chip = FiboChip::construct(config);
+        let out = chip.assign_with_public_seed(
+            layouter.namespace(|| "fibonacci table"),
+            1,
+            2,
+            self.iter_num,
+        )?;
+        chip.expose_public(layouter.namespace(|| "out"), &out, 0)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MyCircuit;
+    use crate::testing::nth_fibonacci;
+    use halo2_proofs::{dev::MockProver, pasta::Fp};
+
+    #[test]
+    fn test_public_seed() {
+        let k = 4;
+
+        // any start, with the same circuit
+        for (a, b) in [(1, 2), (3, 5), (0, 1)] {
+            let public_input = vec![Fp::from(nth_fibonacci(a, b, 10)), Fp::from(a), Fp::from(b)];
+            let prover = MockProver::run(k, &MyCircuit::default(), vec![public_input]).unwrap();
+            prover.assert_satisfied();
+        }
+    }
+
+    #[test]
+    fn test_public_seed_failed() {
+        let k = 4;
+
+        // a wrong output
+        let public_input = vec![
+            Fp::from(nth_fibonacci(1, 2, 10) + 1),
+            Fp::from(1),
+            Fp::from(2),
+        ];
+        let prover = MockProver::run(k, &MyCircuit::default(), vec![public_input]).unwrap();
+        assert!(prover.verify().is_err());
+
+        // the output of 1, 2 with the initial terms swapped, the copied cells
+        // are the ones the gate adds up
+        let public_input = vec![Fp::from(nth_fibonacci(1, 2, 10)), Fp::from(2), Fp::from(1)];
+        let prover = MockProver::run(k, &MyCircuit::default(), vec![public_input]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}
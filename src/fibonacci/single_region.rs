@@ -0,0 +1,169 @@
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::example1::{self, compute_witnesses, FiboConfig};
+use crate::error::CircuitError;
+
+//
+// selector | col_a | col_b | col_c
+// ---------+-------+-------+-------
+//   s0     |   a0  |   b0  |   c0       region "fibonacci table", offset 0
+//   s1     |   a1  |   b1  |   c1       offset 1
+//   ...    |  ...  |  ...  |  ...
+//
+// example1's columns and gate, but the whole table is one region and every row
+// is assigned at its own offset, like example2 does, instead of one region per
+// row. The rows are still chained with copies (a1 = b0, b1 = c0), now made with
+// constrain_equal inside the region.
+//
+// Why one region: the layouter measures every region (a first pass over its
+// assignments) and then places it, so fewer regions is less synthesis work,
+// and a floor planner can't leave gaps inside a region. With
+// SimpleFloorPlanner example1's one-row regions already end up stacked back to
+// back, so the table takes the same 8 rows and the same k here, the gain is
+// 1 region instead of 8 (see test_single_region_layout). Within one region the
+// gate could also reach the next row with Rotation::next() and drop the copies
+// altogether, which is what packed.rs does.
+
+// N is the index of the last Fibonacci term, as in example1
+#[derive(Default)]
+pub struct MyCircuit<F, const N: usize> {
+    pub a: Option<F>,
+    pub b: Option<F>,
+}
+
+impl<F: FieldExt, const N: usize> Circuit<F> for MyCircuit<F, N> {
+    type Config = FiboConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        example1::MyCircuit::<F, N>::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        // the first row alone holds 3 terms
+        if N < 3 {
+            return Err(CircuitError::IterTooSmall {
+                iter_num: N,
+                min: 3,
+            }
+            .into());
+        }
+
+        // None when synthesizing without witnesses
+        let terms = self.a.zip(self.b).map(|(a, b)| compute_witnesses(a, b, N));
+        let term = |i: usize| terms.as_ref().map(|terms| terms[i]);
+
+        let out = layouter.assign_region(
+            || "fibonacci table",
+            |mut region| {
+                let [col_a, col_b, col_c] = config.advice;
+                let mut prev: Option<(AssignedCell<F, F>, AssignedCell<F, F>)> = None;
+
+                // row r holds terms r, r + 1 and r + 2
+                for row in 0..N - 2 {
+                    config.selector.enable(&mut region, row)?;
+
+                    let mut assign = |name, column, i| {
+                        region.assign_advice(
+                            || name,
+                            column,
+                            row,
+                            || term(i).ok_or(CircuitError::MissingWitness.into()),
+                        )
+                    };
+                    let a = assign("a", col_a, row)?;
+                    let b = assign("b", col_b, row + 1)?;
+                    let c = assign("c", col_c, row + 2)?;
+
+                    if let Some((prev_b, prev_c)) = prev {
+                        region.constrain_equal(a.cell(), prev_b.cell())?;
+                        region.constrain_equal(b.cell(), prev_c.cell())?;
+                    }
+                    prev = Some((b, c));
+                }
+                Ok(prev.unwrap().1)
+            },
+        )?;
+        layouter.constrain_instance(out.cell(), config.instance, 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MyCircuit;
+    use crate::fibonacci::example1;
+    use crate::testing::{dump_regions, nth_fibonacci};
+    use halo2_proofs::{dev::MockProver, pasta::Fp, plonk::Circuit};
+
+    // the smallest k the circuit can be laid out in
+    fn min_k<C: Circuit<Fp>>(circuit: &C, public_input: Vec<Fp>) -> u32 {
+        (1..10)
+            .find(|k| MockProver::run(*k, circuit, vec![public_input.clone()]).is_ok())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_single_region() {
+        let circuit = MyCircuit::<_, 10> {
+            a: Some(Fp::from(1)),
+            b: Some(Fp::from(2)),
+        };
+
+        let public_input = vec![Fp::from(nth_fibonacci(1, 2, 10))];
+        let prover = MockProver::run(4, &circuit, vec![public_input]).unwrap();
+        prover.assert_satisfied();
+    }
+
+    #[test]
+    fn test_single_region_failed() {
+        let circuit = MyCircuit::<_, 10> {
+            a: Some(Fp::from(1)),
+            b: Some(Fp::from(2)),
+        };
+
+        let public_input = vec![Fp::from(nth_fibonacci(1, 2, 10) + 1)];
+        let prover = MockProver::run(4, &circuit, vec![public_input]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn test_single_region_too_small() {
+        // N = 2 has no row at all, and N < 2 would underflow the row count
+        let circuit = MyCircuit::<_, 2> {
+            a: Some(Fp::from(1)),
+            b: Some(Fp::from(2)),
+        };
+        assert!(MockProver::run(4, &circuit, vec![vec![Fp::from(2)]]).is_err());
+    }
+
+    #[test]
+    fn test_single_region_layout() {
+        let single = MyCircuit::<_, 10> {
+            a: Some(Fp::from(1)),
+            b: Some(Fp::from(2)),
+        };
+        let per_row = example1::MyCircuit::<_, 10>::from_u64(1, 2);
+        let public_input = vec![Fp::from(nth_fibonacci(1, 2, 10))];
+
+        // the same output...
+        let k = min_k(&single, public_input.clone());
+        let prover = MockProver::run(k, &per_row, vec![public_input.clone()]).unwrap();
+        prover.assert_satisfied();
+        assert!(k <= min_k(&per_row, public_input));
+
+        // ...in the same rows, with 1 region instead of 8
+        let regions = dump_regions(k, &single);
+        assert_eq!(regions, vec![("fibonacci table".to_string(), 0..8)]);
+        let per_row_regions = dump_regions(k, &per_row);
+        assert_eq!(per_row_regions.len(), 8);
+        assert_eq!(per_row_regions.last().unwrap().1.end, 8);
+    }
+}
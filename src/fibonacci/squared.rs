@@ -0,0 +1,365 @@
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+use super::{ColumnSummary, FiboInstructions};
+use crate::error::CircuitError;
+
+//
+// selector | col_a | col_b | col_c
+// ---------+-------+-------+-------
+//   s0     |   a0  |   b0  |   c0      (c0 = a0^2 + b0^2)
+//   s1     |   b0  |   c0  |   c1      (c1 = b0^2 + c0^2)
+//
+// Same layout as example1, only the gate changes:
+//   s * (a * a + b * b - c) = 0
+// which is degree 3 (2 from a * a, 1 from the selector), while example1's
+// s * (a + b - c) is degree 2.
+//
+// NOTE: a higher degree doesn't mean a bigger k by itself. k only has to fit the
+// rows, but the prover evaluates the constraints on an extended domain of
+// 2^(k + ceil(log2(degree - 1))) points. Here the permutation argument already
+// needs degree 3, so both circuits end up with the same degree and the same k
+// (see test_squared_degree). A gate of degree 4 or more, e.g. a^3, would make
+// the extended domain twice as big again.
+
+#[derive(Debug, Clone)]
+pub struct SquaredConfig {
+    pub advice: [Column<Advice>; 3],
+    pub instance: Column<Instance>,
+    pub selector: Selector,
+}
+
+impl SquaredConfig {
+    pub fn column_summary(&self) -> ColumnSummary {
+        ColumnSummary::of(&self.advice, &[], &[self.instance], &[self.selector])
+    }
+}
+
+// (b, c) of the first row
+type CellPair<F> = (AssignedCell<F, F>, AssignedCell<F, F>);
+
+// the first row already holds the 3rd term
+const MIN_ITER_NUM: usize = 3;
+
+fn check_iter_num(iter_num: usize) -> Result<(), CircuitError> {
+    if iter_num < MIN_ITER_NUM {
+        return Err(CircuitError::IterTooSmall {
+            iter_num,
+            min: MIN_ITER_NUM,
+        });
+    }
+    Ok(())
+}
+
+pub struct SquaredChip<F: FieldExt> {
+    config: SquaredConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> SquaredChip<F> {
+    pub fn construct(config: SquaredConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        instance: Column<Instance>,
+    ) -> SquaredConfig {
+        let col_a = advice[0];
+        let col_b = advice[1];
+        let col_c = advice[2];
+        let selector = meta.selector();
+
+        // for permutation check
+        meta.enable_equality(col_a);
+        meta.enable_equality(col_b);
+        meta.enable_equality(col_c);
+        meta.enable_equality(instance);
+
+        meta.create_gate("square add", |meta| {
+            let s = meta.query_selector(selector);
+            let a = meta.query_advice(col_a, Rotation::cur());
+            let b = meta.query_advice(col_b, Rotation::cur());
+            let c = meta.query_advice(col_c, Rotation::cur());
+            vec![s * (a.clone() * a + b.clone() * b - c)]
+        });
+
+        SquaredConfig {
+            advice: [col_a, col_b, col_c],
+            instance,
+            selector,
+        }
+    }
+
+    pub fn assign_first_row(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: Option<F>,
+        b: Option<F>,
+    ) -> Result<CellPair<F>, Error> {
+        layouter.assign_region(
+            || "first row",
+            |mut region| {
+                self.config.selector.enable(&mut region, 0)?;
+                let c_val = a.and_then(|a| b.map(|b| a.square() + b.square()));
+
+                region.assign_advice(
+                    || "a",
+                    self.config.advice[0],
+                    0,
+                    || a.ok_or(CircuitError::MissingWitness.into()),
+                )?;
+                let b_cell = region.assign_advice(
+                    || "b",
+                    self.config.advice[1],
+                    0,
+                    || b.ok_or(CircuitError::MissingWitness.into()),
+                )?;
+                let c_cell = region.assign_advice(
+                    || "c",
+                    self.config.advice[2],
+                    0,
+                    || c_val.ok_or(CircuitError::MissingWitness.into()),
+                )?;
+
+                Ok((b_cell, c_cell))
+            },
+        )
+    }
+
+    pub fn assign_row(
+        &self,
+        mut layouter: impl Layouter<F>,
+        prev_b: &AssignedCell<F, F>,
+        prev_c: &AssignedCell<F, F>,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        layouter.assign_region(
+            || "row",
+            |mut region| {
+                self.config.selector.enable(&mut region, 0)?;
+
+                let a = prev_b.copy_advice(|| "a", &mut region, self.config.advice[0], 0)?;
+                let b = prev_c.copy_advice(|| "b", &mut region, self.config.advice[1], 0)?;
+
+                let c_val = a
+                    .value()
+                    .and_then(|a| b.value().map(|b| a.square() + b.square()));
+                region.assign_advice(
+                    || "c",
+                    self.config.advice[2],
+                    0,
+                    || c_val.ok_or(CircuitError::MissingWitness.into()),
+                )
+            },
+        )
+    }
+}
+
+impl<F: FieldExt> FiboInstructions<F> for SquaredChip<F> {
+    fn assign(
+        &self,
+        mut layouter: impl Layouter<F>,
+        init_a: Option<F>,
+        init_b: Option<F>,
+        iter_num: usize,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        check_iter_num(iter_num)?;
+
+        let (mut prev_b, mut prev_c) =
+            self.assign_first_row(layouter.namespace(|| "first row"), init_a, init_b)?;
+        for _i in 3..iter_num {
+            let c_cell = self.assign_row(layouter.namespace(|| "next row"), &prev_b, &prev_c)?;
+            prev_b = prev_c;
+            prev_c = c_cell;
+        }
+        Ok(prev_c)
+    }
+
+    fn expose_public(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cell: &AssignedCell<F, F>,
+        row: usize,
+    ) -> Result<(), Error> {
+        layouter.constrain_instance(cell.cell(), self.config.instance, row)
+    }
+}
+
+pub struct MyCircuit<F> {
+    pub a: Option<F>,
+    pub b: Option<F>,
+    // the number of terms, a and b included
+    pub iter_num: usize,
+}
+
+impl<F> Default for MyCircuit<F> {
+    fn default() -> Self {
+        Self {
+            a: None,
+            b: None,
+            iter_num: 6,
+        }
+    }
+}
+
+impl<F: FieldExt> MyCircuit<F> {
+    // a and b from plain integers, iter_num keeps its default
+    pub fn from_u64(a: u64, b: u64) -> Self {
+        Self {
+            a: Some(F::from(a)),
+            b: Some(F::from(b)),
+            ..Self::default()
+        }
+    }
+}
+
+// builds a MyCircuit field by field, anything not set keeps its default
+pub struct MyCircuitBuilder<F> {
+    circuit: MyCircuit<F>,
+}
+
+impl<F> MyCircuit<F> {
+    pub fn builder() -> MyCircuitBuilder<F> {
+        MyCircuitBuilder {
+            circuit: Self::default(),
+        }
+    }
+}
+
+impl<F> MyCircuitBuilder<F> {
+    pub fn a(mut self, a: F) -> Self {
+        self.circuit.a = Some(a);
+        self
+    }
+
+    pub fn b(mut self, b: F) -> Self {
+        self.circuit.b = Some(b);
+        self
+    }
+
+    pub fn iter_num(mut self, iter_num: usize) -> Self {
+        self.circuit.iter_num = iter_num;
+        self
+    }
+
+    pub fn build(self) -> Result<MyCircuit<F>, CircuitError> {
+        check_iter_num(self.circuit.iter_num)?;
+        Ok(self.circuit)
+    }
+}
+
+impl<F: FieldExt> Circuit<F> for MyCircuit<F> {
+    type Config = SquaredConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            iter_num: self.iter_num,
+            ..Self::default()
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let col_a = meta.advice_column();
+        let col_b = meta.advice_column();
+        let col_c = meta.advice_column();
+        let instance = meta.instance_column();
+
+        SquaredChip::configure(meta, [col_a, col_b, col_c], instance)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let chip = SquaredChip::construct(config);
+
+        let out = chip.assign(
+            layouter.namespace(|| "squared fibonacci"),
+            self.a,
+            self.b,
+            self.iter_num,
+        )?;
+        chip.expose_public(layouter.namespace(|| "out"), &out, 0)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MyCircuit;
+    use crate::fibonacci::example1;
+    use halo2_proofs::{dev::MockProver, pasta::Fp, plonk::*};
+
+    #[test]
+    fn test_squared() {
+        let k = 4;
+
+        // 1, 2, 5, 29, 866, 750797
+        let circuit = MyCircuit {
+            a: Some(Fp::from(1)),
+            b: Some(Fp::from(2)),
+            iter_num: 6,
+        };
+
+        let public_input = vec![Fp::from(750797)];
+        let prover = MockProver::run(k, &circuit, vec![public_input]).unwrap();
+        prover.assert_satisfied();
+    }
+
+    #[test]
+    fn test_squared_failed() {
+        let k = 4;
+
+        // 866 = 5^2 + 29^2, but the linear sum would be 34
+        let circuit = MyCircuit {
+            a: Some(Fp::from(1)),
+            b: Some(Fp::from(2)),
+            iter_num: 5,
+        };
+
+        let public_input = vec![Fp::from(34)];
+        let prover = MockProver::run(k, &circuit, vec![public_input]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn test_squared_degree() {
+        let mut linear = ConstraintSystem::<Fp>::default();
+        example1::MyCircuit::<Fp, 10>::configure(&mut linear);
+        let mut squared = ConstraintSystem::<Fp>::default();
+        MyCircuit::<Fp>::configure(&mut squared);
+
+        // the gates are degree 2 and 3, but the permutation argument already
+        // needs degree 3, so it decides the degree of both circuits
+        assert_eq!(linear.degree(), 3);
+        assert_eq!(squared.degree(), 3);
+    }
+
+    #[cfg(feature = "dev-graph")]
+    #[test]
+    fn plot_squared() {
+        use plotters::prelude::*;
+
+        let root = BitMapBackend::new("fib-squared-layout.png", (1024, 3096)).into_drawing_area();
+        root.fill(&WHITE).unwrap();
+        let root = root
+            .titled("Squared Fib Layout", ("sans-serif", 60))
+            .unwrap();
+
+        let circuit = MyCircuit {
+            a: Some(Fp::from(1)),
+            b: Some(Fp::from(2)),
+            iter_num: 6,
+        };
+        halo2_proofs::dev::CircuitLayout::default()
+            .render(4, &circuit, &root)
+            .unwrap();
+    }
+}
@@ -0,0 +1,156 @@
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::example2::{FiboChip, FiboConfig};
+use super::FiboInstructions;
+
+//
+// region             | selector |  col  | instance
+// -------------------+----------+-------+-----------
+// fibonacci region   |    1     |  a0   |  out1
+//   (first chip)     |   ...    |  ...  |  out2
+//                    |          |  out1 |
+// fibonacci region   |    1     |  c0   |
+//   (second chip)    |   ...    |  ...  |
+//                    |          |  out2 |
+//
+// Two independent sequences, (a, b) and (c, d), computed by two FiboChips
+// built from the same FiboConfig: configure runs once, so both chips share
+// the advice column, the selector, the gate and the instance column. A chip
+// is only a view of its config, any number of them can be constructed.
+//
+// The two computations don't collide because each one is assigned in its own
+// region. Rows inside a region are offsets from its start (both chips write
+// their first term at offset 0), and the floor planner decides where a region
+// starts: regions sharing a column are placed one after the other, so the
+// second table starts at row iter_num. The outputs don't collide either: the
+// caller gives each chip its own row of the instance column.
+
+pub struct MyCircuit<F> {
+    // the initial terms of the first sequence
+    pub a: Option<F>,
+    pub b: Option<F>,
+    // the initial terms of the second sequence
+    pub c: Option<F>,
+    pub d: Option<F>,
+    // the number of terms of each sequence
+    pub iter_num: usize,
+}
+
+impl<F> Default for MyCircuit<F> {
+    fn default() -> Self {
+        Self {
+            a: None,
+            b: None,
+            c: None,
+            d: None,
+            iter_num: 10,
+        }
+    }
+}
+
+impl<F: FieldExt> MyCircuit<F> {
+    // the four initial terms from plain integers, iter_num keeps its default
+    pub fn from_u64(a: u64, b: u64, c: u64, d: u64) -> Self {
+        Self {
+            a: Some(F::from(a)),
+            b: Some(F::from(b)),
+            c: Some(F::from(c)),
+            d: Some(F::from(d)),
+            ..Self::default()
+        }
+    }
+}
+
+impl<F: FieldExt> Circuit<F> for MyCircuit<F> {
+    type Config = FiboConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            iter_num: self.iter_num,
+            ..Self::default()
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        super::example2::MyCircuit::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let first = FiboChip::construct(config.clone());
+        let second = FiboChip::construct(config);
+
+        let out1 = first.assign(
+            layouter.namespace(|| "first sequence"),
+            self.a,
+            self.b,
+            self.iter_num,
+        )?;
+        let out2 = second.assign(
+            layouter.namespace(|| "second sequence"),
+            self.c,
+            self.d,
+            self.iter_num,
+        )?;
+
+        first.expose_public(layouter.namespace(|| "out1"), &out1, 0)?;
+        second.expose_public(layouter.namespace(|| "out2"), &out2, 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MyCircuit;
+    use crate::testing::{assert_unsatisfied, dump_regions, nth_fibonacci};
+    use halo2_proofs::{dev::MockProver, pasta::Fp};
+
+    // the two tables of 10 rows, one after the other
+    const K: u32 = 5;
+
+    #[test]
+    fn test_two_sequences() {
+        let circuit = MyCircuit::from_u64(1, 2, 3, 5);
+
+        let public_input = vec![
+            Fp::from(nth_fibonacci(1, 2, 10)),
+            Fp::from(nth_fibonacci(3, 5, 10)),
+        ];
+        let prover = MockProver::run(K, &circuit, vec![public_input]).unwrap();
+        prover.assert_satisfied();
+    }
+
+    #[test]
+    fn test_two_sequences_failed() {
+        let circuit = MyCircuit::from_u64(1, 2, 3, 5);
+
+        // the outputs swapped
+        let public_input = vec![
+            Fp::from(nth_fibonacci(3, 5, 10)),
+            Fp::from(nth_fibonacci(1, 2, 10)),
+        ];
+        assert_unsatisfied(K, &circuit, vec![public_input]);
+
+        // the first output right, the second one off by one
+        let public_input = vec![
+            Fp::from(nth_fibonacci(1, 2, 10)),
+            Fp::from(nth_fibonacci(3, 5, 10) + 1),
+        ];
+        assert_unsatisfied(K, &circuit, vec![public_input]);
+    }
+
+    #[test]
+    fn test_two_sequences_regions() {
+        let regions = dump_regions(K, &MyCircuit::<Fp>::default());
+        assert_eq!(
+            regions,
+            vec![
+                ("fibonacci region".to_string(), 0..10),
+                ("fibonacci region".to_string(), 10..20),
+            ]
+        );
+    }
+}
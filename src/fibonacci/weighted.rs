@@ -0,0 +1,253 @@
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+use super::ColumnSummary;
+use crate::error::CircuitError;
+
+//
+// selector | coeff (fixed) |         col          |
+// ---------+---------------+----------------------|
+//   s0     |      k0       |  a0                  |
+//   s1     |      k1       |  a1                  |
+//          |               |  a2 = k0 * a0 + a1   |
+//          |               |  a3 = k1 * a1 + a2   |
+//
+// example2 with a weight on the older term, the weights coming from a fixed
+// column loaded with one coefficient per step:
+//   s * (coeff * a + b - c) = 0
+// All ones is the Fibonacci sequence, all twos is c = 2a + b, and so on.
+//
+// A selector is a fixed column too, but one that only holds 0 or 1 and
+// multiplies the whole constraint to switch it on or off (which is what lets
+// halo2 combine simple selectors). The coefficient takes any field value and is
+// part of the arithmetic of the constraint itself. Both are fixed at keygen, so
+// changing a coefficient changes the verifying key, not just the proof.
+
+#[derive(Debug, Clone)]
+pub struct WeightedConfig {
+    pub advice: Column<Advice>,
+    pub coeff: Column<Fixed>,
+    pub instance: Column<Instance>,
+    pub selector: Selector,
+}
+
+impl WeightedConfig {
+    pub fn column_summary(&self) -> ColumnSummary {
+        ColumnSummary::of(
+            &[self.advice],
+            &[self.coeff],
+            &[self.instance],
+            &[self.selector],
+        )
+    }
+}
+
+pub struct WeightedChip<F: FieldExt> {
+    config: WeightedConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> WeightedChip<F> {
+    pub fn construct(config: WeightedConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: Column<Advice>,
+        coeff: Column<Fixed>,
+        instance: Column<Instance>,
+    ) -> WeightedConfig {
+        let selector = meta.selector();
+
+        meta.enable_equality(advice);
+        meta.enable_equality(instance);
+
+        meta.create_gate("weighted add", |meta| {
+            let s = meta.query_selector(selector);
+            let coeff = meta.query_fixed(coeff, Rotation::cur());
+            let a = meta.query_advice(advice, Rotation::cur());
+            let b = meta.query_advice(advice, Rotation::next());
+            let c = meta.query_advice(advice, Rotation(2));
+            vec![s * (coeff * a + b - c)]
+        });
+
+        WeightedConfig {
+            advice,
+            coeff,
+            instance,
+            selector,
+        }
+    }
+
+    // one step per coefficient, returns the cell holding the last term
+    pub fn assign(
+        &self,
+        mut layouter: impl Layouter<F>,
+        init_a: Option<F>,
+        init_b: Option<F>,
+        coeffs: &[F],
+    ) -> Result<AssignedCell<F, F>, Error> {
+        layouter.assign_region(
+            || "weighted region",
+            |mut region| {
+                let mut a = init_a;
+                region.assign_advice(
+                    || "a",
+                    self.config.advice,
+                    0,
+                    || a.ok_or(CircuitError::MissingWitness.into()),
+                )?;
+                let mut b_cell = region.assign_advice(
+                    || "b",
+                    self.config.advice,
+                    1,
+                    || init_b.ok_or(CircuitError::MissingWitness.into()),
+                )?;
+
+                for (row, coeff) in coeffs.iter().enumerate() {
+                    self.config.selector.enable(&mut region, row)?;
+                    region.assign_fixed(|| "coeff", self.config.coeff, row, || Ok(*coeff))?;
+
+                    let b = b_cell.value().copied();
+                    b_cell = region.assign_advice(
+                        || "advice",
+                        self.config.advice,
+                        row + 2,
+                        || {
+                            a.zip(b)
+                                .map(|(a, b)| *coeff * a + b)
+                                .ok_or(CircuitError::MissingWitness.into())
+                        },
+                    )?;
+                    a = b;
+                }
+                Ok(b_cell)
+            },
+        )
+    }
+
+    pub fn expose_public(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cell: &AssignedCell<F, F>,
+        row: usize,
+    ) -> Result<(), Error> {
+        layouter.constrain_instance(cell.cell(), self.config.instance, row)
+    }
+}
+
+#[derive(Default)]
+pub struct MyCircuit<F> {
+    pub a: Option<F>,
+    pub b: Option<F>,
+    // one per step, so the output is the (coeffs.len() + 2)-th term
+    pub coeffs: Vec<F>,
+}
+
+impl<F: FieldExt> MyCircuit<F> {
+    // the coeffs have no default, they set the number of steps
+    pub fn from_u64(a: u64, b: u64, coeffs: &[u64]) -> Self {
+        Self {
+            a: Some(F::from(a)),
+            b: Some(F::from(b)),
+            coeffs: coeffs.iter().map(|&coeff| F::from(coeff)).collect(),
+        }
+    }
+}
+
+impl<F: FieldExt> Circuit<F> for MyCircuit<F> {
+    type Config = WeightedConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        // the coefficients are part of the circuit, not witnesses
+        Self {
+            a: None,
+            b: None,
+            coeffs: self.coeffs.clone(),
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let advice = meta.advice_column();
+        let coeff = meta.fixed_column();
+        let instance = meta.instance_column();
+        WeightedChip::configure(meta, advice, coeff, instance)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let chip = WeightedChip::construct(config);
+        let out = chip.assign(
+            layouter.namespace(|| "weighted table"),
+            self.a,
+            self.b,
+            &self.coeffs,
+        )?;
+        chip.expose_public(layouter.namespace(|| "out"), &out, 0)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MyCircuit;
+    use crate::testing::nth_fibonacci;
+    use halo2_proofs::{dev::MockProver, pasta::Fp};
+
+    fn circuit(coeffs: &[u64]) -> MyCircuit<Fp> {
+        MyCircuit {
+            a: Some(Fp::from(1)),
+            b: Some(Fp::from(1)),
+            coeffs: coeffs.iter().map(|c| Fp::from(*c)).collect(),
+        }
+    }
+
+    #[test]
+    fn test_weighted() {
+        let k = 4;
+
+        // c = 2a + b: 1, 1, 3, 5, 11, 21, 43, 85, 171, 341
+        let public_input = vec![Fp::from(341)];
+        let prover = MockProver::run(k, &circuit(&[2; 8]), vec![public_input]).unwrap();
+        prover.assert_satisfied();
+    }
+
+    #[test]
+    fn test_weighted_fibonacci() {
+        let k = 4;
+
+        // all ones is the plain sequence
+        let public_input = vec![Fp::from(nth_fibonacci(1, 1, 10))];
+        let prover = MockProver::run(k, &circuit(&[1; 8]), vec![public_input]).unwrap();
+        prover.assert_satisfied();
+    }
+
+    #[test]
+    fn test_weighted_mixed() {
+        let k = 4;
+
+        // 1, 1, 1 + 1, 0 * 1 + 2, 3 * 2 + 2 = 8
+        let public_input = vec![Fp::from(8)];
+        let prover = MockProver::run(k, &circuit(&[1, 0, 3]), vec![public_input]).unwrap();
+        prover.assert_satisfied();
+    }
+
+    #[test]
+    fn test_weighted_failed() {
+        let k = 4;
+
+        // the Fibonacci output, with the weights of 2
+        let public_input = vec![Fp::from(nth_fibonacci(1, 1, 10))];
+        let prover = MockProver::run(k, &circuit(&[2; 8]), vec![public_input]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}
@@ -0,0 +1,223 @@
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+use crate::error::CircuitError;
+
+//
+// selector | input | output | power (fixed) |
+// ---------+-------+--------+---------------|
+//          |       |        |      1        |
+//          |       |        |      2        |
+//          |       |        |     ...       |
+//   s_exp  |   x   | x*2^exp|    2^exp      |
+//          |       |        |     ...       |
+//          |       |        |  2^(POWERS-1) |
+//
+// The power column is loaded with 2^0..2^(POWERS-1), one per row, and the gate
+// scales the input by the constant of the row it's enabled on:
+//   s * (input * power - output) = 0
+//
+// Unlike an advice column, the fixed column is filled in by the circuit itself,
+// the same way for every prover: its values are committed at keygen and end up
+// in the verifying key, so a prover can't change them. Which power is used is
+// decided by the row the selector sits on, and selectors are fixed as well, so
+// exp is part of the circuit shape rather than a witness.
+
+// the number of powers of two in the table
+const POWERS: usize = 8;
+
+#[derive(Debug, Clone)]
+struct FixedConfig {
+    pub input: Column<Advice>,
+    pub output: Column<Advice>,
+    pub power: Column<Fixed>,
+    pub instance: Column<Instance>,
+    pub selector: Selector,
+}
+
+struct FixedChip<F: FieldExt> {
+    config: FixedConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> FixedChip<F> {
+    pub fn construct(config: FixedConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        input: Column<Advice>,
+        output: Column<Advice>,
+        power: Column<Fixed>,
+        instance: Column<Instance>,
+    ) -> FixedConfig {
+        let selector = meta.selector();
+
+        meta.enable_equality(output);
+        meta.enable_equality(instance);
+
+        meta.create_gate("scale", |meta| {
+            let s = meta.query_selector(selector);
+            let input = meta.query_advice(input, Rotation::cur());
+            let output = meta.query_advice(output, Rotation::cur());
+            let power = meta.query_fixed(power, Rotation::cur());
+            vec![s * (input * power - output)]
+        });
+
+        FixedConfig {
+            input,
+            output,
+            power,
+            instance,
+            selector,
+        }
+    }
+
+    // returns the cell holding input * 2^exp
+    pub fn assign(
+        &self,
+        mut layouter: impl Layouter<F>,
+        input: Option<F>,
+        exp: usize,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        assert!(exp < POWERS, "2^{} isn't in the table", exp);
+
+        layouter.assign_region(
+            || "scale",
+            |mut region| {
+                for row in 0..POWERS {
+                    region.assign_fixed(
+                        || format!("2^{}", row),
+                        self.config.power,
+                        row,
+                        || Ok(F::from(1 << row)),
+                    )?;
+                }
+
+                self.config.selector.enable(&mut region, exp)?;
+
+                region.assign_advice(
+                    || "input",
+                    self.config.input,
+                    exp,
+                    || input.ok_or(CircuitError::MissingWitness.into()),
+                )?;
+
+                let output = input.map(|x| x * F::from(1 << exp));
+                region.assign_advice(
+                    || "output",
+                    self.config.output,
+                    exp,
+                    || output.ok_or(CircuitError::MissingWitness.into()),
+                )
+            },
+        )
+    }
+
+    pub fn expose_public(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cell: &AssignedCell<F, F>,
+        row: usize,
+    ) -> Result<(), Error> {
+        layouter.constrain_instance(cell.cell(), self.config.instance, row)
+    }
+}
+
+#[derive(Default)]
+struct MyCircuit<F> {
+    pub input: Option<F>,
+    pub exp: usize,
+}
+
+impl<F: FieldExt> Circuit<F> for MyCircuit<F> {
+    type Config = FixedConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            input: None,
+            exp: self.exp,
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let input = meta.advice_column();
+        let output = meta.advice_column();
+        let power = meta.fixed_column();
+        let instance = meta.instance_column();
+
+        FixedChip::configure(meta, input, output, power, instance)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let chip = FixedChip::construct(config);
+
+        let out = chip.assign(layouter.namespace(|| "scale"), self.input, self.exp)?;
+        chip.expose_public(layouter.namespace(|| "out"), &out, 0)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MyCircuit;
+    use halo2_proofs::{dev::MockProver, pasta::Fp};
+
+    #[test]
+    fn test_fixed() {
+        let k = 4;
+
+        // 3 * 2^4
+        let circuit = MyCircuit {
+            input: Some(Fp::from(3)),
+            exp: 4,
+        };
+
+        let public_input = vec![Fp::from(48)];
+        let prover = MockProver::run(k, &circuit, vec![public_input]).unwrap();
+        prover.assert_satisfied();
+    }
+
+    #[test]
+    fn test_fixed_failed() {
+        let k = 4;
+
+        // 3 * 2^3, the output of the row above
+        let circuit = MyCircuit {
+            input: Some(Fp::from(3)),
+            exp: 4,
+        };
+
+        let public_input = vec![Fp::from(24)];
+        let prover = MockProver::run(k, &circuit, vec![public_input]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[cfg(feature = "dev-graph")]
+    #[test]
+    fn plot_fixed() {
+        use plotters::prelude::*;
+
+        let root = BitMapBackend::new("fixed-layout.png", (1024, 3096)).into_drawing_area();
+        root.fill(&WHITE).unwrap();
+        let root = root.titled("Fixed Layout", ("sans-serif", 60)).unwrap();
+
+        let circuit = MyCircuit {
+            input: Some(Fp::from(3)),
+            exp: 4,
+        };
+        halo2_proofs::dev::CircuitLayout::default()
+            .render(4, &circuit, &root)
+            .unwrap();
+    }
+}
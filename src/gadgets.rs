@@ -0,0 +1,6 @@
+pub(crate) mod boolean;
+pub(crate) mod divmod;
+pub(crate) mod nonzero;
+pub(crate) mod pow;
+pub(crate) mod running_sum;
+pub(crate) mod weighted_sum;
@@ -0,0 +1,130 @@
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+use crate::error::CircuitError;
+
+//
+// selector | value
+// ---------+-------
+//    1     |   x
+//
+// Constrains x to be a bit:
+//   s * x * (1 - x) = 0
+// The value column has equality enabled, so the bit can be copied into other
+// chips (e.g. as the condition of a mux).
+
+#[derive(Debug, Clone)]
+pub(crate) struct BooleanConfig {
+    pub value: Column<Advice>,
+    pub selector: Selector,
+}
+
+pub(crate) struct BooleanChip<F: FieldExt> {
+    config: BooleanConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> BooleanChip<F> {
+    pub fn construct(config: BooleanConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(meta: &mut ConstraintSystem<F>, value: Column<Advice>) -> BooleanConfig {
+        let selector = meta.selector();
+
+        meta.enable_equality(value);
+
+        meta.create_gate("bool", |meta| {
+            let s = meta.query_selector(selector);
+            let x = meta.query_advice(value, Rotation::cur());
+            let one = Expression::Constant(F::one());
+            vec![s * x.clone() * (one - x)]
+        });
+
+        BooleanConfig { value, selector }
+    }
+
+    pub fn assign_bit(
+        &self,
+        mut layouter: impl Layouter<F>,
+        value: Option<F>,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        layouter.assign_region(
+            || "bit",
+            |mut region| {
+                self.config.selector.enable(&mut region, 0)?;
+                region.assign_advice(
+                    || "bit",
+                    self.config.value,
+                    0,
+                    || value.ok_or(CircuitError::MissingWitness.into()),
+                )
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BooleanChip, BooleanConfig};
+    use crate::testing::assert_fails_with;
+    use halo2_proofs::{circuit::*, dev::MockProver, pasta::Fp, plonk::*};
+
+    #[derive(Default)]
+    struct TestCircuit {
+        value: Option<Fp>,
+    }
+
+    impl Circuit<Fp> for TestCircuit {
+        type Config = BooleanConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self::default()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            let value = meta.advice_column();
+            BooleanChip::configure(meta, value)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = BooleanChip::construct(config);
+            chip.assign_bit(layouter.namespace(|| "bit"), self.value)?;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_boolean() {
+        let k = 4;
+
+        for bit in [0, 1] {
+            let circuit = TestCircuit {
+                value: Some(Fp::from(bit)),
+            };
+
+            let prover = MockProver::run(k, &circuit, vec![]).unwrap();
+            prover.assert_satisfied();
+        }
+    }
+
+    #[test]
+    fn test_boolean_failed() {
+        let k = 4;
+
+        let circuit = TestCircuit {
+            value: Some(Fp::from(2)),
+        };
+
+        let prover = MockProver::run(k, &circuit, vec![]).unwrap();
+        assert_fails_with(&prover, "bool");
+    }
+}
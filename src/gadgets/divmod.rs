@@ -0,0 +1,375 @@
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+use crate::error::CircuitError;
+use crate::range_check::lookup::{LookupRangeCheckChip, LookupRangeCheckConfig};
+
+//
+// selector |  a  |  d  |  q  |  r  |    slack
+// ---------+-----+-----+-----+-----+-------------
+//    1     |  a  |  d  |  q  |  r  | d - 1 - r
+//
+// Divides a by d, witnessing the quotient q and remainder r:
+//   s * (a - q * d - r) = 0
+//   s * (d - 1 - r - slack) = 0
+// q, r and slack are range checked to [0, RANGE) with the lookup range check.
+// slack >= 0 is what makes r < d, and bounding q keeps q * d + r from wrapping
+// around the modulus, otherwise any r would do with q = (a - r) / d.
+//
+// d is an advice cell, so `divide` can copy it from a witness, e.g. the gcd
+// example divides by the previous remainder. `assign` takes a fixed divisor
+// and loads it from the constant column instead, which keeps it part of the
+// verifying key, and rejects a divisor outside of (0, RANGE] with
+// DivisorOutOfRange. Either way d has to be in that range, and a below RANGE * d.
+// `divide` doesn't range check d itself, but d = 0 can't pass: slack would be
+// -1 - r, which isn't in the table.
+
+type CellPair<F> = (AssignedCell<F, F>, AssignedCell<F, F>);
+
+#[derive(Debug, Clone)]
+pub(crate) struct DivModConfig<F: FieldExt, const RANGE: usize, const NUM_BITS: usize> {
+    pub a: Column<Advice>,
+    pub d: Column<Advice>,
+    pub q: Column<Advice>,
+    pub r: Column<Advice>,
+    pub slack: Column<Advice>,
+    pub selector: Selector,
+    pub range: LookupRangeCheckConfig<F, RANGE, NUM_BITS>,
+}
+
+pub(crate) struct DivModChip<F: FieldExt, const RANGE: usize, const NUM_BITS: usize> {
+    config: DivModConfig<F, RANGE, NUM_BITS>,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt, const RANGE: usize, const NUM_BITS: usize> DivModChip<F, RANGE, NUM_BITS> {
+    pub fn construct(config: DivModConfig<F, RANGE, NUM_BITS>) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 5],
+        constant: Column<Fixed>,
+        value: Column<Advice>,
+    ) -> DivModConfig<F, RANGE, NUM_BITS> {
+        let [a, d, q, r, slack] = advice;
+        let selector = meta.selector();
+
+        for column in advice {
+            meta.enable_equality(column);
+        }
+        // for the fixed divisors of `assign`
+        meta.enable_constant(constant);
+
+        meta.create_gate("divmod", |meta| {
+            let s = meta.query_selector(selector);
+            let a = meta.query_advice(a, Rotation::cur());
+            let q = meta.query_advice(q, Rotation::cur());
+            let r = meta.query_advice(r, Rotation::cur());
+            let slack = meta.query_advice(slack, Rotation::cur());
+            let d = meta.query_advice(d, Rotation::cur());
+            let one = Expression::Constant(F::one());
+            vec![
+                s.clone() * (a - q * d.clone() - r.clone()),
+                s * (d - one - r - slack),
+            ]
+        });
+
+        DivModConfig {
+            a,
+            d,
+            q,
+            r,
+            slack,
+            selector,
+            range: LookupRangeCheckChip::configure(meta, value),
+        }
+    }
+
+    // returns the cells holding (q, r)
+    pub fn assign(
+        &self,
+        layouter: impl Layouter<F>,
+        a: Option<F>,
+        d: u64,
+    ) -> Result<CellPair<F>, Error> {
+        if d == 0 || d > RANGE as u64 {
+            return Err(CircuitError::DivisorOutOfRange { d, max: RANGE }.into());
+        }
+
+        self.assign_row(layouter, |region| {
+            let a = region.assign_advice(
+                || "a",
+                self.config.a,
+                0,
+                || a.ok_or(CircuitError::MissingWitness.into()),
+            )?;
+            let d = region.assign_advice_from_constant(|| "d", self.config.d, 0, F::from(d))?;
+            Ok((a, d))
+        })
+    }
+
+    // same as `assign`, with a and d copied from cells assigned elsewhere
+    pub fn divide(
+        &self,
+        layouter: impl Layouter<F>,
+        a: &AssignedCell<F, F>,
+        d: &AssignedCell<F, F>,
+    ) -> Result<CellPair<F>, Error> {
+        self.assign_row(layouter, |region| {
+            let a = a.copy_advice(|| "a", region, self.config.a, 0)?;
+            let d = d.copy_advice(|| "d", region, self.config.d, 0)?;
+            Ok((a, d))
+        })
+    }
+
+    // lays out the row, with a and d assigned by `load_ad`, and range checks
+    // q, r and slack
+    fn assign_row(
+        &self,
+        mut layouter: impl Layouter<F>,
+        load_ad: impl Fn(&mut Region<'_, F>) -> Result<CellPair<F>, Error>,
+    ) -> Result<CellPair<F>, Error> {
+        let (q_cell, r_cell, slack_cell) = layouter.assign_region(
+            || "divmod",
+            |mut region| {
+                self.config.selector.enable(&mut region, 0)?;
+
+                let (a, d) = load_ad(&mut region)?;
+
+                // a is below RANGE * d, so the lower 128 bits are the whole
+                // value. There's nothing to divide by 0, leave it all in r and
+                // let the range check on slack reject it
+                let qr = a.value().zip(d.value()).map(|(a, d)| {
+                    let (a, d) = (a.get_lower_128(), d.get_lower_128());
+                    a.checked_div(d).zip(a.checked_rem(d)).unwrap_or((0, a))
+                });
+                let q = qr.map(|(q, _)| F::from_u128(q));
+                let r = qr.map(|(_, r)| F::from_u128(r));
+                let slack = d.value().zip(r).map(|(d, r)| *d - F::one() - r);
+
+                let q_cell = region.assign_advice(
+                    || "q",
+                    self.config.q,
+                    0,
+                    || q.ok_or(CircuitError::MissingWitness.into()),
+                )?;
+                let r_cell = region.assign_advice(
+                    || "r",
+                    self.config.r,
+                    0,
+                    || r.ok_or(CircuitError::MissingWitness.into()),
+                )?;
+                let slack_cell = region.assign_advice(
+                    || "slack",
+                    self.config.slack,
+                    0,
+                    || slack.ok_or(CircuitError::MissingWitness.into()),
+                )?;
+
+                Ok((q_cell, r_cell, slack_cell))
+            },
+        )?;
+
+        let range_chip =
+            LookupRangeCheckChip::<F, RANGE, NUM_BITS>::construct(self.config.range.clone());
+        range_chip.check(layouter.namespace(|| "q"), &q_cell)?;
+        range_chip.check(layouter.namespace(|| "r"), &r_cell)?;
+        range_chip.check(layouter.namespace(|| "slack"), &slack_cell)?;
+
+        Ok((q_cell, r_cell))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DivModChip, DivModConfig};
+    use crate::error::CircuitError;
+    use halo2_proofs::{circuit::*, dev::MockProver, pasta::Fp, plonk::*};
+
+    const RANGE: usize = 256;
+    const NUM_BITS: usize = 8;
+
+    #[derive(Default)]
+    struct TestCircuit {
+        a: Option<Fp>,
+        d: u64,
+    }
+
+    impl Circuit<Fp> for TestCircuit {
+        type Config = (DivModConfig<Fp, RANGE, NUM_BITS>, Column<Instance>);
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self { a: None, d: self.d }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let constant = meta.fixed_column();
+            let value = meta.advice_column();
+            let instance = meta.instance_column();
+            meta.enable_equality(instance);
+
+            (
+                DivModChip::configure(meta, advice, constant, value),
+                instance,
+            )
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let (config, instance) = config;
+            config.range.table.load(&mut layouter)?;
+
+            let chip = DivModChip::construct(config);
+            let (q, r) = chip.assign(layouter.namespace(|| "divmod"), self.a, self.d)?;
+            layouter.constrain_instance(q.cell(), instance, 0)?;
+            layouter.constrain_instance(r.cell(), instance, 1)
+        }
+    }
+
+    // divides by a witness instead of a fixed divisor
+    #[derive(Default)]
+    struct DivideCircuit {
+        a: Option<Fp>,
+        d: Option<Fp>,
+    }
+
+    impl Circuit<Fp> for DivideCircuit {
+        type Config = (DivModConfig<Fp, RANGE, NUM_BITS>, Column<Instance>);
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self::default()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            TestCircuit::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let (config, instance) = config;
+            config.range.table.load(&mut layouter)?;
+
+            let (a, d) = layouter.assign_region(
+                || "load a and d",
+                |mut region| {
+                    let a = region.assign_advice(
+                        || "a",
+                        config.a,
+                        0,
+                        || self.a.ok_or(CircuitError::MissingWitness.into()),
+                    )?;
+                    let d = region.assign_advice(
+                        || "d",
+                        config.d,
+                        0,
+                        || self.d.ok_or(CircuitError::MissingWitness.into()),
+                    )?;
+                    Ok((a, d))
+                },
+            )?;
+
+            let chip = DivModChip::construct(config);
+            let (q, r) = chip.divide(layouter.namespace(|| "divmod"), &a, &d)?;
+            layouter.constrain_instance(q.cell(), instance, 0)?;
+            layouter.constrain_instance(r.cell(), instance, 1)
+        }
+    }
+
+    #[test]
+    fn test_divmod() {
+        // the range table takes 256 rows
+        let k = 9;
+
+        // 17 = 3 * 5 + 2, and the exact division 20 = 4 * 5
+        for (a, q, r) in [(17, 3, 2), (20, 4, 0)] {
+            let circuit = TestCircuit {
+                a: Some(Fp::from(a)),
+                d: 5,
+            };
+
+            let public_input = vec![Fp::from(q), Fp::from(r)];
+            let prover = MockProver::run(k, &circuit, vec![public_input]).unwrap();
+            prover.assert_satisfied();
+        }
+    }
+
+    #[test]
+    fn test_divmod_failed() {
+        let k = 9;
+
+        let circuit = TestCircuit {
+            a: Some(Fp::from(17)),
+            d: 5,
+        };
+
+        // 17 = 2 * 5 + 7 holds, but the remainder isn't below 5
+        let public_input = vec![Fp::from(2), Fp::from(7)];
+        let prover = MockProver::run(k, &circuit, vec![public_input]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn test_divmod_divisor_out_of_range() {
+        let k = 9;
+
+        // both rejected before anything is laid out
+        for d in [0, RANGE as u64 + 1] {
+            let circuit = TestCircuit {
+                a: Some(Fp::from(17)),
+                d,
+            };
+            let public_input = vec![Fp::zero(), Fp::zero()];
+            assert!(MockProver::run(k, &circuit, vec![public_input]).is_err());
+        }
+    }
+
+    #[test]
+    fn test_divmod_divide() {
+        let k = 9;
+
+        // 200 = 13 * 15 + 5, with the divisor a witness
+        let circuit = DivideCircuit {
+            a: Some(Fp::from(200)),
+            d: Some(Fp::from(15)),
+        };
+
+        let public_input = vec![Fp::from(13), Fp::from(5)];
+        let prover = MockProver::run(k, &circuit, vec![public_input]).unwrap();
+        prover.assert_satisfied();
+    }
+
+    #[test]
+    fn test_divmod_divide_by_zero() {
+        let k = 9;
+
+        // 17 = q * 0 + 17 for any q, but slack = 0 - 1 - 17 is out of range
+        let circuit = DivideCircuit {
+            a: Some(Fp::from(17)),
+            d: Some(Fp::zero()),
+        };
+
+        let public_input = vec![Fp::zero(), Fp::from(17)];
+        let prover = MockProver::run(k, &circuit, vec![public_input]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}
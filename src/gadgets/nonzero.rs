@@ -0,0 +1,198 @@
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+use crate::error::CircuitError;
+
+//
+// selector | value | value_inv
+// ---------+-------+-----------
+//    1     |   v   |   v^-1
+//
+// Constrains v to be nonzero:
+//   s * (v * value_inv - 1) = 0
+// Zero is the one value with no inverse, so no value_inv can satisfy it.
+//
+// This is the dual of the is_zero gadget, which has the same value_inv hint
+// but only constrains v * (1 - v * value_inv) = 0: that holds for every v, and
+// 1 - v * value_inv is the answer to "is v zero?" as a cell the circuit can
+// use. Here the answer isn't computed but asserted, with one constraint and
+// no output column, so a zero value makes the circuit fail instead.
+//
+// The value column has equality enabled, so the checked value can be copied
+// into other chips, e.g. as a divisor.
+
+#[derive(Debug, Clone)]
+pub(crate) struct NonZeroConfig {
+    pub value: Column<Advice>,
+    pub value_inv: Column<Advice>,
+    pub selector: Selector,
+}
+
+pub(crate) struct NonZeroChip<F: FieldExt> {
+    config: NonZeroConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> NonZeroChip<F> {
+    pub fn construct(config: NonZeroConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        value: Column<Advice>,
+        value_inv: Column<Advice>,
+    ) -> NonZeroConfig {
+        let selector = meta.selector();
+
+        meta.enable_equality(value);
+
+        meta.create_gate("nonzero", |meta| {
+            let s = meta.query_selector(selector);
+            let v = meta.query_advice(value, Rotation::cur());
+            let v_inv = meta.query_advice(value_inv, Rotation::cur());
+            let one = Expression::Constant(F::one());
+            vec![s * (v * v_inv - one)]
+        });
+
+        NonZeroConfig {
+            value,
+            value_inv,
+            selector,
+        }
+    }
+
+    // returns the cell holding value. A zero value can't be proven, so it's
+    // rejected with ZeroValue before anything is laid out.
+    pub fn assign(
+        &self,
+        mut layouter: impl Layouter<F>,
+        value: Option<F>,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        if value == Some(F::zero()) {
+            return Err(CircuitError::ZeroValue.into());
+        }
+
+        layouter.assign_region(
+            || "nonzero",
+            |mut region| {
+                self.config.selector.enable(&mut region, 0)?;
+
+                let value_cell = region.assign_advice(
+                    || "value",
+                    self.config.value,
+                    0,
+                    || value.ok_or(CircuitError::MissingWitness.into()),
+                )?;
+                region.assign_advice(
+                    || "value_inv",
+                    self.config.value_inv,
+                    0,
+                    || {
+                        value
+                            .map(|v| v.invert().unwrap())
+                            .ok_or(CircuitError::MissingWitness.into())
+                    },
+                )?;
+                Ok(value_cell)
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{NonZeroChip, NonZeroConfig};
+    use crate::testing::assert_fails_with;
+    use halo2_proofs::{circuit::*, dev::MockProver, pasta::Fp, plonk::*};
+
+    #[derive(Default)]
+    struct TestCircuit {
+        value: Option<Fp>,
+    }
+
+    impl Circuit<Fp> for TestCircuit {
+        type Config = NonZeroConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self::default()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            let value = meta.advice_column();
+            let value_inv = meta.advice_column();
+            NonZeroChip::configure(meta, value, value_inv)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = NonZeroChip::construct(config);
+            chip.assign(layouter.namespace(|| "nonzero"), self.value)?;
+            Ok(())
+        }
+    }
+
+    // a prover claiming 0 * 1 = 1, bypassing the check in assign
+    struct ZeroCircuit;
+
+    impl Circuit<Fp> for ZeroCircuit {
+        type Config = NonZeroConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            TestCircuit::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            layouter.assign_region(
+                || "zero",
+                |mut region| {
+                    config.selector.enable(&mut region, 0)?;
+                    region.assign_advice(|| "value", config.value, 0, || Ok(Fp::zero()))?;
+                    region.assign_advice(|| "value_inv", config.value_inv, 0, || Ok(Fp::one()))?;
+                    Ok(())
+                },
+            )
+        }
+    }
+
+    #[test]
+    fn test_nonzero() {
+        for value in [Fp::one(), Fp::from(5), -Fp::one()] {
+            let circuit = TestCircuit { value: Some(value) };
+            let prover = MockProver::run(4, &circuit, vec![]).unwrap();
+            prover.assert_satisfied();
+        }
+    }
+
+    #[test]
+    fn test_nonzero_zero() {
+        // assign refuses it
+        let circuit = TestCircuit {
+            value: Some(Fp::zero()),
+        };
+        assert!(matches!(
+            MockProver::run(4, &circuit, vec![]),
+            Err(Error::Synthesis)
+        ));
+
+        // and no inverse gets past the gate
+        let prover = MockProver::run(4, &ZeroCircuit, vec![]).unwrap();
+        assert_fails_with(&prover, "nonzero");
+    }
+}
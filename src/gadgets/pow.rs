@@ -0,0 +1,195 @@
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+use std::marker::PhantomData;
+
+use crate::arithmetic::gate::{ArithmeticChip, ArithmeticConfig};
+use crate::conditional_select::example::{CondSelectChip, CondSelectConfig};
+use crate::gadgets::boolean::{BooleanChip, BooleanConfig};
+
+//
+// base^exp by square and multiply, with exp given as bits, most significant first:
+//
+//   acc = 1
+//   for bit in exp_bits:
+//       acc = acc * acc                      (square)
+//       acc = bit ? acc * base : acc         (multiply)
+//
+// e.g. 3^5, exp = 101:  1 -> 1 * 3 = 3 -> 9 -> 81 * 3 = 243
+// Every step squares, so the result after the step is base^(the bits seen so
+// far), and a bit multiplies in one more base. That's 2 * bits multiplications
+// whatever exp is, instead of exp of them.
+//
+// No new gate: each step is two rows of the arithmetic chip (square, times
+// base) and one of the conditional select chip, with the bit checked by the
+// boolean chip before it's copied in as the cond. Both branches are computed,
+// the circuit can't skip the multiplication when the bit is 0.
+//
+// The chips share their advice columns, none of their regions overlap.
+
+#[derive(Debug, Clone)]
+pub(crate) struct PowConfig {
+    pub arithmetic: ArithmeticConfig,
+    pub select: CondSelectConfig,
+    pub boolean: BooleanConfig,
+}
+
+pub(crate) struct PowChip<F: FieldExt> {
+    config: PowConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> PowChip<F> {
+    pub fn construct(config: PowConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    // the instance column is only there because the reused chips expose their
+    // results to it
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 4],
+        constant: Column<Fixed>,
+        instance: Column<Instance>,
+    ) -> PowConfig {
+        let [col_0, col_1, col_2, _] = advice;
+
+        // for the initial 1
+        meta.enable_constant(constant);
+
+        PowConfig {
+            arithmetic: ArithmeticChip::configure(meta, [col_0, col_1, col_2], instance),
+            select: CondSelectChip::configure(meta, advice, instance),
+            boolean: BooleanChip::configure(meta, col_0),
+        }
+    }
+
+    // returns the cell holding base^exp, `exp_bits` being the bits of exp from
+    // the most significant one. The number of bits is the fixed width of exp,
+    // leading zeros included.
+    pub fn assign(
+        &self,
+        mut layouter: impl Layouter<F>,
+        base: Option<F>,
+        exp_bits: &[Option<F>],
+    ) -> Result<AssignedCell<F, F>, Error> {
+        let arithmetic = ArithmeticChip::construct(self.config.arithmetic.clone());
+        let select = CondSelectChip::construct(self.config.select.clone());
+        let boolean = BooleanChip::construct(self.config.boolean.clone());
+
+        let base = arithmetic.load_private(layouter.namespace(|| "load base"), base)?;
+        let mut acc = layouter.assign_region(
+            || "load one",
+            |mut region| {
+                region.assign_advice_from_constant(
+                    || "one",
+                    self.config.arithmetic.advice[0],
+                    0,
+                    F::one(),
+                )
+            },
+        )?;
+
+        for (i, bit) in exp_bits.iter().enumerate() {
+            let bit = boolean.assign_bit(layouter.namespace(|| format!("bit {}", i)), *bit)?;
+
+            let squared = arithmetic.mul(layouter.namespace(|| "square"), &acc, &acc)?;
+            let multiplied = arithmetic.mul(layouter.namespace(|| "multiply"), &squared, &base)?;
+            acc = select.select(layouter.namespace(|| "select"), &bit, &multiplied, &squared)?;
+        }
+        Ok(acc)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PowChip, PowConfig};
+    use crate::testing::assert_fails_with;
+    use halo2_proofs::{circuit::*, dev::MockProver, pasta::Fp, plonk::*};
+
+    #[derive(Debug, Clone)]
+    struct TestConfig {
+        pow: PowConfig,
+        instance: Column<Instance>,
+    }
+
+    #[derive(Default)]
+    struct TestCircuit {
+        base: Option<Fp>,
+        exp_bits: Vec<Option<Fp>>,
+    }
+
+    impl Circuit<Fp> for TestCircuit {
+        type Config = TestConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self {
+                base: None,
+                exp_bits: vec![None; self.exp_bits.len()],
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let constant = meta.fixed_column();
+            let instance = meta.instance_column();
+
+            TestConfig {
+                pow: PowChip::configure(meta, advice, constant, instance),
+                instance,
+            }
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = PowChip::construct(config.pow);
+            let out = chip.assign(layouter.namespace(|| "pow"), self.base, &self.exp_bits)?;
+            layouter.constrain_instance(out.cell(), config.instance, 0)
+        }
+    }
+
+    // exp as 4 bits, most significant first
+    fn run(base: u64, exp: u64, out: u64) -> MockProver<Fp> {
+        let circuit = TestCircuit {
+            base: Some(Fp::from(base)),
+            exp_bits: (0..4)
+                .rev()
+                .map(|i| Some(Fp::from((exp >> i) & 1)))
+                .collect(),
+        };
+        MockProver::run(5, &circuit, vec![vec![Fp::from(out)]]).unwrap()
+    }
+
+    #[test]
+    fn test_pow() {
+        run(3, 5, 243).assert_satisfied();
+        run(2, 0, 1).assert_satisfied();
+        run(2, 15, 1 << 15).assert_satisfied();
+    }
+
+    #[test]
+    fn test_pow_failed() {
+        assert!(run(3, 5, 244).verify().is_err());
+    }
+
+    #[test]
+    fn test_pow_bit_not_boolean() {
+        // exp = 2 * 2 + 1 = 5 in the field, but with a 2 as the high bit
+        let circuit = TestCircuit {
+            base: Some(Fp::from(3)),
+            exp_bits: vec![Some(Fp::from(2)), Some(Fp::from(1))],
+        };
+        let prover = MockProver::run(5, &circuit, vec![vec![Fp::from(243)]]).unwrap();
+        assert_fails_with(&prover, "bool");
+    }
+}
@@ -0,0 +1,205 @@
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+use crate::error::CircuitError;
+
+//
+// selector |  x  |  acc
+// ---------+-----+------------
+//    0     |     |  0             (from the fixed column)
+//    1     | x0  |  0 + x0
+//    1     | x1  |  acc1 + x1
+//   ...    | ... |  ...
+//
+// A reusable running sum: each enabled row adds its x to the acc of the row above
+//   s * (acc_prev + x - acc_cur) = 0
+// The first acc is copied from a constant 0, so summing nothing gives that cell.
+// Both columns have equality enabled: the x's can be copied in from another
+// chip (accumulate_cells) and the result can be copied elsewhere.
+
+#[derive(Debug, Clone)]
+pub(crate) struct RunningSumConfig {
+    pub x: Column<Advice>,
+    pub acc: Column<Advice>,
+    pub selector: Selector,
+}
+
+pub(crate) struct RunningSumChip<F: FieldExt> {
+    config: RunningSumConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> RunningSumChip<F> {
+    pub fn construct(config: RunningSumConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        x: Column<Advice>,
+        acc: Column<Advice>,
+        constant: Column<Fixed>,
+    ) -> RunningSumConfig {
+        let selector = meta.selector();
+
+        meta.enable_equality(x);
+        meta.enable_equality(acc);
+        meta.enable_constant(constant);
+
+        meta.create_gate("running sum", |meta| {
+            let s = meta.query_selector(selector);
+            let x = meta.query_advice(x, Rotation::cur());
+            let acc_prev = meta.query_advice(acc, Rotation::prev());
+            let acc_cur = meta.query_advice(acc, Rotation::cur());
+            vec![s * (acc_prev + x - acc_cur)]
+        });
+
+        RunningSumConfig { x, acc, selector }
+    }
+
+    // returns the cell holding the sum of `values`
+    pub fn accumulate(
+        &self,
+        mut layouter: impl Layouter<F>,
+        values: &[Option<F>],
+    ) -> Result<AssignedCell<F, F>, Error> {
+        layouter.assign_region(
+            || "running sum",
+            |mut region| {
+                let mut acc_cell =
+                    region.assign_advice_from_constant(|| "acc", self.config.acc, 0, F::zero())?;
+
+                for (i, x) in values.iter().enumerate() {
+                    let row = i + 1;
+                    region.assign_advice(
+                        || "x",
+                        self.config.x,
+                        row,
+                        || x.ok_or(CircuitError::MissingWitness.into()),
+                    )?;
+                    acc_cell = self.add_row(&mut region, row, *x, &acc_cell)?;
+                }
+                Ok(acc_cell)
+            },
+        )
+    }
+
+    // same as `accumulate`, but the x's are copied from cells assigned by
+    // another chip, so the sum is bound to them
+    pub fn accumulate_cells(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cells: &[AssignedCell<F, F>],
+    ) -> Result<AssignedCell<F, F>, Error> {
+        layouter.assign_region(
+            || "running sum",
+            |mut region| {
+                let mut acc_cell =
+                    region.assign_advice_from_constant(|| "acc", self.config.acc, 0, F::zero())?;
+
+                for (i, cell) in cells.iter().enumerate() {
+                    let row = i + 1;
+                    let x_cell = cell.copy_advice(|| "x", &mut region, self.config.x, row)?;
+                    acc_cell =
+                        self.add_row(&mut region, row, x_cell.value().copied(), &acc_cell)?;
+                }
+                Ok(acc_cell)
+            },
+        )
+    }
+
+    // turns the selector on and assigns acc_prev + x, once x is in the row
+    fn add_row(
+        &self,
+        region: &mut Region<'_, F>,
+        row: usize,
+        x: Option<F>,
+        acc_prev: &AssignedCell<F, F>,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        self.config.selector.enable(region, row)?;
+
+        let acc = acc_prev.value().and_then(|acc| x.map(|x| *acc + x));
+        region.assign_advice(
+            || "acc",
+            self.config.acc,
+            row,
+            || acc.ok_or(CircuitError::MissingWitness.into()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RunningSumChip, RunningSumConfig};
+    use halo2_proofs::{circuit::*, dev::MockProver, pasta::Fp, plonk::*};
+
+    #[derive(Debug, Clone)]
+    struct TestConfig {
+        sum: RunningSumConfig,
+        instance: Column<Instance>,
+    }
+
+    #[derive(Default)]
+    struct TestCircuit {
+        values: Vec<Option<Fp>>,
+    }
+
+    impl Circuit<Fp> for TestCircuit {
+        type Config = TestConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self {
+                values: vec![None; self.values.len()],
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            let x = meta.advice_column();
+            let acc = meta.advice_column();
+            let constant = meta.fixed_column();
+            let instance = meta.instance_column();
+            meta.enable_equality(instance);
+
+            TestConfig {
+                sum: RunningSumChip::configure(meta, x, acc, constant),
+                instance,
+            }
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = RunningSumChip::construct(config.sum);
+            let sum = chip.accumulate(layouter.namespace(|| "sum"), &self.values)?;
+            layouter.constrain_instance(sum.cell(), config.instance, 0)
+        }
+    }
+
+    fn run(values: &[u64], sum: u64) -> MockProver<Fp> {
+        let circuit = TestCircuit {
+            values: values.iter().map(|v| Some(Fp::from(*v))).collect(),
+        };
+        MockProver::run(4, &circuit, vec![vec![Fp::from(sum)]]).unwrap()
+    }
+
+    #[test]
+    fn test_running_sum() {
+        run(&[3, 1, 4, 1, 5], 14).assert_satisfied();
+    }
+
+    #[test]
+    fn test_running_sum_empty() {
+        run(&[], 0).assert_satisfied();
+    }
+
+    #[test]
+    fn test_running_sum_failed() {
+        assert!(run(&[3, 1, 4, 1, 5], 15).verify().is_err());
+    }
+}
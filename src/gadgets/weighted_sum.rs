@@ -0,0 +1,211 @@
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+use crate::error::CircuitError;
+
+//
+// selector |  x  | w (fixed) |  acc
+// ---------+-----+-----------+-----------------
+//    0     |     |           |  0                (from the constant column)
+//    1     | x0  |    w0     |  0 + w0 * x0
+//    1     | x1  |    w1     |  acc1 + w1 * x1
+//   ...    | ... |    ...    |  ...
+//
+// running_sum with a weight per row: each enabled row adds w * x to the acc of
+// the row above
+//   s * (acc_prev + w * x - acc_cur) = 0
+// so the last acc is the linear combination w0 * x0 + w1 * x1 + ...
+//
+// The weights are known when the circuit is written, so they're assigned with
+// assign_fixed in the same region as the x's, next to their x. Fixed columns
+// are committed at keygen: the weights are part of the verifying key, and a
+// prover can't change them, only the x's are witnesses.
+
+#[derive(Debug, Clone)]
+pub(crate) struct WeightedSumConfig {
+    pub x: Column<Advice>,
+    pub weight: Column<Fixed>,
+    pub acc: Column<Advice>,
+    pub selector: Selector,
+}
+
+pub(crate) struct WeightedSumChip<F: FieldExt> {
+    config: WeightedSumConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> WeightedSumChip<F> {
+    pub fn construct(config: WeightedSumConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        x: Column<Advice>,
+        weight: Column<Fixed>,
+        acc: Column<Advice>,
+        constant: Column<Fixed>,
+    ) -> WeightedSumConfig {
+        let selector = meta.selector();
+
+        meta.enable_equality(x);
+        meta.enable_equality(acc);
+        meta.enable_constant(constant);
+
+        meta.create_gate("weighted sum", |meta| {
+            let s = meta.query_selector(selector);
+            let x = meta.query_advice(x, Rotation::cur());
+            let w = meta.query_fixed(weight, Rotation::cur());
+            let acc_prev = meta.query_advice(acc, Rotation::prev());
+            let acc_cur = meta.query_advice(acc, Rotation::cur());
+            vec![s * (acc_prev + w * x - acc_cur)]
+        });
+
+        WeightedSumConfig {
+            x,
+            weight,
+            acc,
+            selector,
+        }
+    }
+
+    // returns the cell holding the sum of weights[i] * values[i]; there must
+    // be one weight per value
+    pub fn assign(
+        &self,
+        mut layouter: impl Layouter<F>,
+        values: &[Option<F>],
+        weights: &[F],
+    ) -> Result<AssignedCell<F, F>, Error> {
+        if values.len() != weights.len() {
+            return Err(CircuitError::LengthMismatch {
+                expected: weights.len(),
+                actual: values.len(),
+            }
+            .into());
+        }
+
+        layouter.assign_region(
+            || "weighted sum",
+            |mut region| {
+                let mut acc_cell =
+                    region.assign_advice_from_constant(|| "acc", self.config.acc, 0, F::zero())?;
+
+                for (i, (x, w)) in values.iter().zip(weights).enumerate() {
+                    let row = i + 1;
+                    self.config.selector.enable(&mut region, row)?;
+
+                    region.assign_advice(
+                        || "x",
+                        self.config.x,
+                        row,
+                        || x.ok_or(CircuitError::MissingWitness.into()),
+                    )?;
+                    region.assign_fixed(|| "w", self.config.weight, row, || Ok(*w))?;
+
+                    let acc = acc_cell.value().zip(*x).map(|(acc, x)| *acc + *w * x);
+                    acc_cell = region.assign_advice(
+                        || "acc",
+                        self.config.acc,
+                        row,
+                        || acc.ok_or(CircuitError::MissingWitness.into()),
+                    )?;
+                }
+                Ok(acc_cell)
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{WeightedSumChip, WeightedSumConfig};
+    use halo2_proofs::{circuit::*, dev::MockProver, pasta::Fp, plonk::*};
+
+    #[derive(Debug, Clone)]
+    struct TestConfig {
+        sum: WeightedSumConfig,
+        instance: Column<Instance>,
+    }
+
+    #[derive(Default)]
+    struct TestCircuit {
+        values: Vec<Option<Fp>>,
+        weights: Vec<Fp>,
+    }
+
+    impl Circuit<Fp> for TestCircuit {
+        type Config = TestConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self {
+                values: vec![None; self.values.len()],
+                weights: self.weights.clone(),
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            let x = meta.advice_column();
+            let weight = meta.fixed_column();
+            let acc = meta.advice_column();
+            let constant = meta.fixed_column();
+            let instance = meta.instance_column();
+            meta.enable_equality(instance);
+
+            TestConfig {
+                sum: WeightedSumChip::configure(meta, x, weight, acc, constant),
+                instance,
+            }
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = WeightedSumChip::construct(config.sum);
+            let sum = chip.assign(layouter.namespace(|| "sum"), &self.values, &self.weights)?;
+            layouter.constrain_instance(sum.cell(), config.instance, 0)
+        }
+    }
+
+    fn circuit(values: &[u64], weights: &[u64]) -> TestCircuit {
+        TestCircuit {
+            values: values.iter().map(|v| Some(Fp::from(*v))).collect(),
+            weights: weights.iter().map(|w| Fp::from(*w)).collect(),
+        }
+    }
+
+    fn run(values: &[u64], weights: &[u64], sum: u64) -> MockProver<Fp> {
+        MockProver::run(4, &circuit(values, weights), vec![vec![Fp::from(sum)]]).unwrap()
+    }
+
+    #[test]
+    fn test_weighted_sum() {
+        // 2 * 1 + 3 * 2 + 4 * 3
+        run(&[1, 2, 3], &[2, 3, 4], 20).assert_satisfied();
+    }
+
+    #[test]
+    fn test_weighted_sum_empty() {
+        run(&[], &[], 0).assert_satisfied();
+    }
+
+    #[test]
+    fn test_weighted_sum_failed() {
+        // a wrong sum
+        assert!(run(&[1, 2, 3], &[2, 3, 4], 18).verify().is_err());
+        // the same values under other weights
+        assert!(run(&[1, 2, 3], &[4, 3, 2], 20).verify().is_err());
+    }
+
+    #[test]
+    fn test_weighted_sum_length_mismatch() {
+        let result = MockProver::run(4, &circuit(&[1, 2, 3], &[2, 3]), vec![vec![Fp::from(8)]]);
+        assert!(matches!(result, Err(Error::Synthesis)));
+    }
+}
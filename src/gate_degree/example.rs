@@ -0,0 +1,246 @@
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+use crate::error::CircuitError;
+
+//
+// selector |    x    |        out
+// ---------+---------+--------------------
+//    s     |   x0    | x0 * x1 * ... * x(D-1)
+//          |   x1    |
+//          |   ...   |
+//          |  x(D-1) |
+//
+// D = DEGREE. The gate multiplies DEGREE linear terms, one per rotation of x,
+//   s * (x(cur) * x(next) * ... * x(DEGREE - 1) - out)
+// so its degree is DEGREE + 1 (the selector counts too).
+//
+// The degree doesn't change the number of rows: the prover evaluates the gates
+// on an extended domain of (degree - 1) * 2^k points, and that is what a
+// higher degree pays for (see test_gate_degree_extended_domain).
+// Here k grows as well, because every factor is another rotation of x: each
+// opening of a column needs its own blinding row, max(3, DEGREE) + 2 of them,
+// and those rows aren't usable by the circuit (see test_gate_degree_min_k).
+
+#[derive(Debug, Clone)]
+struct DegreeConfig {
+    pub x: Column<Advice>,
+    pub out: Column<Advice>,
+    pub instance: Column<Instance>,
+    pub selector: Selector,
+}
+
+struct DegreeChip<F: FieldExt, const DEGREE: usize> {
+    config: DegreeConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt, const DEGREE: usize> DegreeChip<F, DEGREE> {
+    pub fn construct(config: DegreeConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        x: Column<Advice>,
+        out: Column<Advice>,
+        instance: Column<Instance>,
+    ) -> DegreeConfig {
+        assert!(DEGREE > 0, "the product needs at least one term");
+        let selector = meta.selector();
+
+        meta.enable_equality(out);
+        meta.enable_equality(instance);
+
+        meta.create_gate("product", |meta| {
+            let s = meta.query_selector(selector);
+            let product = (1..DEGREE).fold(meta.query_advice(x, Rotation::cur()), |acc, i| {
+                acc * meta.query_advice(x, Rotation(i as i32))
+            });
+            let out = meta.query_advice(out, Rotation::cur());
+            vec![s * (product - out)]
+        });
+
+        DegreeConfig {
+            x,
+            out,
+            instance,
+            selector,
+        }
+    }
+
+    pub fn assign(
+        &self,
+        mut layouter: impl Layouter<F>,
+        xs: [Option<F>; DEGREE],
+    ) -> Result<AssignedCell<F, F>, Error> {
+        layouter.assign_region(
+            || "product region",
+            |mut region| {
+                self.config.selector.enable(&mut region, 0)?;
+
+                for (row, x) in xs.iter().enumerate() {
+                    region.assign_advice(
+                        || "x",
+                        self.config.x,
+                        row,
+                        || x.ok_or(CircuitError::MissingWitness.into()),
+                    )?;
+                }
+
+                let product = xs.iter().try_fold(F::one(), |acc, x| x.map(|x| acc * x));
+                region.assign_advice(
+                    || "out",
+                    self.config.out,
+                    0,
+                    || product.ok_or(CircuitError::MissingWitness.into()),
+                )
+            },
+        )
+    }
+
+    pub fn expose_public(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cell: &AssignedCell<F, F>,
+        row: usize,
+    ) -> Result<(), Error> {
+        layouter.constrain_instance(cell.cell(), self.config.instance, row)
+    }
+}
+
+struct MyCircuit<F, const DEGREE: usize> {
+    pub xs: [Option<F>; DEGREE],
+}
+
+impl<F: FieldExt, const DEGREE: usize> Default for MyCircuit<F, DEGREE> {
+    fn default() -> Self {
+        Self { xs: [None; DEGREE] }
+    }
+}
+
+impl<F: FieldExt, const DEGREE: usize> MyCircuit<F, DEGREE> {
+    // x = 1, 2, ..., DEGREE, so the output is DEGREE!
+    pub fn counting() -> Self {
+        let mut xs = [None; DEGREE];
+        for (i, x) in xs.iter_mut().enumerate() {
+            *x = Some(F::from(i as u64 + 1));
+        }
+        Self { xs }
+    }
+}
+
+impl<F: FieldExt, const DEGREE: usize> Circuit<F> for MyCircuit<F, DEGREE> {
+    type Config = DegreeConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let x = meta.advice_column();
+        let out = meta.advice_column();
+        let instance = meta.instance_column();
+
+        DegreeChip::<F, DEGREE>::configure(meta, x, out, instance)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let chip = DegreeChip::<F, DEGREE>::construct(config);
+
+        let out = chip.assign(layouter.namespace(|| "product"), self.xs)?;
+        chip.expose_public(layouter.namespace(|| "out"), &out, 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MyCircuit;
+    use halo2_proofs::{
+        dev::MockProver,
+        pasta::{EqAffine, Fp},
+        plonk::{keygen_vk, Circuit, ConstraintSystem, VerifyingKey},
+        poly::commitment::Params,
+    };
+
+    fn factorial(n: usize) -> u64 {
+        (1..=n as u64).product()
+    }
+
+    // the smallest k for which the circuit fits and verifies
+    fn min_k<const DEGREE: usize>() -> u32 {
+        let circuit = MyCircuit::<Fp, DEGREE>::counting();
+        let public_input = vec![Fp::from(factorial(DEGREE))];
+
+        (1..=10)
+            .find(
+                |&k| match MockProver::run(k, &circuit, vec![public_input.clone()]) {
+                    Ok(prover) => prover.verify().is_ok(),
+                    Err(_) => false,
+                },
+            )
+            .expect("fits in 2^10 rows")
+    }
+
+    fn gate_degree<const DEGREE: usize>() -> usize {
+        let mut meta = ConstraintSystem::<Fp>::default();
+        MyCircuit::<Fp, DEGREE>::configure(&mut meta);
+        meta.degree()
+    }
+
+    #[test]
+    fn test_gate_degree() {
+        let k = 4;
+
+        let circuit = MyCircuit::<Fp, 4>::counting();
+        let prover = MockProver::run(k, &circuit, vec![vec![Fp::from(24)]]).unwrap();
+        prover.assert_satisfied();
+
+        assert_eq!(gate_degree::<2>(), 3);
+        assert_eq!(gate_degree::<4>(), 5);
+        assert_eq!(gate_degree::<8>(), 9);
+    }
+
+    #[test]
+    fn test_gate_degree_failed() {
+        let k = 4;
+
+        let circuit = MyCircuit::<Fp, 4>::counting();
+        let prover = MockProver::run(k, &circuit, vec![vec![Fp::from(25)]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn test_gate_degree_min_k() {
+        // DEGREE used rows plus max(3, DEGREE) + 2 blinding rows and a few
+        // more reserved ones
+        let ks = [min_k::<2>(), min_k::<4>(), min_k::<8>(), min_k::<16>()];
+        println!("minimal k for DEGREE 2, 4, 8, 16: {:?}", ks);
+        assert_eq!(ks, [3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn test_gate_degree_extended_domain() {
+        // the same k for every DEGREE, only the extended domain grows with it
+        let k = 6;
+        let params: Params<EqAffine> = Params::new(k);
+
+        let extended = |vk: VerifyingKey<EqAffine>| vk.get_domain().extended_len() >> k;
+        let sizes = [
+            extended(keygen_vk(&params, &MyCircuit::<Fp, 2>::default()).unwrap()),
+            extended(keygen_vk(&params, &MyCircuit::<Fp, 4>::default()).unwrap()),
+            extended(keygen_vk(&params, &MyCircuit::<Fp, 8>::default()).unwrap()),
+            extended(keygen_vk(&params, &MyCircuit::<Fp, 16>::default()).unwrap()),
+        ];
+        // (degree - 1) * 2^k points, rounded up to a power of two
+        assert_eq!(sizes, [2, 4, 8, 16]);
+    }
+}
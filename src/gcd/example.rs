@@ -0,0 +1,249 @@
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+use std::marker::PhantomData;
+
+use crate::conditional_select::example::{CondSelectChip, CondSelectConfig};
+use crate::error::CircuitError;
+use crate::gadgets::divmod::{DivModChip, DivModConfig};
+use crate::is_zero::example::{IsZeroChip, IsZeroConfig};
+use crate::range_check::lookup::LookupRangeCheckChip;
+
+//
+// gcd(a, b) with the Euclidean algorithm, one step at a time:
+//   (a, b) -> (b, a mod b)
+// until b is 0, at which point a is the gcd. Each step is put together from
+// existing chips, every input copied from the cell it comes from:
+//
+//   is_zero (is_zero/example.rs)
+//     value | value_inv | is_zero
+//     ------+-----------+--------
+//       b   |   b^-1    |  done
+//
+//   conditional select (conditional_select/example.rs), the divisor
+//     cond | a | b | out
+//     -----+---+---+-----
+//     done | 1 | b |  d
+//
+//   divmod (gadgets/divmod.rs), with the advice divisor d
+//      a  |  d  |  q  |  r  |   slack
+//     ----+-----+-----+-----+-----------
+//      a  |  d  |  q  |  r  | d - 1 - r
+//
+//   conditional select, the next a
+//     cond | a | b |  out
+//     -----+---+---+--------
+//     done | a | b | a_next
+//
+// and the next b is r. For 48 and 18:
+//   (48, 18) -> (18, 12) -> (12, 6) -> (6, 0) -> (6, 0) -> ...   gcd = 6
+//
+// A circuit can't loop a data-dependent number of times, so there are always
+// STEPS steps, enough for any a, b below RANGE. Once b reaches 0 the remaining
+// steps are padding that carry (a, 0) down unchanged, so the result doesn't
+// depend on how many steps were actually needed: done is 1, so a is divided by
+// 1 instead of 0, which leaves r and so the next b at 0, and the select keeps a.
+//
+// The last b is constrained to the constant 0, so a and b that need more than
+// STEPS steps fail instead of giving a wrong result.
+
+// 8-bit inputs
+const RANGE: usize = 256;
+const NUM_BITS: usize = 8;
+// the worst case below 256 is gcd(233, 144), two consecutive Fibonacci numbers
+const STEPS: usize = 12;
+
+#[derive(Debug, Clone)]
+struct GcdConfig<F: FieldExt> {
+    pub is_zero: IsZeroConfig,
+    pub select: CondSelectConfig,
+    pub divmod: DivModConfig<F, RANGE, NUM_BITS>,
+}
+
+struct GcdChip<F: FieldExt> {
+    config: GcdConfig<F>,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> GcdChip<F> {
+    pub fn construct(config: GcdConfig<F>) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 5],
+        value: Column<Advice>,
+        constant: Column<Fixed>,
+        instance: Column<Instance>,
+    ) -> GcdConfig<F> {
+        let [col_0, col_1, col_2, col_3, _] = advice;
+
+        // the chips share the columns, and the select and divmod chips enable
+        // equality on all of them, so any cell can be copied into any other chip
+        GcdConfig {
+            is_zero: IsZeroChip::configure(meta, col_0, col_1, col_2),
+            select: CondSelectChip::configure(meta, [col_0, col_1, col_2, col_3], instance),
+            divmod: DivModChip::configure(meta, advice, constant, value),
+        }
+    }
+
+    // returns the cell holding gcd(a, b)
+    pub fn assign(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: Option<F>,
+        b: Option<F>,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        let is_zero = IsZeroChip::construct(self.config.is_zero.clone());
+        let select = CondSelectChip::construct(self.config.select.clone());
+        let divmod = DivModChip::construct(self.config.divmod.clone());
+        let range_chip =
+            LookupRangeCheckChip::<F, RANGE, NUM_BITS>::construct(self.config.divmod.range.clone());
+
+        // the divisor of the padding steps is this constant 1
+        let (mut a, mut b, one) = layouter.assign_region(
+            || "load",
+            |mut region| {
+                let a = region.assign_advice(
+                    || "a",
+                    self.config.select.a,
+                    0,
+                    || a.ok_or(CircuitError::MissingWitness.into()),
+                )?;
+                let b = region.assign_advice(
+                    || "b",
+                    self.config.select.b,
+                    0,
+                    || b.ok_or(CircuitError::MissingWitness.into()),
+                )?;
+                let one = region.assign_advice_from_constant(
+                    || "one",
+                    self.config.select.cond,
+                    0,
+                    F::one(),
+                )?;
+                Ok((a, b, one))
+            },
+        )?;
+        // the later a and b are all remainders or copies of these
+        range_chip.check(layouter.namespace(|| "a"), &a)?;
+        range_chip.check(layouter.namespace(|| "b"), &b)?;
+
+        for _ in 0..STEPS {
+            let done = is_zero.is_zero(layouter.namespace(|| "b == 0"), &b)?;
+            let d = select.select(layouter.namespace(|| "divisor"), &done, &one, &b)?;
+            let (_, r) = divmod.divide(layouter.namespace(|| "a mod b"), &a, &d)?;
+
+            a = select.select(layouter.namespace(|| "next a"), &done, &a, &b)?;
+            b = r;
+        }
+
+        layouter.assign_region(
+            || "last b",
+            |mut region| {
+                let last_b = b.copy_advice(|| "b", &mut region, self.config.select.b, 0)?;
+                region.constrain_constant(last_b.cell(), F::zero())
+            },
+        )?;
+
+        Ok(a)
+    }
+
+    pub fn expose_public(
+        &self,
+        layouter: impl Layouter<F>,
+        cell: &AssignedCell<F, F>,
+        row: usize,
+    ) -> Result<(), Error> {
+        let select = CondSelectChip::<F>::construct(self.config.select.clone());
+        select.expose_public(layouter, cell, row)
+    }
+}
+
+#[derive(Default)]
+struct MyCircuit<F> {
+    pub a: Option<F>,
+    pub b: Option<F>,
+}
+
+impl<F: FieldExt> Circuit<F> for MyCircuit<F> {
+    type Config = GcdConfig<F>;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let advice = [
+            meta.advice_column(),
+            meta.advice_column(),
+            meta.advice_column(),
+            meta.advice_column(),
+            meta.advice_column(),
+        ];
+        let value = meta.advice_column();
+        let constant = meta.fixed_column();
+        let instance = meta.instance_column();
+
+        GcdChip::configure(meta, advice, value, constant, instance)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        config.divmod.range.table.load(&mut layouter)?;
+
+        let chip = GcdChip::construct(config);
+        let gcd = chip.assign(layouter.namespace(|| "gcd"), self.a, self.b)?;
+        chip.expose_public(layouter.namespace(|| "out"), &gcd, 0)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MyCircuit;
+    use halo2_proofs::{dev::MockProver, pasta::Fp};
+
+    fn circuit(a: u64, b: u64) -> MyCircuit<Fp> {
+        MyCircuit {
+            a: Some(Fp::from(a)),
+            b: Some(Fp::from(b)),
+        }
+    }
+
+    #[test]
+    fn test_gcd() {
+        // the range table takes 256 rows
+        let k = 9;
+
+        // the last one takes all 12 steps, the one before none at all
+        for (a, b, gcd) in [
+            (48, 18, 6),
+            (17, 5, 1),
+            (18, 48, 6),
+            (7, 0, 7),
+            (233, 144, 1),
+        ] {
+            let public_input = vec![Fp::from(gcd)];
+            let prover = MockProver::run(k, &circuit(a, b), vec![public_input]).unwrap();
+            prover.assert_satisfied();
+        }
+    }
+
+    #[test]
+    fn test_gcd_failed() {
+        let k = 9;
+
+        // a common divisor, but not the greatest one
+        let public_input = vec![Fp::from(3)];
+        let prover = MockProver::run(k, &circuit(48, 18), vec![public_input]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}
@@ -0,0 +1,202 @@
+use crate::fibonacci::prove::verify;
+use halo2_proofs::{
+    pasta::{EqAffine, Fp},
+    plonk::{keygen_vk, Circuit, Error},
+    poly::commitment::Params,
+};
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter, Write},
+    path::Path,
+};
+
+// Generating the IPA params (the commitment key) is the slow part of the setup,
+// so they can be stored to disk once and loaded again on later runs.
+//
+// halo2_proofs 0.1.0 doesn't support (de)serializing `VerifyingKey`/`ProvingKey`,
+// they're regenerated from the loaded params instead. That's cheap and
+// deterministic: the same params and circuit always give the same keys.
+
+#[derive(Debug)]
+pub enum IoError {
+    // failed to create, open, read or write the file
+    Io(std::io::Error),
+    // the loaded params don't fit the circuit, e.g. k is too small for it
+    Keygen(Error),
+}
+
+impl From<std::io::Error> for IoError {
+    fn from(err: std::io::Error) -> Self {
+        IoError::Io(err)
+    }
+}
+
+pub fn write_params(params: &Params<EqAffine>, path: impl AsRef<Path>) -> Result<(), IoError> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    params.write(&mut writer)?;
+    writer.flush()?;
+    Ok(())
+}
+
+pub fn read_params(path: impl AsRef<Path>) -> Result<Params<EqAffine>, IoError> {
+    let mut reader = BufReader::new(File::open(path)?);
+    Ok(Params::read(&mut reader)?)
+}
+
+// The verifier side of a split deployment: all it gets is the serialized
+// params, the proof and the public inputs, no proving key and no witnesses.
+//
+// Since the verifying key can't be serialized in halo2_proofs 0.1.0, it's
+// rebuilt from the params and the shape of the circuit, i.e. the circuit
+// without witnesses (e.g. MyCircuit::default() with the prover's iter_num).
+// keygen_vk only looks at the layout, so that's the same key the prover used.
+// Bytes that aren't valid params give Err, a proof that doesn't verify Ok(false),
+// whatever its bytes: a truncated proof or one that doesn't decode to curve
+// points fails while reading the transcript, which verify_proof reports as an
+// error too (see test_verify_only_malformed_proof and the fuzz target in fuzz/).
+pub fn verify_only<C: Circuit<Fp>>(
+    params_bytes: &[u8],
+    shape: &C,
+    proof: &[u8],
+    public: &[Fp],
+) -> Result<bool, IoError> {
+    let params: Params<EqAffine> = Params::read(&mut &params_bytes[..])?;
+    let vk = keygen_vk(&params, shape).map_err(IoError::Keygen)?;
+    Ok(verify(&params, &vk, proof, public))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{read_params, verify_only, write_params, IoError};
+    use crate::fibonacci::{
+        example2::MyCircuit,
+        prove::{prove, verify},
+    };
+    use crate::testing::{nth_fibonacci, test_rng};
+    use halo2_proofs::{
+        pasta::{EqAffine, Fp},
+        plonk::{keygen_pk, keygen_vk},
+        poly::commitment::Params,
+    };
+
+    #[test]
+    fn test_params_round_trip() {
+        let k = 4;
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("params.bin");
+
+        let params: Params<EqAffine> = Params::new(k);
+        write_params(&params, &path).unwrap();
+        let params = read_params(&path).unwrap();
+
+        let circuit = MyCircuit {
+            a: Some(Fp::from(1)),
+            b: Some(Fp::from(2)),
+            iter_num: 10,
+        };
+        let vk = keygen_vk(&params, &circuit).unwrap();
+        let pk = keygen_pk(&params, vk, &circuit).unwrap();
+
+        let public_input = vec![Fp::from(nth_fibonacci(1, 2, 10))];
+        let proof = prove(&params, &pk, circuit, &public_input).unwrap();
+        assert!(verify(&params, pk.get_vk(), &proof, &public_input));
+    }
+
+    #[test]
+    fn test_read_params_missing_file() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let result = read_params(dir.path().join("missing.bin"));
+        assert!(matches!(result, Err(IoError::Io(_))));
+    }
+
+    #[test]
+    fn test_verify_only() {
+        let k = 4;
+        let public_input = vec![Fp::from(nth_fibonacci(1, 2, 10))];
+
+        // prover side: sends the params and the proof
+        let (params_bytes, proof) = {
+            let params: Params<EqAffine> = Params::new(k);
+            let mut params_bytes = vec![];
+            params.write(&mut params_bytes).unwrap();
+
+            let circuit = MyCircuit::from_u64(1, 2);
+            let vk = keygen_vk(&params, &circuit).unwrap();
+            let pk = keygen_pk(&params, vk, &circuit).unwrap();
+            let proof = prove(&params, &pk, circuit, &public_input).unwrap();
+            (params_bytes, proof)
+        };
+
+        // verifier side: only the bytes, the public inputs and the circuit shape
+        let shape = MyCircuit::default();
+        assert!(verify_only(&params_bytes, &shape, &proof, &public_input).unwrap());
+
+        let wrong_input = vec![Fp::from(nth_fibonacci(1, 2, 10) + 1)];
+        assert!(!verify_only(&params_bytes, &shape, &proof, &wrong_input).unwrap());
+
+        // another shape gives another key
+        let shape = MyCircuit {
+            iter_num: 9,
+            ..MyCircuit::default()
+        };
+        assert!(!verify_only(&params_bytes, &shape, &proof, &public_input).unwrap());
+
+        let truncated = &params_bytes[..params_bytes.len() / 2];
+        assert!(matches!(
+            verify_only(truncated, &MyCircuit::default(), &proof, &public_input),
+            Err(IoError::Io(_))
+        ));
+
+        // valid params, but too few rows for the circuit
+        let mut small_params = vec![];
+        Params::<EqAffine>::new(2).write(&mut small_params).unwrap();
+        assert!(matches!(
+            verify_only(&small_params, &MyCircuit::default(), &proof, &public_input),
+            Err(IoError::Keygen(_))
+        ));
+    }
+
+    #[test]
+    fn test_verify_only_malformed_proof() {
+        use rand_core::RngCore;
+
+        let k = 4;
+        let public_input = vec![Fp::from(nth_fibonacci(1, 2, 10))];
+
+        let params: Params<EqAffine> = Params::new(k);
+        let mut params_bytes = vec![];
+        params.write(&mut params_bytes).unwrap();
+
+        let circuit = MyCircuit::from_u64(1, 2);
+        let vk = keygen_vk(&params, &circuit).unwrap();
+        let pk = keygen_pk(&params, vk, &circuit).unwrap();
+        let proof = prove(&params, &pk, circuit, &public_input).unwrap();
+
+        let shape = MyCircuit::default();
+        let rejected = |proof: &[u8]| {
+            matches!(
+                verify_only(&params_bytes, &shape, proof, &public_input),
+                Ok(false)
+            )
+        };
+        assert!(verify_only(&params_bytes, &shape, &proof, &public_input).unwrap());
+
+        // a random bit flipped anywhere in the proof
+        let mut rng = test_rng();
+        for _ in 0..32 {
+            let mut flipped = proof.clone();
+            let i = rng.next_u32() as usize % flipped.len();
+            flipped[i] ^= 1 << (rng.next_u32() % 8);
+            assert!(rejected(&flipped), "byte {} flipped", i);
+        }
+
+        // cut short, padded, garbage
+        assert!(rejected(&[]));
+        assert!(rejected(&proof[..proof.len() - 1]));
+        assert!(rejected(&[proof.clone(), vec![0]].concat()));
+        let mut garbage = vec![0u8; proof.len()];
+        rng.fill_bytes(&mut garbage);
+        assert!(rejected(&garbage));
+    }
+}
@@ -0,0 +1,228 @@
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+use crate::error::CircuitError;
+
+//
+// selector | value | value_inv | is_zero |
+// ---------+-------+-----------+---------|
+//   s0     |   v   |   v^-1    |    0    |   (v != 0)
+//   s1     |   0   |  (any)    |    1    |   (v == 0)
+//
+// is_zero_expr = 1 - v * value_inv, and v * is_zero_expr = 0
+//   - if v != 0, the second constraint forces is_zero_expr = 0, i.e. value_inv = v^-1
+//   - if v == 0, is_zero_expr = 1 whatever value_inv is
+// value_inv is only a hint from the prover, the gate itself never computes an inverse.
+
+#[derive(Debug, Clone)]
+pub(crate) struct IsZeroConfig {
+    pub value: Column<Advice>,
+    pub value_inv: Column<Advice>,
+    pub output: Column<Advice>,
+    pub selector: Selector,
+}
+
+pub(crate) struct IsZeroChip<F: FieldExt> {
+    config: IsZeroConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> IsZeroChip<F> {
+    pub fn construct(config: IsZeroConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        value: Column<Advice>,
+        value_inv: Column<Advice>,
+        output: Column<Advice>,
+    ) -> IsZeroConfig {
+        let selector = meta.selector();
+
+        meta.create_gate("is_zero", |meta| {
+            let s = meta.query_selector(selector);
+            let v = meta.query_advice(value, Rotation::cur());
+            let v_inv = meta.query_advice(value_inv, Rotation::cur());
+            let out = meta.query_advice(output, Rotation::cur());
+
+            let is_zero_expr = Expression::Constant(F::one()) - v.clone() * v_inv;
+            vec![
+                s.clone() * (v * is_zero_expr.clone()),
+                s * (out - is_zero_expr.clone()),
+            ]
+        });
+
+        IsZeroConfig {
+            value,
+            value_inv,
+            output,
+            selector,
+        }
+    }
+
+    pub fn assign(
+        &self,
+        mut layouter: impl Layouter<F>,
+        value: Option<F>,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        layouter.assign_region(
+            || "is_zero",
+            |mut region| {
+                self.config.selector.enable(&mut region, 0)?;
+
+                region.assign_advice(
+                    || "value",
+                    self.config.value,
+                    0,
+                    || value.ok_or(CircuitError::MissingWitness.into()),
+                )?;
+                self.assign_output(&mut region, value)
+            },
+        )
+    }
+
+    // same as `assign`, with the value copied from a cell assigned elsewhere;
+    // the value column needs equality enabled for it
+    pub fn is_zero(
+        &self,
+        mut layouter: impl Layouter<F>,
+        value: &AssignedCell<F, F>,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        layouter.assign_region(
+            || "is_zero",
+            |mut region| {
+                self.config.selector.enable(&mut region, 0)?;
+
+                let value = value.copy_advice(|| "value", &mut region, self.config.value, 0)?;
+                self.assign_output(&mut region, value.value().copied())
+            },
+        )
+    }
+
+    // assigns value_inv and the output next to the value
+    fn assign_output(
+        &self,
+        region: &mut Region<'_, F>,
+        value: Option<F>,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        // zero has no inverse, any value works there so we just use 0
+        let value_inv = value.map(|v| v.invert().unwrap_or(F::zero()));
+        region.assign_advice(
+            || "value_inv",
+            self.config.value_inv,
+            0,
+            || value_inv.ok_or(CircuitError::MissingWitness.into()),
+        )?;
+
+        let is_zero = value.map(|v| if v == F::zero() { F::one() } else { F::zero() });
+        region.assign_advice(
+            || "is_zero",
+            self.config.output,
+            0,
+            || is_zero.ok_or(CircuitError::MissingWitness.into()),
+        )
+    }
+
+    pub fn expose_public(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cell: &AssignedCell<F, F>,
+        instance: Column<Instance>,
+        row: usize,
+    ) -> Result<(), Error> {
+        layouter.constrain_instance(cell.cell(), instance, row)
+    }
+}
+
+#[derive(Default)]
+struct MyCircuit<F> {
+    pub value: Option<F>,
+}
+
+impl<F: FieldExt> Circuit<F> for MyCircuit<F> {
+    type Config = (IsZeroConfig, Column<Instance>);
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let value = meta.advice_column();
+        let value_inv = meta.advice_column();
+        let output = meta.advice_column();
+        let instance = meta.instance_column();
+
+        meta.enable_equality(output);
+        meta.enable_equality(instance);
+
+        (
+            IsZeroChip::configure(meta, value, value_inv, output),
+            instance,
+        )
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let (config, instance) = config;
+        let chip = IsZeroChip::construct(config);
+
+        let out = chip.assign(layouter.namespace(|| "is_zero"), self.value)?;
+        chip.expose_public(layouter.namespace(|| "out"), &out, instance, 0)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MyCircuit;
+    use halo2_proofs::{dev::MockProver, pasta::Fp};
+
+    #[test]
+    fn test_is_zero() {
+        let k = 4;
+
+        let circuit = MyCircuit {
+            value: Some(Fp::zero()),
+        };
+
+        let public_input = vec![Fp::one()];
+        let prover = MockProver::run(k, &circuit, vec![public_input]).unwrap();
+        prover.assert_satisfied();
+    }
+
+    #[test]
+    fn test_is_not_zero() {
+        let k = 4;
+
+        for value in [Fp::one(), Fp::from(5), -Fp::one()] {
+            let circuit = MyCircuit { value: Some(value) };
+
+            let public_input = vec![Fp::zero()];
+            let prover = MockProver::run(k, &circuit, vec![public_input]).unwrap();
+            prover.assert_satisfied();
+        }
+    }
+
+    #[test]
+    fn test_is_zero_failed() {
+        let k = 4;
+
+        // claims 5 is zero
+        let circuit = MyCircuit {
+            value: Some(Fp::from(5)),
+        };
+
+        let public_input = vec![Fp::one()];
+        let prover = MockProver::run(k, &circuit, vec![public_input]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}
@@ -1 +1,55 @@
-mod fibonacci;
+//! Small, self-contained halo2 circuits, written to learn how halo2 circuits
+//! are put together: columns, gates, selectors, regions, copy constraints,
+//! lookups and public inputs, then the real prover and verifier.
+//!
+//! Every example is one file with an ASCII table of its layout at the top, a
+//! config, a chip and a `MyCircuit`, and is checked by its own tests with
+//! `MockProver`. The Fibonacci examples in [`fibonacci`] lay out the same
+//! sequence in many different ways and are public, so they can be reused as
+//! a dependency; [`testing`] has the helpers their tests use.
+
+// Only the Fibonacci examples are public, the other ones are driven from their
+// own test modules, so outside of `cargo test` nothing in them is used.
+
+#[cfg_attr(not(test), allow(dead_code))]
+mod arithmetic;
+#[cfg_attr(not(test), allow(dead_code))]
+mod comparison;
+#[cfg_attr(not(test), allow(dead_code))]
+mod composed;
+#[cfg_attr(not(test), allow(dead_code))]
+mod conditional_select;
+#[cfg_attr(not(test), allow(dead_code))]
+mod copy_constraint;
+#[cfg_attr(not(test), allow(dead_code))]
+mod decompose;
+pub mod error;
+#[cfg_attr(not(test), allow(dead_code))]
+mod factorial;
+pub mod fibonacci;
+#[cfg_attr(not(test), allow(dead_code))]
+mod fixed;
+#[cfg_attr(not(test), allow(dead_code))]
+mod gadgets;
+#[cfg_attr(not(test), allow(dead_code))]
+mod gate_degree;
+#[cfg_attr(not(test), allow(dead_code))]
+mod gcd;
+pub mod io;
+#[cfg_attr(not(test), allow(dead_code))]
+mod is_zero;
+#[cfg_attr(not(test), allow(dead_code))]
+mod linalg;
+#[cfg_attr(not(test), allow(dead_code))]
+mod lookup;
+#[cfg_attr(not(test), allow(dead_code))]
+mod merkle;
+#[cfg_attr(not(test), allow(dead_code))]
+mod poly_eval;
+#[cfg_attr(not(test), allow(dead_code))]
+mod range_check;
+#[cfg_attr(not(test), allow(dead_code))]
+mod selectors;
+pub mod testing;
+#[cfg(feature = "wasm")]
+pub mod wasm;
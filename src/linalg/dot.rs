@@ -0,0 +1,225 @@
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+use crate::error::CircuitError;
+
+//
+// selector |  a  |  b  |  acc
+// ---------+-----+-----+---------------------
+//    0     |     |     |  0                     (from the fixed column)
+//    1     | a0  | b0  |  0 + a0 * b0
+//    1     | a1  | b1  |  acc1 + a1 * b1
+//   ...    | ... | ... |  ...
+//
+// The dot product is accumulated row by row in a running sum:
+//   s * (acc_prev + a * b - acc_cur) = 0
+// The first acc is copied from a constant 0, so the empty product is just that cell.
+//
+// The vectors are slices, not arrays of a const length: the gate is the same
+// for any length, only the number of rows changes, so there's nothing a const
+// generic would buy here (unlike example1's N, which fixes the circuit shape in
+// the type). The circuit shape still depends on the length, which is why
+// without_witnesses keeps it.
+
+#[derive(Debug, Clone)]
+struct DotConfig {
+    pub a: Column<Advice>,
+    pub b: Column<Advice>,
+    pub acc: Column<Advice>,
+    pub instance: Column<Instance>,
+    pub selector: Selector,
+}
+
+struct DotChip<F: FieldExt> {
+    config: DotConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> DotChip<F> {
+    pub fn construct(config: DotConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        a: Column<Advice>,
+        b: Column<Advice>,
+        acc: Column<Advice>,
+        instance: Column<Instance>,
+        constant: Column<Fixed>,
+    ) -> DotConfig {
+        let selector = meta.selector();
+
+        meta.enable_equality(acc);
+        meta.enable_equality(instance);
+        meta.enable_constant(constant);
+
+        meta.create_gate("dot", |meta| {
+            let s = meta.query_selector(selector);
+            let a = meta.query_advice(a, Rotation::cur());
+            let b = meta.query_advice(b, Rotation::cur());
+            let acc_prev = meta.query_advice(acc, Rotation::prev());
+            let acc_cur = meta.query_advice(acc, Rotation::cur());
+            vec![s * (acc_prev + a * b - acc_cur)]
+        });
+
+        DotConfig {
+            a,
+            b,
+            acc,
+            instance,
+            selector,
+        }
+    }
+
+    // returns the cell holding sum(a[i] * b[i])
+    pub fn assign(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &[Option<F>],
+        b: &[Option<F>],
+    ) -> Result<AssignedCell<F, F>, Error> {
+        assert_eq!(a.len(), b.len(), "the vectors must have the same length");
+
+        layouter.assign_region(
+            || "dot product",
+            |mut region| {
+                let mut acc_cell =
+                    region.assign_advice_from_constant(|| "acc", self.config.acc, 0, F::zero())?;
+
+                for (i, (a, b)) in a.iter().zip(b).enumerate() {
+                    let row = i + 1;
+                    self.config.selector.enable(&mut region, row)?;
+
+                    region.assign_advice(
+                        || "a",
+                        self.config.a,
+                        row,
+                        || a.ok_or(CircuitError::MissingWitness.into()),
+                    )?;
+                    region.assign_advice(
+                        || "b",
+                        self.config.b,
+                        row,
+                        || b.ok_or(CircuitError::MissingWitness.into()),
+                    )?;
+
+                    let acc = acc_cell
+                        .value()
+                        .and_then(|acc| a.zip(*b).map(|(a, b)| *acc + a * b));
+                    acc_cell = region.assign_advice(
+                        || "acc",
+                        self.config.acc,
+                        row,
+                        || acc.ok_or(CircuitError::MissingWitness.into()),
+                    )?;
+                }
+                Ok(acc_cell)
+            },
+        )
+    }
+
+    pub fn expose_public(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cell: &AssignedCell<F, F>,
+        row: usize,
+    ) -> Result<(), Error> {
+        layouter.constrain_instance(cell.cell(), self.config.instance, row)
+    }
+}
+
+#[derive(Default)]
+struct MyCircuit<F> {
+    pub a: Vec<Option<F>>,
+    pub b: Vec<Option<F>>,
+}
+
+impl<F: FieldExt> Circuit<F> for MyCircuit<F> {
+    type Config = DotConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        // the length decides the layout
+        Self {
+            a: vec![None; self.a.len()],
+            b: vec![None; self.b.len()],
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let a = meta.advice_column();
+        let b = meta.advice_column();
+        let acc = meta.advice_column();
+        let instance = meta.instance_column();
+        let constant = meta.fixed_column();
+
+        DotChip::configure(meta, a, b, acc, instance, constant)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let chip = DotChip::construct(config);
+
+        let out = chip.assign(layouter.namespace(|| "dot"), &self.a, &self.b)?;
+        chip.expose_public(layouter.namespace(|| "out"), &out, 0)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MyCircuit;
+    use halo2_proofs::{dev::MockProver, pasta::Fp};
+
+    fn to_witnesses(values: &[u64]) -> Vec<Option<Fp>> {
+        values.iter().map(|v| Some(Fp::from(*v))).collect()
+    }
+
+    #[test]
+    fn test_dot() {
+        let k = 4;
+
+        // 1 * 4 + 2 * 5 + 3 * 6
+        let circuit = MyCircuit {
+            a: to_witnesses(&[1, 2, 3]),
+            b: to_witnesses(&[4, 5, 6]),
+        };
+
+        let public_input = vec![Fp::from(32)];
+        let prover = MockProver::run(k, &circuit, vec![public_input]).unwrap();
+        prover.assert_satisfied();
+    }
+
+    #[test]
+    fn test_dot_empty() {
+        let k = 4;
+
+        let circuit = MyCircuit::<Fp>::default();
+
+        let public_input = vec![Fp::zero()];
+        let prover = MockProver::run(k, &circuit, vec![public_input]).unwrap();
+        prover.assert_satisfied();
+    }
+
+    #[test]
+    fn test_dot_failed() {
+        let k = 4;
+
+        let circuit = MyCircuit {
+            a: to_witnesses(&[1, 2, 3]),
+            b: to_witnesses(&[4, 5, 6]),
+        };
+
+        let public_input = vec![Fp::from(33)];
+        let prover = MockProver::run(k, &circuit, vec![public_input]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}
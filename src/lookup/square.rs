@@ -0,0 +1,229 @@
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+use crate::error::CircuitError;
+
+//
+// q_lookup | x | y  | table_x | table_y
+// ---------+---+----+---------+---------
+//    1     | 7 | 49 |    0    |    0
+//          |   |    |    1    |    1
+//          |   |    |    2    |    4
+//          |   |    |   ...   |   ...
+//          |   |    |   255   |  65025
+//
+// The pair (x, y) is looked up in a table of (x, x^2) for x in 0..256, so
+// y = x^2 holds without any gate. Both columns are looked up together, which is
+// what ties a y to its own x and not to any square in the table.
+
+const TABLE_SIZE: u64 = 256;
+
+#[derive(Debug, Clone)]
+struct SquareTableConfig<F: FieldExt> {
+    pub x: TableColumn,
+    pub y: TableColumn,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> SquareTableConfig<F> {
+    pub fn configure(meta: &mut ConstraintSystem<F>) -> Self {
+        let x = meta.lookup_table_column();
+        let y = meta.lookup_table_column();
+
+        Self {
+            x,
+            y,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn load(&self, layouter: &mut impl Layouter<F>) -> Result<(), Error> {
+        layouter.assign_table(
+            || "load square table",
+            |mut table| {
+                for (offset, x) in (0..TABLE_SIZE).enumerate() {
+                    table.assign_cell(|| "x", self.x, offset, || Ok(F::from(x)))?;
+                    table.assign_cell(|| "x^2", self.y, offset, || Ok(F::from(x * x)))?;
+                }
+                Ok(())
+            },
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SquareConfig<F: FieldExt> {
+    pub x: Column<Advice>,
+    pub y: Column<Advice>,
+    pub q_lookup: Selector,
+    pub table: SquareTableConfig<F>,
+}
+
+struct SquareChip<F: FieldExt> {
+    config: SquareConfig<F>,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> SquareChip<F> {
+    pub fn construct(config: SquareConfig<F>) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        x: Column<Advice>,
+        y: Column<Advice>,
+    ) -> SquareConfig<F> {
+        // selectors used in lookups must be complex selectors
+        let q_lookup = meta.complex_selector();
+        let table = SquareTableConfig::configure(meta);
+
+        meta.lookup(|meta| {
+            let q = meta.query_selector(q_lookup);
+            let x = meta.query_advice(x, Rotation::cur());
+            let y = meta.query_advice(y, Rotation::cur());
+
+            // when the selector is off we look up (0, 0), which is the first row of the table
+            vec![(q.clone() * x, table.x), (q * y, table.y)]
+        });
+
+        SquareConfig {
+            x,
+            y,
+            q_lookup,
+            table,
+        }
+    }
+
+    // assigns x and the claimed y = x^2
+    pub fn assign(
+        &self,
+        mut layouter: impl Layouter<F>,
+        x: Option<F>,
+        y: Option<F>,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        layouter.assign_region(
+            || "square lookup",
+            |mut region| {
+                self.config.q_lookup.enable(&mut region, 0)?;
+                region.assign_advice(
+                    || "x",
+                    self.config.x,
+                    0,
+                    || x.ok_or(CircuitError::MissingWitness.into()),
+                )?;
+                region.assign_advice(
+                    || "y",
+                    self.config.y,
+                    0,
+                    || y.ok_or(CircuitError::MissingWitness.into()),
+                )
+            },
+        )
+    }
+}
+
+#[derive(Default)]
+struct MyCircuit<F> {
+    pub x: Option<F>,
+    pub y: Option<F>,
+}
+
+impl<F: FieldExt> Circuit<F> for MyCircuit<F> {
+    type Config = SquareConfig<F>;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let x = meta.advice_column();
+        let y = meta.advice_column();
+        SquareChip::configure(meta, x, y)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        config.table.load(&mut layouter)?;
+
+        let chip = SquareChip::construct(config);
+        chip.assign(layouter.namespace(|| "square"), self.x, self.y)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MyCircuit;
+    use halo2_proofs::{dev::MockProver, pasta::Fp};
+
+    #[test]
+    fn test_square_lookup() {
+        // the table alone takes 256 rows
+        let k = 9;
+
+        let circuit = MyCircuit {
+            x: Some(Fp::from(7)),
+            y: Some(Fp::from(49)),
+        };
+
+        let prover = MockProver::run(k, &circuit, vec![]).unwrap();
+        prover.assert_satisfied();
+    }
+
+    #[test]
+    fn test_square_lookup_failed() {
+        let k = 9;
+
+        // 48 isn't 7^2 (and isn't a square in the table at all)
+        let circuit = MyCircuit {
+            x: Some(Fp::from(7)),
+            y: Some(Fp::from(48)),
+        };
+
+        let prover = MockProver::run(k, &circuit, vec![]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn test_square_lookup_wrong_pair() {
+        let k = 9;
+
+        // 64 is in the y column, but on the row of 8, not of 7
+        let circuit = MyCircuit {
+            x: Some(Fp::from(7)),
+            y: Some(Fp::from(64)),
+        };
+
+        let prover = MockProver::run(k, &circuit, vec![]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[cfg(feature = "dev-graph")]
+    #[test]
+    fn plot_square_lookup() {
+        use plotters::prelude::*;
+
+        let root = BitMapBackend::new("square-lookup-layout.png", (1024, 3096)).into_drawing_area();
+        root.fill(&WHITE).unwrap();
+        let root = root
+            .titled("Square Lookup Layout", ("sans-serif", 60))
+            .unwrap();
+
+        let circuit = MyCircuit {
+            x: Some(Fp::from(7)),
+            y: Some(Fp::from(49)),
+        };
+        halo2_proofs::dev::CircuitLayout::default()
+            .render(9, &circuit, &root)
+            .unwrap();
+    }
+}
@@ -0,0 +1,408 @@
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+use crate::error::CircuitError;
+
+//
+// Verifies a Merkle path from a leaf up to a public root, one level at a time:
+//
+// s_swap | node | sibling | bit | left | right
+// -------+------+---------+-----+------+-------
+//    1   |  n0  |   s0    | b0  |  l0  |  r0      (n0 = leaf)
+//
+// s_hash | left | right | digest
+// -------+------+-------+--------
+//    1   |  l0  |  r0   |   n1                    (n1 = hash(l0, r0), next level's node)
+//
+// The position bit tells whether the node is the left (0) or the right (1) child:
+//   bit * (1 - bit) = 0
+//   left  = node + bit * (sibling - node)
+//   right = sibling + bit * (node - sibling)
+//
+// NOTE: the hash here is the toy compression hash(l, r) = l^2 + r. It's order
+// dependent, which is all this example needs, but it's NOT collision resistant.
+// A real circuit would use a cryptographic hash such as Poseidon in its place.
+
+pub fn hash<F: FieldExt>(left: F, right: F) -> F {
+    left * left + right
+}
+
+// (left, right)
+type CellPair<F> = (AssignedCell<F, F>, AssignedCell<F, F>);
+
+#[derive(Debug, Clone)]
+struct CondSwapConfig {
+    pub advice: [Column<Advice>; 5],
+    pub selector: Selector,
+}
+
+struct CondSwapChip<F: FieldExt> {
+    config: CondSwapConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> CondSwapChip<F> {
+    pub fn construct(config: CondSwapConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 5],
+    ) -> CondSwapConfig {
+        let selector = meta.selector();
+
+        meta.create_gate("conditional swap", |meta| {
+            let s = meta.query_selector(selector);
+            let node = meta.query_advice(advice[0], Rotation::cur());
+            let sibling = meta.query_advice(advice[1], Rotation::cur());
+            let bit = meta.query_advice(advice[2], Rotation::cur());
+            let left = meta.query_advice(advice[3], Rotation::cur());
+            let right = meta.query_advice(advice[4], Rotation::cur());
+            let one = Expression::Constant(F::one());
+
+            vec![
+                s.clone() * bit.clone() * (one - bit.clone()),
+                s.clone() * (node.clone() + bit.clone() * (sibling.clone() - node.clone()) - left),
+                s * (sibling.clone() + bit * (node - sibling) - right),
+            ]
+        });
+
+        CondSwapConfig { advice, selector }
+    }
+
+    // returns (left, right), i.e. (node, sibling) if bit is 0, (sibling, node) otherwise
+    pub fn swap(
+        &self,
+        mut layouter: impl Layouter<F>,
+        node: &AssignedCell<F, F>,
+        sibling: Option<F>,
+        bit: Option<F>,
+    ) -> Result<CellPair<F>, Error> {
+        layouter.assign_region(
+            || "conditional swap",
+            |mut region| {
+                self.config.selector.enable(&mut region, 0)?;
+
+                let node = node.copy_advice(|| "node", &mut region, self.config.advice[0], 0)?;
+                region.assign_advice(
+                    || "sibling",
+                    self.config.advice[1],
+                    0,
+                    || sibling.ok_or(CircuitError::MissingWitness.into()),
+                )?;
+                region.assign_advice(
+                    || "bit",
+                    self.config.advice[2],
+                    0,
+                    || bit.ok_or(CircuitError::MissingWitness.into()),
+                )?;
+
+                let swapped = node
+                    .value()
+                    .zip(sibling)
+                    .zip(bit)
+                    .map(|((node, sibling), bit)| {
+                        if bit == F::zero() {
+                            (*node, sibling)
+                        } else {
+                            (sibling, *node)
+                        }
+                    });
+                let left = region.assign_advice(
+                    || "left",
+                    self.config.advice[3],
+                    0,
+                    || {
+                        swapped
+                            .map(|(l, _)| l)
+                            .ok_or(CircuitError::MissingWitness.into())
+                    },
+                )?;
+                let right = region.assign_advice(
+                    || "right",
+                    self.config.advice[4],
+                    0,
+                    || {
+                        swapped
+                            .map(|(_, r)| r)
+                            .ok_or(CircuitError::MissingWitness.into())
+                    },
+                )?;
+
+                Ok((left, right))
+            },
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+struct MerkleConfig {
+    pub advice: [Column<Advice>; 5],
+    pub instance: Column<Instance>,
+    pub s_hash: Selector,
+    pub swap: CondSwapConfig,
+}
+
+struct MerkleChip<F: FieldExt> {
+    config: MerkleConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> MerkleChip<F> {
+    pub fn construct(config: MerkleConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 5],
+        instance: Column<Instance>,
+    ) -> MerkleConfig {
+        let s_hash = meta.selector();
+
+        // nodes are copied between the swap and hash regions
+        for column in advice {
+            meta.enable_equality(column);
+        }
+        meta.enable_equality(instance);
+
+        let swap = CondSwapChip::configure(meta, advice);
+
+        meta.create_gate("hash", |meta| {
+            let s = meta.query_selector(s_hash);
+            let left = meta.query_advice(advice[0], Rotation::cur());
+            let right = meta.query_advice(advice[1], Rotation::cur());
+            let digest = meta.query_advice(advice[2], Rotation::cur());
+            vec![s * (left.clone() * left + right - digest)]
+        });
+
+        MerkleConfig {
+            advice,
+            instance,
+            s_hash,
+            swap,
+        }
+    }
+
+    pub fn load_leaf(
+        &self,
+        mut layouter: impl Layouter<F>,
+        leaf: Option<F>,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        layouter.assign_region(
+            || "load leaf",
+            |mut region| {
+                region.assign_advice(
+                    || "leaf",
+                    self.config.advice[0],
+                    0,
+                    || leaf.ok_or(CircuitError::MissingWitness.into()),
+                )
+            },
+        )
+    }
+
+    pub fn hash(
+        &self,
+        mut layouter: impl Layouter<F>,
+        left: &AssignedCell<F, F>,
+        right: &AssignedCell<F, F>,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        layouter.assign_region(
+            || "hash",
+            |mut region| {
+                self.config.s_hash.enable(&mut region, 0)?;
+
+                let left = left.copy_advice(|| "left", &mut region, self.config.advice[0], 0)?;
+                let right = right.copy_advice(|| "right", &mut region, self.config.advice[1], 0)?;
+
+                let digest = left.value().zip(right.value()).map(|(l, r)| hash(*l, *r));
+                region.assign_advice(
+                    || "digest",
+                    self.config.advice[2],
+                    0,
+                    || digest.ok_or(CircuitError::MissingWitness.into()),
+                )
+            },
+        )
+    }
+
+    // hashes the leaf with each sibling bottom-up and returns the root
+    pub fn compute_root(
+        &self,
+        mut layouter: impl Layouter<F>,
+        leaf: Option<F>,
+        path: &[Option<F>],
+        bits: &[Option<F>],
+    ) -> Result<AssignedCell<F, F>, Error> {
+        // one position bit per sibling, zip would silently drop the extra levels
+        if path.len() != bits.len() {
+            return Err(CircuitError::LengthMismatch {
+                expected: path.len(),
+                actual: bits.len(),
+            }
+            .into());
+        }
+
+        let swap_chip = CondSwapChip::construct(self.config.swap.clone());
+
+        let mut node = self.load_leaf(layouter.namespace(|| "leaf"), leaf)?;
+        for (sibling, bit) in path.iter().zip(bits) {
+            let (left, right) =
+                swap_chip.swap(layouter.namespace(|| "swap"), &node, *sibling, *bit)?;
+            node = self.hash(layouter.namespace(|| "hash"), &left, &right)?;
+        }
+        Ok(node)
+    }
+
+    pub fn expose_public(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cell: &AssignedCell<F, F>,
+        row: usize,
+    ) -> Result<(), Error> {
+        layouter.constrain_instance(cell.cell(), self.config.instance, row)
+    }
+}
+
+#[derive(Default)]
+struct MyCircuit<F> {
+    pub leaf: Option<F>,
+    // siblings from the bottom level up
+    pub path: Vec<Option<F>>,
+    // 0 if the node at that level is the left child, 1 if it's the right child
+    pub bits: Vec<Option<F>>,
+}
+
+impl<F: FieldExt> Circuit<F> for MyCircuit<F> {
+    type Config = MerkleConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        // the depth of the tree decides the layout
+        Self {
+            leaf: None,
+            path: vec![None; self.path.len()],
+            bits: vec![None; self.bits.len()],
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let advice = [
+            meta.advice_column(),
+            meta.advice_column(),
+            meta.advice_column(),
+            meta.advice_column(),
+            meta.advice_column(),
+        ];
+        let instance = meta.instance_column();
+
+        MerkleChip::configure(meta, advice, instance)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let chip = MerkleChip::construct(config);
+
+        let root = chip.compute_root(
+            layouter.namespace(|| "merkle path"),
+            self.leaf,
+            &self.path,
+            &self.bits,
+        )?;
+        chip.expose_public(layouter.namespace(|| "root"), &root, 0)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{hash, MyCircuit};
+    use halo2_proofs::{dev::MockProver, pasta::Fp};
+
+    // builds a depth-3 tree over 8 leaves and returns the root and the path of leaf `index`
+    fn merkle_path(leaves: &[Fp], index: usize) -> (Fp, Vec<Fp>, Vec<Fp>) {
+        let mut level = leaves.to_vec();
+        let mut index = index;
+        let mut path = vec![];
+        let mut bits = vec![];
+
+        while level.len() > 1 {
+            path.push(level[index ^ 1]);
+            bits.push(Fp::from((index & 1) as u64));
+
+            level = level.chunks(2).map(|pair| hash(pair[0], pair[1])).collect();
+            index /= 2;
+        }
+        (level[0], path, bits)
+    }
+
+    #[test]
+    fn test_merkle() {
+        let k = 4;
+
+        let leaves: Vec<Fp> = (0..8).map(|i| Fp::from(i + 10)).collect();
+        for index in [0, 5] {
+            let (root, path, bits) = merkle_path(&leaves, index);
+            let circuit = MyCircuit {
+                leaf: Some(leaves[index]),
+                path: path.into_iter().map(Some).collect(),
+                bits: bits.into_iter().map(Some).collect(),
+            };
+
+            let public_input = vec![root];
+            let prover = MockProver::run(k, &circuit, vec![public_input]).unwrap();
+            prover.assert_satisfied();
+        }
+    }
+
+    #[test]
+    fn test_merkle_failed() {
+        let k = 4;
+
+        let leaves: Vec<Fp> = (0..8).map(|i| Fp::from(i + 10)).collect();
+        let (root, mut path, bits) = merkle_path(&leaves, 5);
+        // wrong sibling at the middle level
+        path[1] += Fp::one();
+
+        let circuit = MyCircuit {
+            leaf: Some(leaves[5]),
+            path: path.into_iter().map(Some).collect(),
+            bits: bits.into_iter().map(Some).collect(),
+        };
+
+        let public_input = vec![root];
+        let prover = MockProver::run(k, &circuit, vec![public_input]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn test_merkle_length_mismatch() {
+        let k = 4;
+
+        let leaves: Vec<Fp> = (0..8).map(|i| Fp::from(i + 10)).collect();
+        let (root, path, mut bits) = merkle_path(&leaves, 5);
+        // no bit for the top level
+        bits.pop();
+
+        let circuit = MyCircuit {
+            leaf: Some(leaves[5]),
+            path: path.into_iter().map(Some).collect(),
+            bits: bits.into_iter().map(Some).collect(),
+        };
+
+        let public_input = vec![root];
+        assert!(MockProver::run(k, &circuit, vec![public_input]).is_err());
+    }
+}
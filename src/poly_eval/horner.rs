@@ -0,0 +1,227 @@
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+use crate::error::CircuitError;
+
+//
+// selector |  acc  | x |   c
+// ---------+-------+---+-------
+//    1     |  c2   | x |  c1
+//    1     |  acc1 | x |  c0      acc1 = c2 * x + c1
+//    0     |  acc2 | x |          acc2 = acc1 * x + c0 = p(x)
+//
+// Evaluates p(x) = c0 + c1 * x + ... + cn * x^n with Horner's rule,
+//   p(x) = (...((cn * x + cn-1) * x + cn-2) ...) * x + c0
+// one multiply-add per row:
+//   s * (acc * x + c - acc_next) = 0
+// x is witnessed once in the first row and copied into the others, so the
+// same x is used all the way down.
+
+#[derive(Debug, Clone)]
+struct HornerConfig {
+    pub acc: Column<Advice>,
+    pub x: Column<Advice>,
+    pub c: Column<Advice>,
+    pub instance: Column<Instance>,
+    pub selector: Selector,
+}
+
+struct HornerChip<F: FieldExt> {
+    config: HornerConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> HornerChip<F> {
+    pub fn construct(config: HornerConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        acc: Column<Advice>,
+        x: Column<Advice>,
+        c: Column<Advice>,
+        instance: Column<Instance>,
+    ) -> HornerConfig {
+        let selector = meta.selector();
+
+        meta.enable_equality(acc);
+        meta.enable_equality(x);
+        meta.enable_equality(instance);
+
+        meta.create_gate("horner", |meta| {
+            let s = meta.query_selector(selector);
+            let acc_next = meta.query_advice(acc, Rotation::next());
+            let acc = meta.query_advice(acc, Rotation::cur());
+            let x = meta.query_advice(x, Rotation::cur());
+            let c = meta.query_advice(c, Rotation::cur());
+            vec![s * (acc * x + c - acc_next)]
+        });
+
+        HornerConfig {
+            acc,
+            x,
+            c,
+            instance,
+            selector,
+        }
+    }
+
+    // coeffs are c0..cn, returns the cell holding p(x)
+    pub fn evaluate(
+        &self,
+        mut layouter: impl Layouter<F>,
+        coeffs: &[Option<F>],
+        x: Option<F>,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        assert!(!coeffs.is_empty(), "the polynomial needs a coefficient");
+
+        layouter.assign_region(
+            || "horner",
+            |mut region| {
+                // from the highest coefficient down
+                let mut coeffs = coeffs.iter().rev();
+
+                let top = coeffs.next().unwrap();
+                let mut acc_cell = region.assign_advice(
+                    || "acc",
+                    self.config.acc,
+                    0,
+                    || top.ok_or(CircuitError::MissingWitness.into()),
+                )?;
+                let x_cell = region.assign_advice(
+                    || "x",
+                    self.config.x,
+                    0,
+                    || x.ok_or(CircuitError::MissingWitness.into()),
+                )?;
+
+                for (row, c) in coeffs.enumerate() {
+                    self.config.selector.enable(&mut region, row)?;
+
+                    if row > 0 {
+                        x_cell.copy_advice(|| "x", &mut region, self.config.x, row)?;
+                    }
+                    region.assign_advice(
+                        || "c",
+                        self.config.c,
+                        row,
+                        || c.ok_or(CircuitError::MissingWitness.into()),
+                    )?;
+
+                    let acc = acc_cell
+                        .value()
+                        .and_then(|acc| x.zip(*c).map(|(x, c)| *acc * x + c));
+                    acc_cell = region.assign_advice(
+                        || "acc",
+                        self.config.acc,
+                        row + 1,
+                        || acc.ok_or(CircuitError::MissingWitness.into()),
+                    )?;
+                }
+                Ok(acc_cell)
+            },
+        )
+    }
+
+    pub fn expose_public(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cell: &AssignedCell<F, F>,
+        row: usize,
+    ) -> Result<(), Error> {
+        layouter.constrain_instance(cell.cell(), self.config.instance, row)
+    }
+}
+
+#[derive(Default)]
+struct MyCircuit<F> {
+    // c0..cn
+    pub coeffs: Vec<Option<F>>,
+    pub x: Option<F>,
+}
+
+impl<F: FieldExt> Circuit<F> for MyCircuit<F> {
+    type Config = HornerConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        // the degree decides the layout
+        Self {
+            coeffs: vec![None; self.coeffs.len()],
+            x: None,
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let acc = meta.advice_column();
+        let x = meta.advice_column();
+        let c = meta.advice_column();
+        let instance = meta.instance_column();
+
+        HornerChip::configure(meta, acc, x, c, instance)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let chip = HornerChip::construct(config);
+
+        let out = chip.evaluate(layouter.namespace(|| "p(x)"), &self.coeffs, self.x)?;
+        chip.expose_public(layouter.namespace(|| "out"), &out, 0)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MyCircuit;
+    use halo2_proofs::{dev::MockProver, pasta::Fp};
+
+    // p(x) = 1 + 2x + 3x^2
+    fn circuit(x: u64) -> MyCircuit<Fp> {
+        MyCircuit {
+            coeffs: [1, 2, 3].iter().map(|c| Some(Fp::from(*c))).collect(),
+            x: Some(Fp::from(x)),
+        }
+    }
+
+    #[test]
+    fn test_horner() {
+        let k = 4;
+
+        // 1 + 2 * 2 + 3 * 4
+        let public_input = vec![Fp::from(17)];
+        let prover = MockProver::run(k, &circuit(2), vec![public_input]).unwrap();
+        prover.assert_satisfied();
+    }
+
+    #[test]
+    fn test_horner_failed() {
+        let k = 4;
+
+        let public_input = vec![Fp::from(18)];
+        let prover = MockProver::run(k, &circuit(2), vec![public_input]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[cfg(feature = "dev-graph")]
+    #[test]
+    fn plot_horner() {
+        use plotters::prelude::*;
+
+        let root = BitMapBackend::new("horner-layout.png", (1024, 3096)).into_drawing_area();
+        root.fill(&WHITE).unwrap();
+        let root = root.titled("Horner Layout", ("sans-serif", 60)).unwrap();
+
+        halo2_proofs::dev::CircuitLayout::default()
+            .render(4, &circuit(2), &root)
+            .unwrap();
+    }
+}
@@ -0,0 +1,2 @@
+pub(crate) mod example;
+pub(crate) mod lookup;
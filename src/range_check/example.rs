@@ -0,0 +1,175 @@
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+use crate::error::CircuitError;
+
+//
+// selector | value |
+// ---------+-------|
+//   s0     |   v   |
+//
+// The value v is in [0, RANGE) iff
+//   v * (1 - v) * (2 - v) * ... * (RANGE - 1 - v) = 0
+// Note that the degree of the gate grows with RANGE, so this only suits small ranges.
+
+#[derive(Debug, Clone)]
+pub(crate) struct RangeCheckConfig<const RANGE: usize> {
+    pub value: Column<Advice>,
+    pub selector: Selector,
+}
+
+pub(crate) struct RangeCheckChip<F: FieldExt, const RANGE: usize> {
+    config: RangeCheckConfig<RANGE>,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt, const RANGE: usize> RangeCheckChip<F, RANGE> {
+    pub fn construct(config: RangeCheckConfig<RANGE>) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        value: Column<Advice>,
+    ) -> RangeCheckConfig<RANGE> {
+        let selector = meta.selector();
+
+        // so that cells from other chips can be copied in, see `check`
+        meta.enable_equality(value);
+
+        meta.create_gate("range check", |meta| {
+            let s = meta.query_selector(selector);
+            let v = meta.query_advice(value, Rotation::cur());
+
+            // v * (1 - v) * (2 - v) * ... * (RANGE - 1 - v)
+            let range_check = (1..RANGE).fold(v.clone(), |expr, i| {
+                expr * (Expression::Constant(F::from(i as u64)) - v.clone())
+            });
+            vec![s * range_check]
+        });
+
+        RangeCheckConfig { value, selector }
+    }
+
+    pub fn assign(
+        &self,
+        mut layouter: impl Layouter<F>,
+        value: Option<F>,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        layouter.assign_region(
+            || "range check",
+            |mut region| {
+                self.config.selector.enable(&mut region, 0)?;
+                region.assign_advice(
+                    || "value",
+                    self.config.value,
+                    0,
+                    || value.ok_or(CircuitError::MissingWitness.into()),
+                )
+            },
+        )
+    }
+
+    // same as `assign`, but range checks a cell assigned elsewhere
+    pub fn check(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cell: &AssignedCell<F, F>,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        layouter.assign_region(
+            || "range check",
+            |mut region| {
+                self.config.selector.enable(&mut region, 0)?;
+                cell.copy_advice(|| "value", &mut region, self.config.value, 0)
+            },
+        )
+    }
+}
+
+#[derive(Default)]
+struct MyCircuit<F, const RANGE: usize> {
+    pub value: Option<F>,
+}
+
+impl<F: FieldExt, const RANGE: usize> Circuit<F> for MyCircuit<F, RANGE> {
+    type Config = RangeCheckConfig<RANGE>;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let value = meta.advice_column();
+        RangeCheckChip::configure(meta, value)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let chip = RangeCheckChip::<F, RANGE>::construct(config);
+        chip.assign(layouter.namespace(|| "value"), self.value)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MyCircuit;
+    use halo2_proofs::{dev::MockProver, pasta::Fp};
+
+    const RANGE: usize = 8;
+
+    #[test]
+    fn test_range_check() {
+        let k = 4;
+
+        for i in 0..RANGE {
+            let circuit = MyCircuit::<Fp, RANGE> {
+                value: Some(Fp::from(i as u64)),
+            };
+
+            let prover = MockProver::run(k, &circuit, vec![]).unwrap();
+            prover.assert_satisfied();
+        }
+    }
+
+    #[test]
+    fn test_range_check_failed() {
+        let k = 4;
+
+        for i in [RANGE, RANGE + 1, 100] {
+            let circuit = MyCircuit::<Fp, RANGE> {
+                value: Some(Fp::from(i as u64)),
+            };
+
+            let prover = MockProver::run(k, &circuit, vec![]).unwrap();
+            assert!(prover.verify().is_err());
+        }
+    }
+
+    #[cfg(feature = "dev-graph")]
+    #[test]
+    fn plot_range_check() {
+        use plotters::prelude::*;
+
+        let root = BitMapBackend::new("range-check-layout.png", (1024, 3096)).into_drawing_area();
+        root.fill(&WHITE).unwrap();
+        let root = root
+            .titled("Range Check Layout", ("sans-serif", 60))
+            .unwrap();
+
+        let circuit = MyCircuit::<Fp, RANGE> {
+            value: Some(Fp::from(3)),
+        };
+        halo2_proofs::dev::CircuitLayout::default()
+            .render(4, &circuit, &root)
+            .unwrap();
+    }
+}
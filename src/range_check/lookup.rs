@@ -0,0 +1,226 @@
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+use crate::error::CircuitError;
+
+//
+// q_lookup | value |  table  |
+// ---------+-------+---------|
+//    1     |   v   |    0    |
+//          |       |    1    |
+//          |       |   ...   |
+//          |       | RANGE-1 |
+//
+// Instead of a gate of degree RANGE (see example.rs), the value is looked up
+// in a table holding 0..RANGE, so the degree stays constant no matter how
+// large the range is. The cost is the table itself, which needs RANGE rows.
+
+#[derive(Debug, Clone)]
+pub(crate) struct RangeTableConfig<F: FieldExt, const RANGE: usize> {
+    pub value: TableColumn,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt, const RANGE: usize> RangeTableConfig<F, RANGE> {
+    pub fn configure(meta: &mut ConstraintSystem<F>) -> Self {
+        let value = meta.lookup_table_column();
+
+        Self {
+            value,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn load(&self, layouter: &mut impl Layouter<F>) -> Result<(), Error> {
+        layouter.assign_table(
+            || "load range table",
+            |mut table| {
+                for (offset, value) in (0..RANGE).enumerate() {
+                    table.assign_cell(
+                        || "value",
+                        self.value,
+                        offset,
+                        || Ok(F::from(value as u64)),
+                    )?;
+                }
+                Ok(())
+            },
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct LookupRangeCheckConfig<F: FieldExt, const RANGE: usize, const NUM_BITS: usize> {
+    pub value: Column<Advice>,
+    pub q_lookup: Selector,
+    pub table: RangeTableConfig<F, RANGE>,
+}
+
+pub(crate) struct LookupRangeCheckChip<F: FieldExt, const RANGE: usize, const NUM_BITS: usize> {
+    config: LookupRangeCheckConfig<F, RANGE, NUM_BITS>,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt, const RANGE: usize, const NUM_BITS: usize>
+    LookupRangeCheckChip<F, RANGE, NUM_BITS>
+{
+    pub fn construct(config: LookupRangeCheckConfig<F, RANGE, NUM_BITS>) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        value: Column<Advice>,
+    ) -> LookupRangeCheckConfig<F, RANGE, NUM_BITS> {
+        // NUM_BITS is the bit width of the checked values, so the table can't be any larger
+        assert!(RANGE <= 1 << NUM_BITS);
+
+        // selectors used in lookups must be complex selectors
+        let q_lookup = meta.complex_selector();
+        let table = RangeTableConfig::configure(meta);
+
+        // so that cells from other chips can be copied in, see `check`
+        meta.enable_equality(value);
+
+        meta.lookup(|meta| {
+            let q = meta.query_selector(q_lookup);
+            let v = meta.query_advice(value, Rotation::cur());
+
+            // when the selector is off we look up 0, which is always in the table
+            vec![(q * v, table.value)]
+        });
+
+        LookupRangeCheckConfig {
+            value,
+            q_lookup,
+            table,
+        }
+    }
+
+    pub fn assign(
+        &self,
+        mut layouter: impl Layouter<F>,
+        value: Option<F>,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        layouter.assign_region(
+            || "lookup range check",
+            |mut region| {
+                self.config.q_lookup.enable(&mut region, 0)?;
+                region.assign_advice(
+                    || "value",
+                    self.config.value,
+                    0,
+                    || value.ok_or(CircuitError::MissingWitness.into()),
+                )
+            },
+        )
+    }
+
+    // same as `assign`, but range checks a cell assigned elsewhere
+    pub fn check(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cell: &AssignedCell<F, F>,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        layouter.assign_region(
+            || "lookup range check",
+            |mut region| {
+                self.config.q_lookup.enable(&mut region, 0)?;
+                cell.copy_advice(|| "value", &mut region, self.config.value, 0)
+            },
+        )
+    }
+}
+
+#[derive(Default)]
+struct MyCircuit<F, const RANGE: usize, const NUM_BITS: usize> {
+    pub value: Option<F>,
+}
+
+impl<F: FieldExt, const RANGE: usize, const NUM_BITS: usize> Circuit<F>
+    for MyCircuit<F, RANGE, NUM_BITS>
+{
+    type Config = LookupRangeCheckConfig<F, RANGE, NUM_BITS>;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let value = meta.advice_column();
+        LookupRangeCheckChip::configure(meta, value)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        config.table.load(&mut layouter)?;
+
+        let chip = LookupRangeCheckChip::<F, RANGE, NUM_BITS>::construct(config);
+        chip.assign(layouter.namespace(|| "value"), self.value)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MyCircuit;
+    use halo2_proofs::{dev::MockProver, pasta::Fp};
+
+    const RANGE: usize = 256;
+    const NUM_BITS: usize = 8;
+
+    #[test]
+    fn test_lookup_range_check() {
+        // the table alone takes 256 rows
+        let k = 9;
+
+        for i in [0, 1, 100, 255] {
+            let circuit = MyCircuit::<Fp, RANGE, NUM_BITS> {
+                value: Some(Fp::from(i)),
+            };
+
+            let prover = MockProver::run(k, &circuit, vec![]).unwrap();
+            prover.assert_satisfied();
+        }
+    }
+
+    #[test]
+    fn test_lookup_range_check_failed() {
+        let k = 9;
+
+        let circuit = MyCircuit::<Fp, RANGE, NUM_BITS> {
+            value: Some(Fp::from(300)),
+        };
+
+        let prover = MockProver::run(k, &circuit, vec![]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[cfg(feature = "dev-graph")]
+    #[test]
+    fn plot_lookup_range_check() {
+        use plotters::prelude::*;
+
+        let root =
+            BitMapBackend::new("lookup-range-check-layout.png", (1024, 3096)).into_drawing_area();
+        root.fill(&WHITE).unwrap();
+        let root = root
+            .titled("Lookup Range Check Layout", ("sans-serif", 60))
+            .unwrap();
+
+        let circuit = MyCircuit::<Fp, RANGE, NUM_BITS> {
+            value: Some(Fp::from(3)),
+        };
+        halo2_proofs::dev::CircuitLayout::default()
+            .render(9, &circuit, &root)
+            .unwrap();
+    }
+}
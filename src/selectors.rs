@@ -0,0 +1,2 @@
+mod example;
+mod shared;
@@ -0,0 +1,236 @@
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+use crate::error::CircuitError;
+
+//
+// s_add | s_copy |  a  |  b  |  c
+// ------+--------+-----+-----+-----
+//   1   |   0    |  2  |  3  |  5     c = a + b
+//   0   |   1    |  7  | any |  7     c = a
+//   0   |   0    | any | any | any    nothing checked
+//   1   |   1    |  4  |  0  |  4     both, so b has to be 0
+//
+// One gate with a constraint per selector:
+//   s_add  * (a + b - c) = 0
+//   s_copy * (a - c) = 0
+// Each constraint is multiplied by its own selector, so turning one selector on
+// in a row activates only its part of the gate; a row with neither is free,
+// and a row with both has to satisfy the two of them.
+//
+// meta.selector() vs meta.complex_selector(): halo2 may combine simple
+// selectors into fewer fixed columns, which is only sound when the selector is
+// just a factor multiplying a whole constraint, like here. A selector that is
+// used in any other way, e.g. inside a lookup expression (see
+// range_check/lookup.rs), must be a complex selector so that it keeps a column
+// of its own.
+
+#[derive(Debug, Clone)]
+struct SelectorsConfig {
+    pub advice: [Column<Advice>; 3],
+    pub s_add: Selector,
+    pub s_copy: Selector,
+}
+
+struct SelectorsChip<F: FieldExt> {
+    config: SelectorsConfig,
+    _marker: PhantomData<F>,
+}
+
+// the values of a row, and which selectors are on in it
+#[derive(Clone, Copy, Default)]
+struct Row<F> {
+    pub a: Option<F>,
+    pub b: Option<F>,
+    pub c: Option<F>,
+    pub add: bool,
+    pub copy: bool,
+}
+
+impl<F: FieldExt> SelectorsChip<F> {
+    pub fn construct(config: SelectorsConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+    ) -> SelectorsConfig {
+        let [col_a, col_b, col_c] = advice;
+        let s_add = meta.selector();
+        let s_copy = meta.selector();
+
+        meta.create_gate("add or copy", |meta| {
+            let s_add = meta.query_selector(s_add);
+            let s_copy = meta.query_selector(s_copy);
+            let a = meta.query_advice(col_a, Rotation::cur());
+            let b = meta.query_advice(col_b, Rotation::cur());
+            let c = meta.query_advice(col_c, Rotation::cur());
+            vec![
+                ("add", s_add * (a.clone() + b - c.clone())),
+                ("copy", s_copy * (a - c)),
+            ]
+        });
+
+        SelectorsConfig {
+            advice,
+            s_add,
+            s_copy,
+        }
+    }
+
+    pub fn assign(&self, mut layouter: impl Layouter<F>, rows: &[Row<F>]) -> Result<(), Error> {
+        layouter.assign_region(
+            || "rows",
+            |mut region| {
+                for (offset, row) in rows.iter().enumerate() {
+                    if row.add {
+                        self.config.s_add.enable(&mut region, offset)?;
+                    }
+                    if row.copy {
+                        self.config.s_copy.enable(&mut region, offset)?;
+                    }
+
+                    for (column, value) in self.config.advice.iter().zip([row.a, row.b, row.c]) {
+                        region.assign_advice(
+                            || "value",
+                            *column,
+                            offset,
+                            || value.ok_or(CircuitError::MissingWitness.into()),
+                        )?;
+                    }
+                }
+                Ok(())
+            },
+        )
+    }
+}
+
+#[derive(Default)]
+struct MyCircuit<F> {
+    pub rows: Vec<Row<F>>,
+}
+
+impl<F: FieldExt> Circuit<F> for MyCircuit<F> {
+    type Config = SelectorsConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        // the selectors decide the layout
+        Self {
+            rows: self
+                .rows
+                .iter()
+                .map(|row| Row {
+                    add: row.add,
+                    copy: row.copy,
+                    ..Row::default()
+                })
+                .collect(),
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let advice = [
+            meta.advice_column(),
+            meta.advice_column(),
+            meta.advice_column(),
+        ];
+        SelectorsChip::configure(meta, advice)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let chip = SelectorsChip::construct(config);
+        chip.assign(layouter.namespace(|| "rows"), &self.rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MyCircuit, Row};
+    use crate::testing::assert_fails_with;
+    use halo2_proofs::{dev::MockProver, pasta::Fp};
+
+    fn row(a: u64, b: u64, c: u64, add: bool, copy: bool) -> Row<Fp> {
+        Row {
+            a: Some(Fp::from(a)),
+            b: Some(Fp::from(b)),
+            c: Some(Fp::from(c)),
+            add,
+            copy,
+        }
+    }
+
+    #[test]
+    fn test_selectors() {
+        let k = 4;
+
+        let circuit = MyCircuit {
+            rows: vec![
+                row(2, 3, 5, true, false),
+                row(7, 9, 7, false, true),
+                row(1, 1, 100, false, false),
+                row(4, 0, 4, true, true),
+            ],
+        };
+
+        let prover = MockProver::run(k, &circuit, vec![]).unwrap();
+        prover.assert_satisfied();
+    }
+
+    #[test]
+    fn test_selectors_add_only() {
+        let k = 4;
+
+        // a copy row under the add selector
+        let circuit = MyCircuit {
+            rows: vec![row(7, 9, 7, true, false)],
+        };
+
+        let prover = MockProver::run(k, &circuit, vec![]).unwrap();
+        assert_fails_with(&prover, "add");
+        assert!(prover
+            .verify()
+            .unwrap_err()
+            .iter()
+            .all(|failure| !failure.to_string().contains("('copy')")));
+    }
+
+    #[test]
+    fn test_selectors_copy_only() {
+        let k = 4;
+
+        // an add row under the copy selector
+        let circuit = MyCircuit {
+            rows: vec![row(2, 3, 5, false, true)],
+        };
+
+        let prover = MockProver::run(k, &circuit, vec![]).unwrap();
+        assert_fails_with(&prover, "copy");
+        assert!(prover
+            .verify()
+            .unwrap_err()
+            .iter()
+            .all(|failure| !failure.to_string().contains("('add')")));
+    }
+
+    #[test]
+    fn test_selectors_both_failed() {
+        let k = 4;
+
+        // fine for add alone, but c != a
+        let circuit = MyCircuit {
+            rows: vec![row(2, 3, 5, true, true)],
+        };
+
+        let prover = MockProver::run(k, &circuit, vec![]).unwrap();
+        assert_fails_with(&prover, "copy");
+    }
+}
@@ -0,0 +1,276 @@
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+use crate::error::CircuitError;
+use crate::fibonacci::ColumnSummary;
+
+//
+// q (complex) |  a  |  b  |   sum   | product
+// ------------+-----+-----+---------+---------
+//      1      | a0  | b0  | a0 + b0 | a0 * b0
+//      1      | a1  | b1  | a1 + b1 | a1 * b1
+//
+// Two gates under the same selector:
+//   "add": q * (a + b - sum) = 0
+//   "mul": q * (a * b - product) = 0
+// A selector isn't tied to a gate, any number of gates can query it. One
+// complex selector here is one fixed column at keygen, whatever the rows.
+//
+// With a selector per gate (SHARED = false), both are on in the same rows, and
+// halo2 can only combine simple selectors that are never on in the same row:
+// they'd be merged into one fixed column holding 1 where the first is on, 2
+// where the second is, and so on. So the two selectors stay two columns, one
+// more than the shared one (see test_shared_selector_columns).
+//
+// A simple selector shared by the two gates would also take a single column.
+// The complex one is needed when the selector is used as more than a factor of
+// whole constraints, e.g. in a lookup (range_check/lookup.rs): a combined
+// column holds other values than 0 and 1, which would end up in the lookup.
+// A complex selector is never combined, its column only holds 0s and 1s.
+
+#[derive(Debug, Clone)]
+struct SharedConfig {
+    pub advice: [Column<Advice>; 4],
+    // the same complex selector when SHARED, two simple selectors otherwise
+    pub q_add: Selector,
+    pub q_mul: Selector,
+}
+
+impl SharedConfig {
+    pub fn column_summary(&self) -> ColumnSummary {
+        ColumnSummary::of(&self.advice, &[], &[], &[self.q_add, self.q_mul])
+    }
+}
+
+struct SharedChip<F: FieldExt, const SHARED: bool> {
+    config: SharedConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt, const SHARED: bool> SharedChip<F, SHARED> {
+    pub fn construct(config: SharedConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(meta: &mut ConstraintSystem<F>, advice: [Column<Advice>; 4]) -> SharedConfig {
+        let [col_a, col_b, col_sum, col_product] = advice;
+        let (q_add, q_mul) = if SHARED {
+            let q = meta.complex_selector();
+            (q, q)
+        } else {
+            (meta.selector(), meta.selector())
+        };
+
+        meta.create_gate("add", |meta| {
+            let q = meta.query_selector(q_add);
+            let a = meta.query_advice(col_a, Rotation::cur());
+            let b = meta.query_advice(col_b, Rotation::cur());
+            let sum = meta.query_advice(col_sum, Rotation::cur());
+            vec![q * (a + b - sum)]
+        });
+
+        meta.create_gate("mul", |meta| {
+            let q = meta.query_selector(q_mul);
+            let a = meta.query_advice(col_a, Rotation::cur());
+            let b = meta.query_advice(col_b, Rotation::cur());
+            let product = meta.query_advice(col_product, Rotation::cur());
+            vec![q * (a * b - product)]
+        });
+
+        SharedConfig {
+            advice,
+            q_add,
+            q_mul,
+        }
+    }
+
+    // a row per pair, with its sum and product
+    pub fn assign(
+        &self,
+        mut layouter: impl Layouter<F>,
+        pairs: &[(Option<F>, Option<F>)],
+    ) -> Result<(), Error> {
+        layouter.assign_region(
+            || "sum and product",
+            |mut region| {
+                for (offset, (a, b)) in pairs.iter().enumerate() {
+                    self.config.q_add.enable(&mut region, offset)?;
+                    if !SHARED {
+                        self.config.q_mul.enable(&mut region, offset)?;
+                    }
+
+                    let sum = a.zip(*b).map(|(a, b)| a + b);
+                    let product = a.zip(*b).map(|(a, b)| a * b);
+                    for (column, value) in self.config.advice.iter().zip([*a, *b, sum, product]) {
+                        region.assign_advice(
+                            || "value",
+                            *column,
+                            offset,
+                            || value.ok_or(CircuitError::MissingWitness.into()),
+                        )?;
+                    }
+                }
+                Ok(())
+            },
+        )
+    }
+}
+
+#[derive(Debug, Default)]
+struct MyCircuit<F, const SHARED: bool> {
+    pub pairs: Vec<(Option<F>, Option<F>)>,
+}
+
+impl<F: FieldExt, const SHARED: bool> Circuit<F> for MyCircuit<F, SHARED> {
+    type Config = SharedConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            pairs: vec![(None, None); self.pairs.len()],
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let advice = [
+            meta.advice_column(),
+            meta.advice_column(),
+            meta.advice_column(),
+            meta.advice_column(),
+        ];
+        SharedChip::<F, SHARED>::configure(meta, advice)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let chip = SharedChip::<F, SHARED>::construct(config);
+        chip.assign(layouter.namespace(|| "pairs"), &self.pairs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MyCircuit, SharedConfig};
+    use crate::fibonacci::ColumnSummary;
+    use crate::testing::assert_fails_with;
+    use halo2_proofs::{
+        circuit::*,
+        dev::{CircuitCost, MockProver},
+        pasta::{Eq, Fp},
+        plonk::*,
+    };
+
+    // the sum in the product column, under the selector of both gates
+    struct WrongProductCircuit;
+
+    impl Circuit<Fp> for WrongProductCircuit {
+        type Config = SharedConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            MyCircuit::<Fp, true>::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            layouter.assign_region(
+                || "wrong product",
+                |mut region| {
+                    config.q_add.enable(&mut region, 0)?;
+                    for (column, value) in config.advice.iter().zip([2, 3, 5, 5]) {
+                        region.assign_advice(|| "value", *column, 0, || Ok(Fp::from(value)))?;
+                    }
+                    Ok(())
+                },
+            )
+        }
+    }
+
+    fn circuit<const SHARED: bool>(pairs: &[(u64, u64)]) -> MyCircuit<Fp, SHARED> {
+        MyCircuit {
+            pairs: pairs
+                .iter()
+                .map(|(a, b)| (Some(Fp::from(*a)), Some(Fp::from(*b))))
+                .collect(),
+        }
+    }
+
+    // the fixed columns after keygen has turned the selectors into columns:
+    // each is queried once, at the current row, and CircuitCost counts the
+    // queries after combining the selectors (through its Debug output only)
+    fn fixed_columns<C: Circuit<Fp> + std::fmt::Debug>(circuit: &C) -> usize {
+        let cost = format!("{:?}", CircuitCost::<Eq, C>::measure(4, circuit));
+        let count = cost
+            .split("fixed_queries: ")
+            .nth(1)
+            .and_then(|rest| rest.split(',').next())
+            .expect("CircuitCost prints fixed_queries");
+        count.parse().unwrap()
+    }
+
+    fn summary<const SHARED: bool>() -> ColumnSummary {
+        let mut meta = ConstraintSystem::<Fp>::default();
+        MyCircuit::<Fp, SHARED>::configure(&mut meta).column_summary()
+    }
+
+    #[test]
+    fn test_shared_selector() {
+        let k = 4;
+
+        let prover = MockProver::run(k, &circuit::<true>(&[(2, 3), (4, 5)]), vec![]).unwrap();
+        prover.assert_satisfied();
+
+        // the same rows with a selector per gate
+        let prover = MockProver::run(k, &circuit::<false>(&[(2, 3), (4, 5)]), vec![]).unwrap();
+        prover.assert_satisfied();
+    }
+
+    #[test]
+    fn test_shared_selector_failed() {
+        let k = 4;
+
+        let prover = MockProver::run(k, &WrongProductCircuit, vec![]).unwrap();
+        assert_fails_with(&prover, "mul");
+    }
+
+    #[test]
+    fn test_shared_selector_columns() {
+        assert_eq!(
+            summary::<true>(),
+            ColumnSummary {
+                advice: 4,
+                fixed: 0,
+                instance: 0,
+                selectors: 1,
+            }
+        );
+        assert_eq!(
+            summary::<false>(),
+            ColumnSummary {
+                advice: 4,
+                fixed: 0,
+                instance: 0,
+                selectors: 2,
+            }
+        );
+
+        // and after keygen: the two simple selectors are on in the same rows,
+        // so they can't be combined into one column
+        let pairs = [(2, 3), (4, 5)];
+        assert_eq!(fixed_columns(&circuit::<true>(&pairs)), 1);
+        assert_eq!(fixed_columns(&circuit::<false>(&pairs)), 2);
+    }
+}
@@ -0,0 +1,656 @@
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    dev::{CircuitCost, CircuitGates, MockProver, VerifyFailure},
+    pasta::{Eq, Fp},
+    plonk::{
+        Advice, Any, Assigned, Assignment, Circuit, Column, ConstraintSystem, Error, Fixed,
+        FloorPlanner, Instance, Selector,
+    },
+};
+use std::{fmt::Debug, ops::Range};
+
+// Asserts that the circuit fails to verify, and that at least one of the
+// failures comes from the gate (or the constraint) named `gate_name`.
+//
+// `prover.verify()` returns every failure found, e.g. for a wrong sum in the
+// Fibonacci examples:
+//   ConstraintNotSatisfied { constraint: Constraint 0 in gate 0 ('add'), location, cell_values }
+// so a test can check *why* the circuit failed instead of only that it failed.
+pub fn assert_fails_with(prover: &MockProver<Fp>, gate_name: &str) {
+    let failures = match prover.verify() {
+        Ok(()) => panic!("expected '{}' to fail, but the circuit verified", gate_name),
+        Err(failures) => failures,
+    };
+
+    // both gates and constraints are displayed as "... ('name')"
+    let name = format!("('{}')", gate_name);
+    let found = failures.iter().any(|failure| match failure {
+        VerifyFailure::ConstraintNotSatisfied { constraint, .. }
+        | VerifyFailure::ConstraintPoisoned { constraint } => {
+            constraint.to_string().contains(&name)
+        }
+        VerifyFailure::CellNotAssigned { gate, .. } => gate.to_string().contains(&name),
+        _ => false,
+    });
+    assert!(
+        found,
+        "no failure references '{}', got: {:#?}",
+        gate_name, failures
+    );
+}
+
+// The negative counterpart of `prover.assert_satisfied()`: runs MockProver and
+// asserts that the circuit does NOT verify with these witnesses and public
+// inputs. Use assert_fails_with instead when the failing gate is known.
+pub fn assert_unsatisfied<ConcreteCircuit: Circuit<Fp>>(
+    k: u32,
+    circuit: &ConcreteCircuit,
+    publics: Vec<Vec<Fp>>,
+) {
+    let prover = MockProver::run(k, circuit, publics).unwrap();
+    assert!(
+        prover.verify().is_err(),
+        "expected the circuit to be unsatisfied, but it verified"
+    );
+}
+
+// Prints what the circuit costs, without rendering a layout:
+// - the gates with their constraints, and the total count of each kind of operation
+// - the columns, queries, lookups and permutation columns
+// - the size of a proof with one instance of the circuit, in bytes
+// The counts are in the returned CircuitCost too (they're only readable
+// through its Debug output in halo2_proofs 0.1.0).
+pub fn report_cost<ConcreteCircuit: Circuit<Fp> + Debug>(
+    k: u32,
+    circuit: &ConcreteCircuit,
+) -> CircuitCost<Eq, ConcreteCircuit> {
+    let cost = CircuitCost::<Eq, ConcreteCircuit>::measure(k as usize, circuit);
+
+    println!("{}", CircuitGates::collect::<Fp, ConcreteCircuit>());
+    println!("{:#?}", cost);
+    println!("proof size: {} bytes", usize::from(cost.proof_size(1)));
+
+    cost
+}
+
+// Fails if the circuit doesn't have exactly the expected number of advice
+// columns and gates, so that an extra column or gate added by accident shows
+// up as a test failure. Both counts are parsed from the same output as
+// report_cost, as CircuitCost doesn't expose its fields.
+pub fn assert_cost<ConcreteCircuit: Circuit<Fp> + Debug>(
+    k: u32,
+    circuit: &ConcreteCircuit,
+    expected_advice: usize,
+    expected_gates: usize,
+) {
+    let cost = CircuitCost::<Eq, ConcreteCircuit>::measure(k as usize, circuit);
+    let advice = format!("{:?}", cost);
+    assert!(
+        advice.contains(&format!("advice_columns: {},", expected_advice)),
+        "expected {} advice columns, got: {}",
+        expected_advice,
+        advice
+    );
+
+    let gates = CircuitGates::collect::<Fp, ConcreteCircuit>().to_string();
+    assert!(
+        gates.contains(&format!("Total gates: {}\n", expected_gates)),
+        "expected {} gates, got:\n{}",
+        expected_gates,
+        gates
+    );
+}
+
+// The custom constraints of the circuit, as (gate name, expression) pairs in
+// the order they're created, e.g. for example2:
+//   [("add", "S0 * (A0@0 + A0@1 - A0@2)")]
+// S0 is the first selector, A0@1 the first advice column at Rotation::next()
+// (F and I for the fixed and instance ones). A named constraint is listed as
+// "gate: constraint", like lucas' "lucas: companion".
+//
+// The gates of a ConstraintSystem aren't public in halo2_proofs 0.1.0, this
+// reads them from what CircuitGates prints, which configures a fresh system.
+pub fn describe_gates<F: FieldExt, ConcreteCircuit: Circuit<F>>() -> Vec<(String, String)> {
+    let gates = CircuitGates::collect::<F, ConcreteCircuit>().to_string();
+
+    let mut described = vec![];
+    let mut gate = String::new();
+    let mut constraint = None;
+    for line in gates
+        .lines()
+        .take_while(|line| !line.starts_with("Total gates:"))
+    {
+        if let Some(expression) = line.strip_prefix("  ") {
+            // the expression of the named constraint on the line above
+            let name = format!("{}: {}", gate, constraint.take().unwrap_or_default());
+            described.push((name, expression.to_string()));
+        } else if let Some(rest) = line.strip_prefix("- ") {
+            match rest.strip_suffix(':') {
+                Some(name) => constraint = Some(name.to_string()),
+                None => described.push((gate.clone(), rest.to_string())),
+            }
+        } else {
+            gate = line.trim_end_matches(':').to_string();
+        }
+    }
+    described
+}
+
+// lives in fibonacci.rs, since example2 uses it outside of tests too
+pub use crate::fibonacci::usable_rows;
+
+// Lays the circuit out and returns every region with the rows it was placed
+// on, in the order the regions were assigned, e.g. for example2:
+//   [("fibonacci region", 0..10)]
+// The range covers the rows of the cells assigned and the selectors enabled in
+// the region. Anything assigned outside of a region (lookup tables, constants)
+// isn't listed.
+//
+// k comes before the circuit, as in MockProver::run, CircuitCost::measure and
+// the other helpers here, so a call can be switched between them without
+// reordering the arguments.
+pub fn dump_regions<ConcreteCircuit: Circuit<Fp>>(
+    k: u32,
+    circuit: &ConcreteCircuit,
+) -> Vec<(String, Range<usize>)> {
+    let (_, recorder) = record_layout(k, circuit).expect("the circuit should lay out");
+    recorder
+        .regions
+        .into_iter()
+        .map(|(name, rows)| (name, rows.unwrap_or(0..0)))
+        .collect()
+}
+
+// Lays the circuit out and returns the rows, in increasing order, on which the
+// selector picked from its config is enabled, e.g. for example2 with 10 terms:
+//   enabled_rows(4, &circuit, |config| config.selector) == [0, 1, ..., 7]
+// The rows are absolute, whatever region enabled them.
+pub fn enabled_rows<ConcreteCircuit: Circuit<Fp>>(
+    k: u32,
+    circuit: &ConcreteCircuit,
+    selector: impl FnOnce(&ConcreteCircuit::Config) -> Selector,
+) -> Vec<usize> {
+    let (config, recorder) = record_layout(k, circuit).expect("the circuit should lay out");
+    let selector = selector(&config);
+
+    let mut rows: Vec<_> = recorder
+        .selectors
+        .into_iter()
+        .filter(|(enabled, _)| *enabled == selector)
+        .map(|(_, row)| row)
+        .collect();
+    rows.sort_unstable();
+    rows.dedup();
+    rows
+}
+
+fn record_layout<ConcreteCircuit: Circuit<Fp>>(
+    k: u32,
+    circuit: &ConcreteCircuit,
+) -> Result<(ConcreteCircuit::Config, RegionRecorder), Error> {
+    let mut cs = ConstraintSystem::default();
+    let config = ConcreteCircuit::configure(&mut cs);
+    // the constant columns aren't public in halo2_proofs 0.1.0, but the
+    // constants are assigned outside of the regions anyway, any column will do
+    let constants = vec![cs.fixed_column()];
+
+    let mut recorder = RegionRecorder {
+        k,
+        usable_rows: usable_rows(k, &cs),
+        regions: vec![],
+        current: None,
+        selectors: vec![],
+        instance_rows: vec![],
+    };
+    ConcreteCircuit::FloorPlanner::synthesize(&mut recorder, circuit, config.clone(), constants)?;
+    Ok((config, recorder))
+}
+
+// What run_checked found wrong with the public inputs, before running MockProver.
+#[derive(Debug)]
+pub enum InstanceError {
+    // there must be one Vec<Fp> per instance column of the circuit
+    WrongColumnCount {
+        expected: usize,
+        got: usize,
+    },
+    // the circuit constrains the first `expected` rows of the column, but it
+    // only has `got` values
+    ColumnTooShort {
+        column: usize,
+        expected: usize,
+        got: usize,
+    },
+    // the instances fit the circuit, MockProver::run failed for another reason
+    Prover(Error),
+}
+
+// MockProver::run, after checking the public inputs against the circuit.
+//
+// MockProver only says Error::InvalidInstances when the number of columns is
+// off, and pads a short column with zeros: the output constrained to a
+// missing row then fails as a copy to a zero instead of being reported as
+// missing. run_checked counts the instance columns from configure and the
+// rows the circuit constrains from a layout pass, and says what is wrong
+// first, e.g. for example2 without public input:
+//   WrongColumnCount { expected: 1, got: 0 }
+pub fn run_checked<ConcreteCircuit: Circuit<Fp>>(
+    k: u32,
+    circuit: &ConcreteCircuit,
+    instances: Vec<Vec<Fp>>,
+) -> Result<MockProver<Fp>, InstanceError> {
+    // Column::index() isn't public in halo2_proofs 0.1.0, but two columns of
+    // the same kind are equal when their indexes are, and the j-th instance
+    // column created in an empty ConstraintSystem has index j. A new column in
+    // the circuit's system gets the next index, i.e. the number of them.
+    let mut cs = ConstraintSystem::<Fp>::default();
+    ConcreteCircuit::configure(&mut cs);
+    let next = cs.instance_column();
+    let mut empty = ConstraintSystem::<Fp>::default();
+    let by_index: Vec<Column<Any>> = (0..)
+        .map(|_| empty.instance_column())
+        .take_while(|column| *column != next)
+        .map(Column::<Any>::from)
+        .collect();
+    let columns = by_index.len();
+
+    if instances.len() != columns {
+        return Err(InstanceError::WrongColumnCount {
+            expected: columns,
+            got: instances.len(),
+        });
+    }
+
+    let (_, recorder) = record_layout(k, circuit).map_err(InstanceError::Prover)?;
+    for (column, values) in instances.iter().enumerate() {
+        let expected = recorder
+            .instance_rows
+            .iter()
+            .find(|(recorded, _)| *recorded == by_index[column])
+            .map_or(0, |(_, rows)| *rows);
+        if values.len() < expected {
+            return Err(InstanceError::ColumnTooShort {
+                column,
+                expected,
+                got: values.len(),
+            });
+        }
+    }
+
+    MockProver::run(k, circuit, instances).map_err(InstanceError::Prover)
+}
+
+// the Assignment dump_regions, enabled_rows and run_checked synthesize the
+// circuit with, it only keeps track of the rows each region touches, of the
+// selectors and of the instance rows constrained
+struct RegionRecorder {
+    k: u32,
+    usable_rows: usize,
+    regions: Vec<(String, Option<Range<usize>>)>,
+    // the index in `regions` of the region being assigned
+    current: Option<usize>,
+    // every selector enabled, with its row
+    selectors: Vec<(Selector, usize)>,
+    // the instance columns constrained, with the number of rows up to the
+    // last one constrained
+    instance_rows: Vec<(Column<Any>, usize)>,
+}
+
+impl RegionRecorder {
+    fn touch(&mut self, row: usize) -> Result<(), Error> {
+        if row >= self.usable_rows {
+            return Err(Error::NotEnoughRowsAvailable { current_k: self.k });
+        }
+        if let Some(index) = self.current {
+            let rows = &mut self.regions[index].1;
+            *rows = Some(match rows.take() {
+                Some(rows) => rows.start.min(row)..rows.end.max(row + 1),
+                None => row..row + 1,
+            });
+        }
+        Ok(())
+    }
+}
+
+impl<F: FieldExt> Assignment<F> for RegionRecorder {
+    fn enter_region<NR, N>(&mut self, name_fn: N)
+    where
+        NR: Into<String>,
+        N: FnOnce() -> NR,
+    {
+        self.current = Some(self.regions.len());
+        self.regions.push((name_fn().into(), None));
+    }
+
+    fn exit_region(&mut self) {
+        self.current = None;
+    }
+
+    fn enable_selector<A, AR>(&mut self, _: A, selector: &Selector, row: usize) -> Result<(), Error>
+    where
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        self.selectors.push((*selector, row));
+        self.touch(row)
+    }
+
+    fn query_instance(&self, _: Column<Instance>, _: usize) -> Result<Option<F>, Error> {
+        Ok(None)
+    }
+
+    fn assign_advice<V, VR, A, AR>(
+        &mut self,
+        _: A,
+        _: Column<Advice>,
+        row: usize,
+        _: V,
+    ) -> Result<(), Error>
+    where
+        V: FnOnce() -> Result<VR, Error>,
+        VR: Into<Assigned<F>>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        self.touch(row)
+    }
+
+    fn assign_fixed<V, VR, A, AR>(
+        &mut self,
+        _: A,
+        _: Column<Fixed>,
+        row: usize,
+        _: V,
+    ) -> Result<(), Error>
+    where
+        V: FnOnce() -> Result<VR, Error>,
+        VR: Into<Assigned<F>>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        self.touch(row)
+    }
+
+    fn copy(
+        &mut self,
+        left: Column<Any>,
+        left_row: usize,
+        right: Column<Any>,
+        right_row: usize,
+    ) -> Result<(), Error> {
+        for (column, row) in [(left, left_row), (right, right_row)] {
+            if *column.column_type() != Any::Instance {
+                continue;
+            }
+            match self.instance_rows.iter_mut().find(|(c, _)| *c == column) {
+                Some((_, rows)) => *rows = (*rows).max(row + 1),
+                None => self.instance_rows.push((column, row + 1)),
+            }
+        }
+        Ok(())
+    }
+
+    fn fill_from_row(
+        &mut self,
+        _: Column<Fixed>,
+        _: usize,
+        _: Option<Assigned<F>>,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn push_namespace<NR, N>(&mut self, _: N)
+    where
+        NR: Into<String>,
+        N: FnOnce() -> NR,
+    {
+    }
+
+    fn pop_namespace(&mut self, _: Option<String>) {}
+}
+
+// The n-th term of the Fibonacci sequence starting from a, b (a is the first
+// term), computed in plain Rust to derive the expected public output, e.g.
+// nth_fibonacci(1, 2, 10) = 89 is the output of the examples with iter_num 10.
+// Panics on overflow, the tests keep the terms well below the field modulus.
+pub fn nth_fibonacci(a: u64, b: u64, n: usize) -> u64 {
+    assert!(n > 0, "the sequence starts at the first term");
+
+    if n == 1 {
+        return a;
+    }
+
+    // stops at the n-th term, so a term that fits in u64 never overflows
+    let (mut a, mut b) = (a, b);
+    for _ in 2..n {
+        (a, b) = (b, a + b);
+    }
+    b
+}
+
+// the seed of every randomized test, so a failure shows up again on the next run
+#[cfg(test)]
+pub const TEST_SEED: u64 = 0x5eed;
+
+// the rng for tests drawing random witnesses, the same values on every run
+#[cfg(test)]
+pub fn test_rng() -> impl rand_core::RngCore {
+    use rand_core::SeedableRng;
+    rand_chacha::ChaCha20Rng::seed_from_u64(TEST_SEED)
+}
+
+// proptest's config with `cases` cases, seeded with TEST_SEED. proptest picks
+// a random seed otherwise, and only saves the failing cases it finds.
+#[cfg(test)]
+pub fn proptest_config(cases: u32) -> proptest::prelude::ProptestConfig {
+    proptest::prelude::ProptestConfig {
+        cases,
+        rng_seed: proptest::test_runner::RngSeed::Fixed(TEST_SEED),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        assert_cost, assert_unsatisfied, describe_gates, dump_regions, enabled_rows, nth_fibonacci,
+        report_cost, run_checked, test_rng, usable_rows, InstanceError,
+    };
+    use crate::fibonacci::{example1, example2, lucas};
+    use halo2_proofs::{
+        pasta::{Fp, Fq},
+        plonk::{Circuit, ConstraintSystem, Error},
+    };
+
+    #[test]
+    fn test_report_cost() {
+        let cost1 = report_cost(4, &example1::MyCircuit::<Fp, 10>::default());
+        assert!(format!("{:?}", cost1).contains("advice_columns: 3,"));
+
+        let cost2 = report_cost(4, &example2::MyCircuit::<Fp>::default());
+        assert!(format!("{:?}", cost2).contains("advice_columns: 1,"));
+    }
+
+    #[test]
+    fn test_example2_cost() {
+        // the one advice column and the "add" gate
+        assert_cost(4, &example2::MyCircuit::<Fp>::default(), 1, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "expected 2 advice columns")]
+    fn test_assert_cost_failed() {
+        assert_cost(4, &example2::MyCircuit::<Fp>::default(), 2, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "expected the circuit to be unsatisfied")]
+    fn test_assert_unsatisfied_failed() {
+        // the right output for a = 1, b = 2
+        let circuit = example2::MyCircuit::<Fp>::from_u64(1, 2);
+        assert_unsatisfied(4, &circuit, vec![vec![Fp::from(nth_fibonacci(1, 2, 10))]]);
+    }
+
+    #[test]
+    fn test_describe_gates() {
+        // a + b - c over three rows of the one column, under the selector
+        assert_eq!(
+            describe_gates::<Fp, example2::MyCircuit<Fp>>(),
+            vec![("add".to_string(), "S0 * (A0@0 + A0@1 - A0@2)".to_string())]
+        );
+
+        // the same relation over three columns of one row
+        assert_eq!(
+            describe_gates::<Fp, example1::MyCircuit<Fp, 10>>(),
+            vec![("add".to_string(), "S0 * (A0@0 + A1@0 - A2@0)".to_string())]
+        );
+        // the field doesn't change the shape of the constraints
+        assert_eq!(
+            describe_gates::<Fq, example1::MyCircuit<Fq, 10>>(),
+            describe_gates::<Fp, example1::MyCircuit<Fp, 10>>()
+        );
+
+        assert_eq!(
+            describe_gates::<Fp, lucas::MyCircuit<Fp>>(),
+            vec![
+                (
+                    "lucas: fibonacci".to_string(),
+                    "S0 * (A0@0 + A0@1 - A0@2)".to_string()
+                ),
+                (
+                    "lucas: companion".to_string(),
+                    "S0 * (A0@0 + A0@2 - A1@1)".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_usable_rows() {
+        let mut cs = ConstraintSystem::<Fp>::default();
+        example2::MyCircuit::<Fp>::configure(&mut cs);
+
+        // 5 blinding rows and 1 for the permutation, out of 16
+        assert_eq!(cs.blinding_factors(), 5);
+        assert_eq!(cs.minimum_rows(), 8);
+        assert_eq!(usable_rows(4, &cs), 10);
+        assert_eq!(usable_rows(3, &cs), 2);
+        // 2^64 doesn't fit in a usize, no overflow
+        assert_eq!(usable_rows(64, &cs), usize::MAX - 6);
+
+        println!(
+            "example2 at k = 4: {} usable rows, {} reserved",
+            usable_rows(4, &cs),
+            cs.blinding_factors() + 1
+        );
+    }
+
+    #[test]
+    fn test_dump_regions() {
+        let circuit = example2::MyCircuit {
+            a: Some(Fp::from(1)),
+            b: Some(Fp::from(2)),
+            iter_num: 10,
+        };
+        assert_eq!(
+            dump_regions(4, &circuit),
+            vec![("fibonacci region".to_string(), 0..10)]
+        );
+
+        // one region per row, one after the other
+        let regions = dump_regions(4, &example1::MyCircuit::<Fp, 10>::default());
+        assert_eq!(regions.len(), 8);
+        assert_eq!(regions[0], ("first row".to_string(), 0..1));
+        assert_eq!(regions[7], ("row".to_string(), 7..8));
+    }
+
+    #[test]
+    fn test_enabled_rows() {
+        // one region per row, each enabling the selector on its first row
+        let circuit = example1::MyCircuit::<Fp, 10>::default();
+        let rows = enabled_rows(4, &circuit, |config| config.selector);
+        assert_eq!(rows, (0..8).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_run_checked() {
+        let circuit = example2::MyCircuit::<Fp>::from_u64(1, 2);
+        let out = Fp::from(nth_fibonacci(1, 2, 10));
+
+        run_checked(4, &circuit, vec![vec![out]])
+            .unwrap()
+            .assert_satisfied();
+
+        assert!(matches!(
+            run_checked(4, &circuit, vec![]),
+            Err(InstanceError::WrongColumnCount {
+                expected: 1,
+                got: 0
+            })
+        ));
+        assert!(matches!(
+            run_checked(4, &circuit, vec![vec![]]),
+            Err(InstanceError::ColumnTooShort {
+                column: 0,
+                expected: 1,
+                got: 0
+            })
+        ));
+
+        // a, b and the output
+        let circuit = example2::PublicInputsCircuit::<Fp> {
+            a: Some(Fp::from(1)),
+            b: Some(Fp::from(2)),
+            iter_num: 10,
+        };
+        assert!(matches!(
+            run_checked(5, &circuit, vec![vec![out]]),
+            Err(InstanceError::ColumnTooShort {
+                column: 0,
+                expected: 3,
+                got: 1
+            })
+        ));
+
+        // too many rows for k = 3
+        assert!(matches!(
+            run_checked(
+                3,
+                &example2::MyCircuit::<Fp>::from_u64(1, 2),
+                vec![vec![out]]
+            ),
+            Err(InstanceError::Prover(Error::NotEnoughRowsAvailable { .. }))
+        ));
+    }
+
+    #[test]
+    fn test_nth_fibonacci() {
+        assert_eq!(nth_fibonacci(1, 2, 1), 1);
+        assert_eq!(nth_fibonacci(1, 2, 2), 2);
+        assert_eq!(nth_fibonacci(1, 2, 10), 89);
+        assert_eq!(nth_fibonacci(1, 1, 10), 55);
+        assert_eq!(nth_fibonacci(0, 1, 11), 55);
+        assert_eq!(nth_fibonacci(1, 1, 20), 6765);
+
+        // F(93), the largest Fibonacci number that fits in u64
+        assert_eq!(nth_fibonacci(0, 1, 94), 12_200_160_415_121_876_738);
+    }
+
+    #[test]
+    fn test_test_rng() {
+        use rand_core::RngCore;
+
+        // random inputs for example2, drawn the same way twice
+        let inputs = || {
+            let mut rng = test_rng();
+            (0..4)
+                .map(|_| {
+                    let (a, b) = (rng.next_u64() % 1000, rng.next_u64() % 1000);
+                    example2::MyCircuit::<Fp>::from_u64(a, b)
+                })
+                .map(|circuit| (circuit.a, circuit.b))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(inputs(), inputs());
+    }
+}
@@ -0,0 +1,115 @@
+use halo2_proofs::{
+    pasta::{EqAffine, Fp},
+    plonk::*,
+    poly::commitment::Params,
+};
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen::prelude::*;
+
+use crate::fibonacci::{
+    example1::compute_witnesses,
+    example2::{min_k_for, MyCircuit},
+    prove,
+};
+
+// Entry points for calling example2 from JavaScript through wasm-bindgen.
+// The bindings are only generated for wasm32, natively these are plain functions.
+// Everything crosses the boundary as plain numbers and bytes: the proof is the
+// transcript produced by `prove::prove`.
+//
+// Keys aren't serializable in halo2_proofs 0.1.0, so both sides regenerate
+// them. They depend on the shape of the table, which is why the verifier needs
+// iter_num too, not only the output.
+
+// the params and the witness-free circuit both sides generate the keys from,
+// None if iter_num is out of range
+fn setup(iter_num: usize) -> Option<(Params<EqAffine>, MyCircuit<Fp>)> {
+    if iter_num < 4 {
+        return None;
+    }
+    let params = Params::new(min_k_for(iter_num)?);
+    let circuit = MyCircuit {
+        iter_num,
+        ..MyCircuit::default()
+    };
+    Some((params, circuit))
+}
+
+fn try_prove(
+    params: &Params<EqAffine>,
+    circuit: MyCircuit<Fp>,
+    a: u64,
+    b: u64,
+) -> Result<Vec<u8>, Error> {
+    let iter_num = circuit.iter_num;
+    let vk = keygen_vk(params, &circuit)?;
+    let pk = keygen_pk(params, vk, &circuit)?;
+
+    let circuit = MyCircuit {
+        a: Some(Fp::from(a)),
+        b: Some(Fp::from(b)),
+        iter_num,
+    };
+    let public = [compute_witnesses(Fp::from(a), Fp::from(b), iter_num)[iter_num - 1]];
+    prove::prove(params, &pk, circuit, &public)
+}
+
+// proves the iter_num-th term starting from a, b and returns the proof bytes.
+// An empty Vec means there's no proof, i.e. iter_num is below 4 or the table
+// doesn't fit in 2^MAX_K rows; a panic would trap the wasm instance instead of
+// throwing on the JS side.
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
+pub fn prove_fibonacci(a: u64, b: u64, iter_num: usize) -> Vec<u8> {
+    match setup(iter_num) {
+        Some((params, circuit)) => try_prove(&params, circuit, a, b).unwrap_or_default(),
+        None => vec![],
+    }
+}
+
+// checks that `proof` shows the iter_num-th term is `out`
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
+pub fn verify_fibonacci(proof: &[u8], out: u64, iter_num: usize) -> bool {
+    let Some((params, circuit)) = setup(iter_num) else {
+        return false;
+    };
+
+    // verifying only needs the vk, not the whole proving key
+    match keygen_vk(&params, &circuit) {
+        Ok(vk) => prove::verify(&params, &vk, proof, &[Fp::from(out)]),
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{prove_fibonacci, verify_fibonacci};
+
+    #[test]
+    fn test_wasm_round_trip() {
+        // 1, 2, 3, 5, 8, 13, 21, 34, 55, 89
+        let proof = prove_fibonacci(1, 2, 10);
+        assert!(verify_fibonacci(&proof, 89, 10));
+        assert!(!verify_fibonacci(&proof, 90, 10));
+    }
+
+    #[test]
+    fn test_wasm_larger_table() {
+        // doesn't fit in 2^4 rows
+        let proof = prove_fibonacci(1, 1, 20);
+        assert!(verify_fibonacci(&proof, 6765, 20));
+    }
+
+    #[test]
+    fn test_wasm_iter_too_small() {
+        // no proof rather than a panic
+        assert!(prove_fibonacci(1, 2, 3).is_empty());
+        assert!(!verify_fibonacci(&[], 3, 3));
+    }
+
+    #[test]
+    fn test_wasm_iter_too_large() {
+        // no k up to MAX_K fits the table, rejected before any allocation
+        assert!(prove_fibonacci(1, 2, usize::MAX).is_empty());
+        assert!(!verify_fibonacci(&[], 3, usize::MAX));
+    }
+}
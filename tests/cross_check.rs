@@ -0,0 +1,31 @@
+// example1 and example2 lay the same sequence out differently (three columns
+// and a copy per row, against one column and rotations), so with the same
+// inputs they must agree on the output.
+
+use fibonacci::fibonacci::{example1, example2};
+use fibonacci::testing::nth_fibonacci;
+use halo2_proofs::{dev::MockProver, pasta::Fp};
+
+#[test]
+fn test_example1_example2_same_output() {
+    let k = 4;
+
+    for (a, b) in [(1, 1), (1, 2), (3, 7)] {
+        let out = Fp::from(nth_fibonacci(a, b, 10));
+
+        let circuit1 = example1::MyCircuit::<Fp, 10>::from_u64(a, b);
+        let prover1 = MockProver::run(k, &circuit1, vec![vec![out]]).unwrap();
+        prover1.assert_satisfied();
+
+        let circuit2 = example2::MyCircuit::<Fp>::from_u64(a, b);
+        let prover2 = MockProver::run(k, &circuit2, vec![vec![out]]).unwrap();
+        prover2.assert_satisfied();
+
+        // and neither accepts the other's output for different inputs
+        let other = Fp::from(nth_fibonacci(a, b + 1, 10));
+        let prover1 = MockProver::run(k, &circuit1, vec![vec![other]]).unwrap();
+        assert!(prover1.verify().is_err());
+        let prover2 = MockProver::run(k, &circuit2, vec![vec![other]]).unwrap();
+        assert!(prover2.verify().is_err());
+    }
+}
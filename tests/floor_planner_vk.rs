@@ -0,0 +1,70 @@
+// The floor planner decides where the regions go, and the verifying key
+// commits to where things are: the fixed columns (the selectors included,
+// which become fixed columns at keygen) and the permutation, i.e. which cells
+// are copies of which. So the same circuit placed by SimpleFloorPlanner and by
+// V1 has the same gates and columns, but not the same key: example2's table
+// starts on row 2 with one and on row 0 with the other, which moves the
+// selector and the copies of a, b and the output.
+//
+// A proof is only valid for the key it was made with, so proofs don't carry
+// over between two builds of a circuit that differ only by their planner.
+
+use fibonacci::fibonacci::{floor_planner::MyCircuit, prove};
+use fibonacci::testing::nth_fibonacci;
+use halo2_proofs::{
+    circuit::{floor_planner::V1, SimpleFloorPlanner},
+    pasta::{EqAffine, Fp},
+    plonk::{keygen_pk, keygen_vk, VerifyingKey},
+    poly::commitment::Params,
+};
+
+const K: u32 = 5;
+
+// VerifyingKey has no PartialEq in halo2_proofs 0.1.0, its pinned form (the
+// part hashed into the transcript) prints every commitment and the whole
+// constraint system
+fn pinned(vk: &VerifyingKey<EqAffine>) -> String {
+    format!("{:?}", vk.pinned())
+}
+
+#[test]
+fn test_vk_differs_across_planners() {
+    let params: Params<EqAffine> = Params::new(K);
+
+    let vk_simple = keygen_vk(&params, &MyCircuit::<Fp, SimpleFloorPlanner>::default()).unwrap();
+    let vk_v1 = keygen_vk(&params, &MyCircuit::<Fp, V1>::default()).unwrap();
+    assert_ne!(pinned(&vk_simple), pinned(&vk_v1));
+
+    // keygen is deterministic, the difference only comes from the planner
+    let vk_again = keygen_vk(&params, &MyCircuit::<Fp, V1>::default()).unwrap();
+    assert_eq!(pinned(&vk_v1), pinned(&vk_again));
+
+    // and only from the placement: the domain and the constraint system,
+    // printed before the commitments, are the same
+    let layout_independent = |pinned: &str| {
+        pinned
+            .split("fixed_commitments")
+            .next()
+            .unwrap()
+            .to_string()
+    };
+    assert_eq!(
+        layout_independent(&pinned(&vk_simple)),
+        layout_independent(&pinned(&vk_v1))
+    );
+}
+
+#[test]
+fn test_proof_not_portable_across_planners() {
+    let params: Params<EqAffine> = Params::new(K);
+    let public_input = vec![Fp::from(nth_fibonacci(1, 2, 10))];
+
+    let circuit = MyCircuit::<Fp, SimpleFloorPlanner>::from_u64(1, 2);
+    let vk = keygen_vk(&params, &circuit).unwrap();
+    let pk = keygen_pk(&params, vk, &circuit).unwrap();
+    let proof = prove::prove(&params, &pk, circuit, &public_input).unwrap();
+    assert!(prove::verify(&params, pk.get_vk(), &proof, &public_input));
+
+    let vk_v1 = keygen_vk(&params, &MyCircuit::<Fp, V1>::default()).unwrap();
+    assert!(!prove::verify(&params, &vk_v1, &proof, &public_input));
+}
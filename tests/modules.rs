@@ -0,0 +1,33 @@
+// every Fibonacci example can be configured from outside the crate
+
+use fibonacci::fibonacci::{
+    example1, example2, example2_wide, example3, fixed_enable, floor_planner, lucas, multi_region,
+    packed, prev_rotation, public_seed, single_region, squared, two_sequences, weighted,
+};
+use halo2_proofs::{
+    pasta::Fp,
+    plonk::{Circuit, ConstraintSystem},
+};
+
+fn configure<C: Circuit<Fp>>() {
+    C::configure(&mut ConstraintSystem::default());
+}
+
+#[test]
+fn test_modules_reachable() {
+    configure::<example1::MyCircuit<Fp, 10>>();
+    configure::<example2::MyCircuit<Fp>>();
+    configure::<example2_wide::MyCircuit<Fp>>();
+    configure::<example3::MyCircuit<Fp>>();
+    configure::<fixed_enable::MyCircuit<Fp>>();
+    configure::<floor_planner::MyCircuit<Fp>>();
+    configure::<lucas::MyCircuit<Fp>>();
+    configure::<multi_region::MyCircuit<Fp>>();
+    configure::<packed::MyCircuit<Fp>>();
+    configure::<prev_rotation::MyCircuit<Fp>>();
+    configure::<public_seed::MyCircuit>();
+    configure::<single_region::MyCircuit<Fp, 10>>();
+    configure::<squared::MyCircuit<Fp>>();
+    configure::<two_sequences::MyCircuit<Fp>>();
+    configure::<weighted::MyCircuit<Fp>>();
+}
@@ -0,0 +1,83 @@
+// The examples used from outside the crate, the way a downstream user would:
+// the circuits as they are, and a chip in a circuit of its own.
+
+use fibonacci::fibonacci::{
+    example2::{FiboChip, FiboConfig, MyCircuit},
+    prove::prove_and_verify,
+    FiboInstructions,
+};
+use fibonacci::testing::nth_fibonacci;
+use halo2_proofs::{
+    circuit::{Layouter, SimpleFloorPlanner},
+    dev::MockProver,
+    pasta::Fp,
+    plonk::{Circuit, ConstraintSystem, Error},
+};
+
+#[test]
+fn test_prove_example2() {
+    let circuit = MyCircuit::<Fp>::from_u64(1, 2);
+
+    let public_input = vec![Fp::from(nth_fibonacci(1, 2, 10))];
+    assert!(prove_and_verify(4, circuit, public_input).unwrap());
+}
+
+// exposes the 5th and the 8th terms, with example2's chip
+#[derive(Default)]
+struct TwoTermsCircuit {
+    a: Option<Fp>,
+    b: Option<Fp>,
+}
+
+impl Circuit<Fp> for TwoTermsCircuit {
+    type Config = FiboConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+        let advice = meta.advice_column();
+        let instance = meta.instance_column();
+        let constant = meta.fixed_column();
+        FiboChip::configure(meta, advice, instance, constant)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<Fp>,
+    ) -> Result<(), Error> {
+        let chip = FiboChip::construct(config);
+        for (row, iter_num) in [5, 8].into_iter().enumerate() {
+            let out = chip.assign(
+                layouter.namespace(|| "fibonacci table"),
+                self.a,
+                self.b,
+                iter_num,
+            )?;
+            chip.expose_public(layouter.namespace(|| "out"), &out, row)?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_reuse_chip() {
+    let circuit = TwoTermsCircuit {
+        a: Some(Fp::from(1)),
+        b: Some(Fp::from(2)),
+    };
+
+    let public_input = vec![
+        Fp::from(nth_fibonacci(1, 2, 5)),
+        Fp::from(nth_fibonacci(1, 2, 8)),
+    ];
+    let prover = MockProver::run(5, &circuit, vec![public_input]).unwrap();
+    prover.assert_satisfied();
+
+    let public_input = vec![Fp::from(nth_fibonacci(1, 2, 8)), Fp::one()];
+    let prover = MockProver::run(5, &circuit, vec![public_input]).unwrap();
+    assert!(prover.verify().is_err());
+}